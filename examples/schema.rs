@@ -1,19 +1,13 @@
-use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
-use std::env::current_dir;
-use std::fs::create_dir_all;
+use cosmwasm_schema::write_api;
 
 use xworks_freelance_contract::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
 
+// Writes ./schema/xworks-freelance-contract.json plus one file per message under
+// ./schema/raw/, so message and response changes show up as schema diffs in review
 fn main() {
-    let mut out_dir = current_dir().unwrap();
-    out_dir.push("schema");
-    create_dir_all(&out_dir).unwrap();
-    remove_schemas(&out_dir).unwrap();
-
-    // Message schemas for frontend integration
-    export_schema(&schema_for!(InstantiateMsg), &out_dir);
-    export_schema(&schema_for!(ExecuteMsg), &out_dir);
-    export_schema(&schema_for!(QueryMsg), &out_dir);
-
-    println!("Schemas generated successfully in ./schema/ directory");
+    write_api! {
+        instantiate: InstantiateMsg,
+        execute: ExecuteMsg,
+        query: QueryMsg,
+    }
 }
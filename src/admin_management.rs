@@ -3,98 +3,174 @@ use crate::error::ContractError;
 use crate::msg::{AuditLogsResponse, ConfigResponse, SecurityMetricsResponse};
 use crate::security::RateLimitAction;
 use crate::state::{
-    AuditLog, RateLimitState, SecurityMetrics, AUDIT_LOGS, BLOCKED_ADDRESSES, CONFIG,
-    RATE_LIMITS, SECURITY_METRICS,
+    AdminRole, AuditLog, SecurityMetrics, ACCRUED_FEES, ADMINS, AUDIT_LOGS, BLOCKED_ADDRESSES,
+    CONFIG, PENDING_ADMIN, RATE_LIMITS, SECURITY_METRICS,
 };
-use crate::{build_success_response, ensure_admin};
+use crate::helpers::{escrow_denom, payout_msg};
+use crate::{build_success_response, ensure_admin, ensure_role};
 use cosmwasm_std::{
-    Addr, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Timestamp, Uint128,
+    Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Timestamp,
+    Uint128,
 };
+use cw20::{BalanceResponse, Cw20QueryMsg, Denom};
 
-/// Update contract configuration
-#[allow(clippy::too_many_arguments)]
-pub fn execute_update_config(
+/// Grant an admin role to an address (super admins only)
+pub fn execute_grant_role(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    admin: Option<Addr>,
-    platform_fee_percent: Option<u64>,
-    max_job_duration_days: Option<u64>,
-    max_bounty_duration_days: Option<u64>,
-    max_bounty_submissions: Option<u32>,
-    min_job_budget: Option<Uint128>,
-    max_job_budget: Option<Uint128>,
-    min_bounty_reward: Option<Uint128>,
-    max_bounty_reward: Option<Uint128>,
+    address: String,
+    role: AdminRole,
 ) -> Result<Response, ContractError> {
     // Apply admin check
     ensure_admin!(deps, info);
 
-    // Load current config
-    let mut config = CONFIG.load(deps.storage)?;
+    let grantee = deps.api.addr_validate(&address)?;
+    ADMINS.save(deps.storage, &grantee, &role)?;
 
-    // Update fields if provided
-    if let Some(new_admin) = admin {
-        config.admin = new_admin;
-    }
+    Ok(build_success_response!(
+        "grant_role",
+        0u64,
+        &info.sender,
+        "address" => grantee,
+        "role" => format!("{:?}", role)
+    ))
+}
 
-    if let Some(new_fee) = platform_fee_percent {
-        if new_fee > 10 {
-            return Err(ContractError::InvalidInput {
-                error: "Platform fee cannot exceed 10%".to_string(),
-            });
-        }
-        config.platform_fee_percent = new_fee;
+/// Propose a new contract admin; only the current admin can hand over control
+pub fn execute_propose_admin(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_admin: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
     }
 
-    if let Some(new_duration) = max_job_duration_days {
-        if new_duration == 0 || new_duration > 365 {
-            return Err(ContractError::InvalidInput {
-                error: "Max job duration must be between 1 and 365 days".to_string(),
-            });
-        }
-        config.max_job_duration_days = new_duration;
-    }
+    let pending = deps.api.addr_validate(&new_admin)?;
+    PENDING_ADMIN.save(deps.storage, &pending)?;
 
-    if let Some(new_duration) = max_bounty_duration_days {
-        if new_duration == 0 || new_duration > 365 {
-            return Err(ContractError::InvalidInput {
-                error: "Max bounty duration must be between 1 and 365 days".to_string(),
-            });
-        }
-        // config.max_bounty_duration_days = new_duration; // Field doesn't exist in Config
-        // Use max_job_duration_days instead or comment out bounty-specific duration
-    }
+    Ok(build_success_response!(
+        "propose_admin",
+        0u64,
+        &info.sender,
+        "pending_admin" => pending
+    ))
+}
 
-    if let Some(new_max) = max_bounty_submissions {
-        if new_max == 0 || new_max > 1000 {
-            return Err(ContractError::InvalidInput {
-                error: "Max bounty submissions must be between 1 and 1000".to_string(),
-            });
-        }
-        // config.max_bounty_submissions = new_max; // Field doesn't exist in Config
+/// Complete an admin transfer; only the proposed admin can accept
+pub fn execute_accept_admin(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let pending = PENDING_ADMIN.may_load(deps.storage)?;
+    if pending.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
     }
 
-    if let Some(_new_min) = min_job_budget {
-        // config.min_job_budget = new_min; // Field doesn't exist in Config
-    }
+    let mut config = CONFIG.load(deps.storage)?;
+    let previous_admin = std::mem::replace(&mut config.admin, info.sender.clone());
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_ADMIN.remove(deps.storage);
 
-    if let Some(_new_max) = max_job_budget {
-        // config.max_job_budget = new_max; // Field doesn't exist in Config
-    }
+    Ok(build_success_response!(
+        "accept_admin",
+        0u64,
+        &info.sender,
+        "previous_admin" => previous_admin
+    ))
+}
 
-    if let Some(_new_min) = min_bounty_reward {
-        // config.min_bounty_reward = new_min; // Field doesn't exist in Config
+/// Revoke the admin role held by an address (super admins only)
+pub fn execute_revoke_role(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    // Apply admin check
+    ensure_admin!(deps, info);
+
+    let grantee = deps.api.addr_validate(&address)?;
+    if !ADMINS.has(deps.storage, &grantee) {
+        return Err(ContractError::InvalidInput {
+            error: "Address has no admin role".to_string(),
+        });
     }
+    ADMINS.remove(deps.storage, &grantee);
 
-    if let Some(_new_max) = max_bounty_reward {
-        // config.max_bounty_reward = new_max; // Field doesn't exist in Config
+    Ok(build_success_response!(
+        "revoke_role",
+        0u64,
+        &info.sender,
+        "address" => grantee
+    ))
+}
+
+/// Sweep accrued platform fees to the treasury (super admins only)
+pub fn execute_withdraw_fees(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    denom: Option<Denom>,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    // Apply admin check
+    ensure_admin!(deps, info);
+
+    let config = CONFIG.load(deps.storage)?;
+    let denom = denom.unwrap_or_else(|| Denom::Native(config.denom.clone()));
+    let key = escrow_denom(&denom);
+    let accrued = ACCRUED_FEES.may_load(deps.storage, &key)?.unwrap_or_default();
+    let amount = amount.unwrap_or(accrued);
+
+    if amount.is_zero() {
+        return Err(ContractError::InvalidInput {
+            error: "No fees to withdraw".to_string(),
+        });
+    }
+    if amount > accrued {
+        return Err(ContractError::InsufficientFunds {
+            expected: amount.to_string(),
+            actual: accrued.to_string(),
+        });
     }
 
+    // Never pay out more than the contract actually holds in that denom
+    let balance = match &denom {
+        Denom::Native(denom) => {
+            deps.querier
+                .query_balance(&env.contract.address, denom)?
+                .amount
+        }
+        Denom::Cw20(token) => {
+            let balance: BalanceResponse = deps.querier.query_wasm_smart(
+                token,
+                &Cw20QueryMsg::Balance {
+                    address: env.contract.address.to_string(),
+                },
+            )?;
+            balance.balance
+        }
+    };
+    if balance < amount {
+        return Err(ContractError::InsufficientFunds {
+            expected: amount.to_string(),
+            actual: balance.to_string(),
+        });
+    }
 
-    CONFIG.save(deps.storage, &config)?;
+    let remaining = accrued.checked_sub(amount)?;
+    ACCRUED_FEES.save(deps.storage, &key, &remaining)?;
 
-    Ok(build_success_response!("update_config", 0u64, &info.sender))
+    Ok(build_success_response!(
+        "withdraw_fees",
+        0u64,
+        &info.sender,
+        "denom" => key,
+        "amount" => amount,
+        "treasury" => config.treasury,
+        "remaining" => remaining
+    )
+    .add_message(payout_msg(&denom, &config.treasury, amount)?))
 }
 
 /// Pause the contract
@@ -159,8 +235,8 @@ pub fn execute_block_address(
     address: String,
     reason: String,
 ) -> Result<Response, ContractError> {
-    // Apply admin check
-    ensure_admin!(deps, info);
+    // Apply moderator check
+    ensure_role!(deps, info, AdminRole::Moderator);
 
     let addr_to_block = deps.api.addr_validate(&address)?;
 
@@ -211,8 +287,8 @@ pub fn execute_unblock_address(
     info: MessageInfo,
     address: String,
 ) -> Result<Response, ContractError> {
-    // Apply admin check
-    ensure_admin!(deps, info);
+    // Apply moderator check
+    ensure_role!(deps, info, AdminRole::Moderator);
 
     let addr_to_unblock = deps.api.addr_validate(&address)?;
 
@@ -260,8 +336,8 @@ pub fn execute_reset_rate_limit(
     address: String,
     action: RateLimitAction,
 ) -> Result<Response, ContractError> {
-    // Apply admin check
-    ensure_admin!(deps, info);
+    // Apply moderator check
+    ensure_role!(deps, info, AdminRole::Moderator);
 
     let addr_to_reset = deps.api.addr_validate(&address)?;
     let action_str = format!("{:?}", action);
@@ -359,15 +435,3 @@ pub fn query_is_address_blocked(deps: Deps, address: String) -> StdResult<bool>
     let blocked = BLOCKED_ADDRESSES.may_load(deps.storage, &addr)?.is_some();
     Ok(blocked)
 }
-
-/// Query rate limit status for an address
-pub fn query_rate_limit_status(
-    deps: Deps,
-    address: String,
-    action: RateLimitAction,
-) -> StdResult<Option<RateLimitState>> {
-    let addr = deps.api.addr_validate(&address)?;
-    let action_str = format!("{:?}", action);
-    let rate_limit = RATE_LIMITS.may_load(deps.storage, (&addr, action_str.as_str()))?;
-    Ok(rate_limit)
-}
@@ -1,32 +1,165 @@
 use crate::contract_helpers::*;
 use crate::error::ContractError;
 use crate::helpers::{
-    ensure_not_paused, get_future_timestamp, validate_budget, validate_duration,
+    describe_funds, ensure_cw20_allowed, ensure_denom_allowed, ensure_escrows_not_frozen, ensure_not_paused, get_future_timestamp, payout_msg,
+    record_escrow_event, remove_entity_hashes, sync_active_bounty, throttle_edit, validate_duration,
 };
 use crate::hash_utils::{
     create_content_hash, create_bounty_content_bundle, create_bounty_submission_content_bundle,
 };
+use crate::escrow::accrue_platform_fee;
 use crate::job_management::calculate_platform_fee;
 use crate::msg::{BountiesResponse, BountyResponse, BountySubmissionsResponse, WinnerSelection};
 use crate::security::{check_rate_limit, reentrancy_guard, RateLimitAction};
 use crate::state::{
-    BountySubmissionStatus, BountyStatus, Bounty, BountySubmission, RewardTier,
-    BOUNTIES, BOUNTY_SUBMISSIONS, BOUNTY_SUBMISSIONS_BY_BOUNTY, ESCROWS, EscrowState,
+    AdminRole, BountyContest, BountySubmissionStatus, BountyStatus, Bounty, BountySubmission, Config, RewardTier,
+    BOUNTIES, BOUNTY_SUBMISSIONS, BOUNTY_SUBMISSIONS_BY_BOUNTY, ESCROWS, EscrowEventKind, EscrowState,
+    FEATURED_BOUNTIES,
     DisputeStatus, CONFIG, NEXT_BOUNTY_ID, NEXT_BOUNTY_SUBMISSION_ID, CONTENT_HASHES,
-    HASH_TO_ENTITY, ENTITY_TO_HASH,
+    HASH_TO_ENTITY, ENTITY_TO_HASH, USER_BOUNTY_SUBMISSIONS,
 };
 use crate::hash_utils::ContentHash;
-use crate::{apply_security_checks, build_success_response, validate_content_inputs};
+use crate::{apply_security_checks, build_success_response, ensure_role, validate_content_inputs};
 use cosmwasm_std::{
-    coins, BankMsg, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Uint128,
+    Addr, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Timestamp, Uint128,
 };
+use cw20::Denom;
 
-/// Create a new bounty
+/// Most transfers a reward release may emit: one per reward tier plus the poster's refund
+pub const MAX_BOUNTY_PAYOUTS: usize = 50;
+
+/// Create a new bounty funded with native tokens
 #[allow(clippy::too_many_arguments)]
 pub fn execute_create_bounty(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    title: String,
+    description: String,
+    requirements: Vec<String>,
+    total_reward: Uint128,
+    category: String,
+    skills_required: Vec<String>,
+    submission_deadline_days: u64,
+    review_period_days: u64,
+    max_winners: u64,
+    reward_distribution: Vec<crate::msg::RewardTierInput>,
+    documents: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    // A paused contract takes precedence over funding errors
+    ensure_not_paused(deps.as_ref())?;
+
+    // Validate payment
+    if info.funds.len() != 1 || info.funds[0].amount != total_reward {
+        return Err(ContractError::InvalidFunds {
+            expected: format!("{} in a single denom", total_reward),
+            received: describe_funds(&info.funds),
+        });
+    }
+    let config = CONFIG.load(deps.storage)?;
+    ensure_denom_allowed(&config.allowed_denoms, &info.funds[0].denom)?;
+    let reward_denom = Denom::Native(info.funds[0].denom.clone());
+
+    create_bounty_internal(
+        deps,
+        env,
+        info,
+        reward_denom,
+        title,
+        description,
+        requirements,
+        total_reward,
+        category,
+        skills_required,
+        submission_deadline_days,
+        review_period_days,
+        max_winners,
+        reward_distribution,
+        documents,
+    )
+}
+
+/// Create a new bounty funded through a CW20 `Send`; `info.sender` is the token contract
+#[allow(clippy::too_many_arguments)]
+pub fn execute_create_bounty_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    poster: String,
+    total_reward: Uint128,
+    title: String,
+    description: String,
+    requirements: Vec<String>,
+    category: String,
+    skills_required: Vec<String>,
+    submission_deadline_days: u64,
+    review_period_days: u64,
+    max_winners: u64,
+    reward_distribution: Vec<crate::msg::RewardTierInput>,
+    documents: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure_cw20_allowed(&config.allowed_cw20_tokens, &info.sender)?;
+    let reward_denom = Denom::Cw20(info.sender);
+    let poster_info = MessageInfo {
+        sender: deps.api.addr_validate(&poster)?,
+        funds: vec![],
+    };
+
+    create_bounty_internal(
+        deps,
+        env,
+        poster_info,
+        reward_denom,
+        title,
+        description,
+        requirements,
+        total_reward,
+        category,
+        skills_required,
+        submission_deadline_days,
+        review_period_days,
+        max_winners,
+        reward_distribution,
+        documents,
+    )
+}
+
+/// Default for `Config::max_bounty_reward`: one million XION
+pub const DEFAULT_MAX_BOUNTY_REWARD: Uint128 = Uint128::new(1_000_000_000_000);
+
+fn validate_bounty_reward(config: &Config, total_reward: Uint128) -> Result<(), ContractError> {
+    if total_reward < config.min_escrow_amount {
+        return Err(ContractError::BountyRewardBelowMinimum {
+            min: config.min_escrow_amount,
+        });
+    }
+    if total_reward > config.max_bounty_reward {
+        return Err(ContractError::BountyRewardAboveMaximum {
+            max: config.max_bounty_reward,
+        });
+    }
+    Ok(())
+}
+
+pub fn validate_bounty_reward_bounds(
+    min_reward: Uint128,
+    max_reward: Uint128,
+) -> Result<(), ContractError> {
+    if max_reward < min_reward {
+        return Err(ContractError::InvalidInput {
+            error: "Max bounty reward cannot be below the minimum escrow amount".to_string(),
+        });
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn create_bounty_internal(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    reward_denom: Denom,
     title: String,
     description: String,
     requirements: Vec<String>,
@@ -39,19 +172,23 @@ pub fn execute_create_bounty(
     reward_distribution: Vec<crate::msg::RewardTierInput>,
     documents: Option<Vec<String>>,
 ) -> Result<Response, ContractError> {
+    // Reward bounds are checked before the rate limiter records the attempt
+    let config = CONFIG.load(deps.storage)?;
+    validate_bounty_reward(&config, total_reward)?;
+
     // Apply security checks
     apply_security_checks!(deps, env, info, RateLimitAction::CreateBounty);
 
-    // Load configuration
-    let config = CONFIG.load(deps.storage)?;
-
     // Validate inputs
     validate_content_inputs!(&title, &description);
-    validate_budget(total_reward)?;
     validate_duration(submission_deadline_days, config.max_job_duration_days)?;
     validate_string_field(&category, "Category", 1, 50)?;
-    validate_collection_size(&skills_required, "Skills required", 1, 20)?;
-    validate_collection_size(&requirements, "Requirements", 1, 10)?;
+    validate_skills(&skills_required)?;
+    validate_requirements(&requirements)?;
+    validate_labels(&category, &skills_required)?;
+    if let Some(docs) = &documents {
+        validate_uri_list(docs, "Documents", MAX_DOCUMENTS, MAX_URI_LENGTH)?;
+    }
 
     if max_winners == 0 || max_winners > 100 {
         return Err(ContractError::InvalidInput {
@@ -59,10 +196,7 @@ pub fn execute_create_bounty(
         });
     }
 
-    // Validate payment
-    if info.funds.len() != 1 || info.funds[0].amount != total_reward {
-        return Err(ContractError::InvalidFunds {});
-    }
+    let reward_tiers = compute_reward_tiers(total_reward, &reward_distribution)?;
 
     // Get next bounty ID
     let bounty_id = NEXT_BOUNTY_ID.load(deps.storage)?;
@@ -97,21 +231,12 @@ pub fn execute_create_bounty(
     HASH_TO_ENTITY.save(deps.storage, &content_hash_str, &entity_key)?;
     ENTITY_TO_HASH.save(deps.storage, &entity_key, &content_hash_str)?;
 
-    // Convert reward distribution
-    let mut reward_tiers = Vec::new();
-    for (i, tier_input) in reward_distribution.iter().enumerate() {
-        reward_tiers.push(RewardTier {
-            position: (i + 1) as u64,
-            percentage: tier_input.percentage,
-            amount: total_reward.multiply_ratio(tier_input.percentage, 100u64),
-        });
-    }
-
     let bounty = Bounty {
         id: bounty_id,
         poster: info.sender.clone(),
         total_reward,
-        submission_deadline: get_future_timestamp(env.block.time, submission_deadline_days),
+        reward_denom,
+        submission_deadline: get_future_timestamp(env.block.time, submission_deadline_days)?,
         review_period_days,
         max_winners,
         reward_distribution: reward_tiers,
@@ -121,12 +246,16 @@ pub fn execute_create_bounty(
         total_submissions: 0,
         selected_winners: vec![],
         escrow_id: None,
+        is_featured: false,
+        winners_selected_at: None,
+        contest: None,
         
         // 🌐 Off-chain content reference
         content_hash,
     };
 
     BOUNTIES.save(deps.storage, bounty_id, &bounty)?;
+    sync_active_bounty(deps.storage, &bounty)?;
 
     // Create escrow using EscrowState schema
     let escrow_id = format!("bounty_{}", bounty_id);
@@ -142,9 +271,18 @@ pub fn execute_create_bounty(
         dispute_status: crate::state::DisputeStatus::None,
         dispute_raised_at: None,
         dispute_deadline: None,
+        denom: bounty.reward_denom.clone(),
     };
 
     ESCROWS.save(deps.storage, &escrow_id, &escrow)?;
+    record_escrow_event(
+        deps.storage,
+        &escrow_id,
+        EscrowEventKind::Funded,
+        &info.sender,
+        total_reward,
+        env.block.time,
+    )?;
 
     Ok(build_success_response!(
         "create_bounty",
@@ -179,11 +317,44 @@ pub fn execute_edit_bounty(
     validate_user_authorization(&bounty.poster, &info.sender)?;
     validate_bounty_status_for_operation(&bounty.status, &[BountyStatus::Open], "edit")?;
 
-    let _config = CONFIG.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    if let Some(reqs) = &requirements {
+        validate_requirements(reqs)?;
+    }
+    if let Some(docs) = &documents {
+        validate_uri_list(docs, "Documents", MAX_DOCUMENTS, MAX_URI_LENGTH)?;
+    }
+
+    // Validate the new deadline before touching any state
+    let new_deadline = submission_deadline_days
+        .map(|days| get_future_timestamp(env.block.time, days))
+        .transpose()?;
+    if let Some(deadline) = new_deadline {
+        if deadline <= env.block.time {
+            return Err(ContractError::InvalidDeadline {});
+        }
+        // Submitters already relied on the current window
+        if bounty.total_submissions > 0 && deadline < bounty.submission_deadline {
+            return Err(ContractError::InvalidInput {
+                error: "Cannot shorten the submission deadline once submissions exist"
+                    .to_string(),
+            });
+        }
+    }
+
+    // Throttle repeated edits so content hashes can't be churned
+    throttle_edit(
+        deps.storage,
+        &format!("bounty_{}", bounty_id),
+        config.min_edit_interval_seconds,
+        env.block.time,
+    )?;
 
     // If any content fields are being updated, we need to create a new content bundle
     let content_needs_update = title.is_some() || description.is_some() || 
                               requirements.is_some() || documents.is_some();
+    let mut new_hash = None;
 
     if content_needs_update {
         // Get current content hash to retrieve existing content
@@ -230,11 +401,12 @@ pub fn execute_edit_bounty(
 
         // Update bounty's content hash
         bounty.content_hash = new_content_hash;
+        new_hash = Some(new_content_hash_str);
     }
 
     // Update non-content fields
-    if let Some(new_deadline_days) = submission_deadline_days {
-        bounty.submission_deadline = get_future_timestamp(env.block.time, new_deadline_days);
+    if let Some(deadline) = new_deadline {
+        bounty.submission_deadline = deadline;
     }
 
     if let Some(new_review_period) = review_period_days {
@@ -244,11 +416,51 @@ pub fn execute_edit_bounty(
     bounty.updated_at = env.block.time;
     BOUNTIES.save(deps.storage, bounty_id, &bounty)?;
 
-    Ok(build_success_response!(
-        "edit_bounty",
-        bounty_id,
-        &info.sender
-    ))
+    let mut response = build_success_response!("edit_bounty", bounty_id, &info.sender);
+    if let Some(hash) = new_hash {
+        response = response.add_attribute("content_hash", hash);
+    }
+
+    Ok(response)
+}
+
+/// Split `total_reward` across the tiers so the amounts sum exactly to it;
+/// rounding dust goes to first place
+fn compute_reward_tiers(
+    total_reward: Uint128,
+    reward_distribution: &[crate::msg::RewardTierInput],
+) -> Result<Vec<RewardTier>, ContractError> {
+    let total_percentage: u64 = reward_distribution.iter().map(|tier| tier.percentage).sum();
+    if reward_distribution.is_empty() || total_percentage != 100 {
+        return Err(ContractError::InvalidInput {
+            error: "Reward distribution percentages must sum to 100".to_string(),
+        });
+    }
+    // Leave room for the poster's refund within the payout budget
+    if reward_distribution.len() >= MAX_BOUNTY_PAYOUTS {
+        return Err(ContractError::InvalidInput {
+            error: format!("At most {} reward tiers allowed", MAX_BOUNTY_PAYOUTS - 1),
+        });
+    }
+
+    let mut reward_tiers: Vec<RewardTier> = reward_distribution
+        .iter()
+        .enumerate()
+        .map(|(i, tier_input)| RewardTier {
+            position: (i + 1) as u64,
+            percentage: tier_input.percentage,
+            amount: total_reward.multiply_ratio(tier_input.percentage, 100u64),
+        })
+        .collect();
+
+    let allocated = reward_tiers
+        .iter()
+        .try_fold(Uint128::zero(), |sum, tier| sum.checked_add(tier.amount))?;
+    reward_tiers[0].amount = reward_tiers[0]
+        .amount
+        .checked_add(total_reward.checked_sub(allocated)?)?;
+
+    Ok(reward_tiers)
 }
 
 /// Cancel a bounty
@@ -260,6 +472,7 @@ pub fn execute_cancel_bounty(
 ) -> Result<Response, ContractError> {
     // Apply security checks
     apply_security_checks!(deps, env, info, RateLimitAction::CancelBounty);
+    ensure_escrows_not_frozen(deps.as_ref())?;
 
     // Load and validate bounty
     let mut bounty = BOUNTIES.load(deps.storage, bounty_id)?;
@@ -270,6 +483,24 @@ pub fn execute_cancel_bounty(
     bounty.status = BountyStatus::Cancelled;
     bounty.updated_at = env.block.time;
     BOUNTIES.save(deps.storage, bounty_id, &bounty)?;
+    sync_active_bounty(deps.storage, &bounty)?;
+
+    // Flag outstanding submissions so submitters can see the bounty was withdrawn
+    let submission_ids = BOUNTY_SUBMISSIONS_BY_BOUNTY
+        .may_load(deps.storage, bounty_id)?
+        .unwrap_or_default();
+    let mut cancelled_submissions = 0u64;
+    for submission_id in submission_ids {
+        let mut submission = BOUNTY_SUBMISSIONS.load(deps.storage, submission_id)?;
+        if submission.status == BountySubmissionStatus::Winner
+            || submission.status == BountySubmissionStatus::Withdrawn
+        {
+            continue;
+        }
+        submission.status = BountySubmissionStatus::BountyCancelled;
+        BOUNTY_SUBMISSIONS.save(deps.storage, submission_id, &submission)?;
+        cancelled_submissions += 1;
+    }
 
     // Release escrow
     let escrow_id = format!("bounty_{}", bounty_id);
@@ -277,19 +508,150 @@ pub fn execute_cancel_bounty(
         escrow.released = true;
 
         ESCROWS.save(deps.storage, &escrow_id, &escrow)?;
+        record_escrow_event(
+            deps.storage,
+            &escrow_id,
+            EscrowEventKind::Refunded,
+            &info.sender,
+            bounty.total_reward,
+            env.block.time,
+        )?;
     }
 
-    let mut response = build_success_response!("cancel_bounty", bounty_id, &info.sender);
+    let mut response = build_success_response!(
+        "cancel_bounty",
+        bounty_id,
+        &info.sender,
+        "cancelled_submissions" => cancelled_submissions,
+        "status" => bounty.status.as_code()
+    );
 
-    // Add bank message to return funds
-    response = response.add_message(BankMsg::Send {
-        to_address: info.sender.to_string(),
-        amount: coins(bounty.total_reward.u128(), "uusdc"),
-    });
+    // Return funds in the bounty's reward token
+    response = response.add_message(payout_msg(
+        &bounty.reward_denom,
+        &info.sender,
+        bounty.total_reward,
+    )?);
 
     Ok(response)
 }
 
+/// Refund a bounty whose review window lapsed without any winners being selected
+pub fn execute_expire_bounty(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    bounty_id: u64,
+) -> Result<Response, ContractError> {
+    // Apply security checks
+    apply_security_checks!(deps, env, info, RateLimitAction::ExpireBounty);
+    ensure_escrows_not_frozen(deps.as_ref())?;
+
+    // Load and validate bounty
+    let mut bounty = BOUNTIES.load(deps.storage, bounty_id)?;
+    validate_bounty_status_for_operation(
+        &bounty.status,
+        &[BountyStatus::Open, BountyStatus::InReview],
+        "expire",
+    )?;
+    if !bounty.selected_winners.is_empty() {
+        return Err(ContractError::InvalidInput {
+            error: "Bounty already has selected winners".to_string(),
+        });
+    }
+
+    let review_ends = bounty
+        .submission_deadline
+        .plus_seconds(bounty.review_period_days * 24 * 60 * 60);
+    if env.block.time <= review_ends {
+        return Err(ContractError::InvalidInput {
+            error: "Bounty review period has not ended yet".to_string(),
+        });
+    }
+
+    // Update bounty status
+    bounty.status = BountyStatus::Expired;
+    bounty.updated_at = env.block.time;
+    BOUNTIES.save(deps.storage, bounty_id, &bounty)?;
+    sync_active_bounty(deps.storage, &bounty)?;
+
+    // Flag outstanding submissions as never awarded
+    let submission_ids = BOUNTY_SUBMISSIONS_BY_BOUNTY
+        .may_load(deps.storage, bounty_id)?
+        .unwrap_or_default();
+    let mut expired_submissions = 0u64;
+    for submission_id in submission_ids {
+        let mut submission = BOUNTY_SUBMISSIONS.load(deps.storage, submission_id)?;
+        if submission.status == BountySubmissionStatus::Withdrawn {
+            continue;
+        }
+        submission.status = BountySubmissionStatus::BountyExpired;
+        BOUNTY_SUBMISSIONS.save(deps.storage, submission_id, &submission)?;
+        expired_submissions += 1;
+    }
+
+    // Release escrow
+    let escrow_id = format!("bounty_{}", bounty_id);
+    if let Ok(mut escrow) = ESCROWS.load(deps.storage, &escrow_id) {
+        escrow.released = true;
+        ESCROWS.save(deps.storage, &escrow_id, &escrow)?;
+        record_escrow_event(
+            deps.storage,
+            &escrow_id,
+            EscrowEventKind::Refunded,
+            &info.sender,
+            bounty.total_reward,
+            env.block.time,
+        )?;
+    }
+
+    // Return funds to the poster in the bounty's reward token
+    Ok(build_success_response!(
+        "expire_bounty",
+        bounty_id,
+        &info.sender,
+        "poster" => bounty.poster.to_string(),
+        "expired_submissions" => expired_submissions,
+        "status" => bounty.status.as_code()
+    )
+    .add_message(payout_msg(
+        &bounty.reward_denom,
+        &bounty.poster,
+        bounty.total_reward,
+    )?))
+}
+
+/// Feature or unfeature a bounty (moderators only)
+pub fn execute_set_bounty_featured(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    bounty_id: u64,
+    featured: bool,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref())?;
+    ensure_role!(deps, info, AdminRole::Moderator);
+
+    let mut bounty = BOUNTIES.load(deps.storage, bounty_id)?;
+    bounty.is_featured = featured;
+    bounty.updated_at = env.block.time;
+    BOUNTIES.save(deps.storage, bounty_id, &bounty)?;
+
+    // Keep the featured index in sync with the flag
+    if featured {
+        FEATURED_BOUNTIES.save(deps.storage, bounty_id, &true)?;
+    } else {
+        FEATURED_BOUNTIES.remove(deps.storage, bounty_id);
+    }
+
+    Ok(build_success_response!(
+        "set_bounty_featured",
+        bounty_id,
+        &info.sender,
+        "featured" => featured
+    ))
+}
+
 /// Submit to a bounty
 #[allow(clippy::too_many_arguments)]
 pub fn execute_submit_to_bounty(
@@ -405,6 +767,18 @@ pub fn execute_submit_to_bounty(
 
     BOUNTY_SUBMISSIONS.save(deps.storage, submission_id, &submission)?;
 
+    let mut user_submissions = USER_BOUNTY_SUBMISSIONS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    user_submissions.push(submission_id);
+    USER_BOUNTY_SUBMISSIONS.save(deps.storage, &info.sender, &user_submissions)?;
+
+    let mut bounty_submissions = BOUNTY_SUBMISSIONS_BY_BOUNTY
+        .may_load(deps.storage, bounty_id)?
+        .unwrap_or_default();
+    bounty_submissions.push(submission_id);
+    BOUNTY_SUBMISSIONS_BY_BOUNTY.save(deps.storage, bounty_id, &bounty_submissions)?;
+
     // Update bounty submission count
     bounty.total_submissions += 1; // Use total_submissions instead of submission_count
     BOUNTIES.save(deps.storage, bounty_id, &bounty)?;
@@ -453,6 +827,7 @@ pub fn execute_review_bounty_submission(
     submission.score = score.map(|s| s as u8); // Convert u32 to u8
 
     // If reviewer notes are provided, we need to update the content hash
+    let mut new_hash = None;
     if reviewer_notes.is_some() {
         // Get current content hash and update with review notes
         let entity_key = format!("bounty_submission_{}", submission_id);
@@ -486,16 +861,22 @@ pub fn execute_review_bounty_submission(
 
         // Update submission's content hash
         submission.content_hash = new_content_hash;
+        new_hash = Some(new_hash_str);
     }
 
     BOUNTY_SUBMISSIONS.save(deps.storage, submission_id, &submission)?;
 
-    Ok(build_success_response!(
+    let mut response = build_success_response!(
         "review_bounty_submission",
         submission_id,
         &info.sender,
         "status" => status_str
-    ))
+    );
+    if let Some(hash) = new_hash {
+        response = response.add_attribute("content_hash", hash);
+    }
+
+    Ok(response)
 }
 
 /// Select bounty winners
@@ -532,8 +913,6 @@ pub fn execute_select_bounty_winners(
     }
 
     // Validate all submissions exist and belong to this bounty
-    let mut winner_addresses = Vec::new();
-    let mut reward_distribution = Vec::new();
     let mut total_distributed = Uint128::zero();
 
     for selection in &winner_selections {
@@ -555,16 +934,16 @@ pub fn execute_select_bounty_winners(
             Uint128::zero()
         };
         
-        winner_addresses.push(submission.submitter.clone());
-        reward_distribution.push(reward);
         total_distributed += reward;
     }
 
-    // Update bounty status
+    // Update bounty status; rewards wait out the review period so results can be contested
     bounty.status = BountyStatus::Completed;
     bounty.selected_winners = winner_selections.iter().map(|s| s.submission_id).collect();
+    bounty.winners_selected_at = Some(env.block.time);
     bounty.updated_at = env.block.time;
     BOUNTIES.save(deps.storage, bounty_id, &bounty)?;
+    sync_active_bounty(deps.storage, &bounty)?;
 
     // Update winning submissions
     for selection in &winner_selections {
@@ -574,23 +953,18 @@ pub fn execute_select_bounty_winners(
         BOUNTY_SUBMISSIONS.save(deps.storage, selection.submission_id, &submission)?;
     }
 
-    // Create response with bank messages for winners
-    let mut response = Response::new()
+    Ok(Response::new()
         .add_attribute("method", "select_bounty_winners")
         .add_attribute("bounty_id", bounty_id.to_string())
         .add_attribute("winners_count", winner_selections.len().to_string())
-        .add_attribute("total_distributed", total_distributed.to_string());
-
-    for (i, &reward) in reward_distribution.iter().enumerate() {
-        if reward > Uint128::zero() {
-            response = response.add_message(BankMsg::Send {
-                to_address: winner_addresses[i].to_string(),
-                amount: coins(reward.u128(), "uusdc"),
-            });
-        }
-    }
+        .add_attribute("total_distributed", total_distributed.to_string())
+        .add_attribute("status", bounty.status.as_code())
+        .add_attribute("releasable_at", review_period_end(&bounty, env.block.time)?.to_string()))
+}
 
-    Ok(response)
+/// End of the window in which a winner selection can be contested
+fn review_period_end(bounty: &Bounty, selected_at: Timestamp) -> Result<Timestamp, ContractError> {
+    get_future_timestamp(selected_at, bounty.review_period_days)
 }
 
 /// Edit a bounty submission
@@ -666,16 +1040,24 @@ pub fn execute_withdraw_bounty_submission(
         return Err(ContractError::Unauthorized {});
     }
 
-    // Can't withdraw if already reviewed or selected as winner
-    if submission.status == BountySubmissionStatus::Winner {
+    // Withdrawal closes once review starts or the submission window ends
+    if submission.status != BountySubmissionStatus::Submitted {
+        return Err(ContractError::InvalidInput {
+            error: "Can only withdraw submissions that are still pending review".to_string(),
+        });
+    }
+
+    let bounty = BOUNTIES.load(deps.storage, submission.bounty_id)?;
+    if env.block.time >= bounty.submission_deadline {
         return Err(ContractError::InvalidInput {
-            error: "Cannot withdraw a winning submission".to_string(),
+            error: "Submission deadline has passed".to_string(),
         });
     }
 
     // Update submission status
     submission.status = BountySubmissionStatus::Withdrawn;
     BOUNTY_SUBMISSIONS.save(deps.storage, submission_id, &submission)?;
+    remove_entity_hashes(deps.storage, &format!("bounty_submission_{}", submission_id))?;
 
     // Remove from bounty submissions index
     let bounty_id = submission.bounty_id;
@@ -729,9 +1111,18 @@ pub fn execute_create_bounty_escrow(
         dispute_status: DisputeStatus::None,
         dispute_raised_at: None,
         dispute_deadline: None,
+        denom: bounty.reward_denom.clone(),
     };
 
     ESCROWS.save(deps.storage, &escrow_id, &escrow)?;
+    record_escrow_event(
+        deps.storage,
+        &escrow_id,
+        EscrowEventKind::Funded,
+        &info.sender,
+        bounty.total_reward,
+        env.block.time,
+    )?;
 
     Ok(Response::new()
         .add_attribute("method", "create_bounty_escrow")
@@ -749,11 +1140,147 @@ pub fn execute_release_bounty_rewards(
 ) -> Result<Response, ContractError> {
     // Apply security checks
     apply_security_checks!(deps, env, info, RateLimitAction::ReleaseBountyRewards);
+    ensure_escrows_not_frozen(deps.as_ref())?;
 
     // Load and validate bounty
     let bounty = BOUNTIES.load(deps.storage, bounty_id)?;
-    validate_user_authorization(&bounty.poster, &info.sender)?;
+    validate_bounty_releasable(deps.as_ref(), &bounty)?;
+
+    // Losing submitters get the full review period to contest the selection
+    if bounty.contest.is_some() {
+        return Err(ContractError::BountyContested {});
+    }
+    match bounty.winners_selected_at {
+        // Payouts are fixed once the period lapses, so anyone can push them out
+        // and a poster who walks away can't strand the winners' rewards
+        Some(selected_at) => {
+            if env.block.time < review_period_end(&bounty, selected_at)? {
+                return Err(ContractError::DisputePeriodActive {});
+            }
+        }
+        None => validate_user_authorization(&bounty.poster, &info.sender)?,
+    }
+
+    release_bounty_rewards(deps, &env, &info.sender, &bounty, "release_bounty_rewards")
+}
+
+/// Challenge a winner selection during the review period, holding the rewards for admin review
+pub fn execute_contest_bounty_result(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    bounty_id: u64,
+    reason: String,
+) -> Result<Response, ContractError> {
+    // Contests count against the daily dispute limit
+    apply_security_checks!(deps, env, info, RateLimitAction::RaiseDispute);
+
+    if reason.trim().is_empty() || reason.len() > 1000 {
+        return Err(ContractError::InvalidInput {
+            error: "Contest reason must be between 1-1000 characters".to_string(),
+        });
+    }
+
+    let mut bounty = BOUNTIES.load(deps.storage, bounty_id)?;
+    validate_bounty_releasable(deps.as_ref(), &bounty)?;
+    if bounty.contest.is_some() {
+        return Err(ContractError::BountyContested {});
+    }
+    let selected_at = bounty
+        .winners_selected_at
+        .ok_or_else(|| ContractError::InvalidInput {
+            error: "Bounty has no winner selection to contest".to_string(),
+        })?;
+    if env.block.time >= review_period_end(&bounty, selected_at)? {
+        return Err(ContractError::InvalidInput {
+            error: "Bounty review period has ended".to_string(),
+        });
+    }
 
+    // Only someone who competed can contest the outcome
+    let is_submitter = BOUNTY_SUBMISSIONS_BY_BOUNTY
+        .may_load(deps.storage, bounty_id)?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|submission_id| BOUNTY_SUBMISSIONS.load(deps.storage, submission_id))
+        .collect::<StdResult<Vec<_>>>()?
+        .iter()
+        .any(|submission| {
+            submission.submitter == info.sender
+                && submission.status != BountySubmissionStatus::Withdrawn
+        });
+    if !is_submitter {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    bounty.contest = Some(BountyContest {
+        submitter: info.sender.clone(),
+        reason,
+        contested_at: env.block.time,
+    });
+    bounty.updated_at = env.block.time;
+    BOUNTIES.save(deps.storage, bounty_id, &bounty)?;
+
+    Ok(build_success_response!(
+        "contest_bounty_result",
+        bounty_id,
+        &info.sender
+    ))
+}
+
+/// Settle a contested bounty: uphold pays the selected winners now, overturn reopens selection
+pub fn execute_resolve_bounty_contest(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    bounty_id: u64,
+    uphold_selection: bool,
+) -> Result<Response, ContractError> {
+    apply_security_checks!(deps, env, info, RateLimitAction::ResolveDispute);
+    ensure_role!(deps, info, AdminRole::DisputeResolver);
+
+    let mut bounty = BOUNTIES.load(deps.storage, bounty_id)?;
+    validate_bounty_releasable(deps.as_ref(), &bounty)?;
+    let contest = bounty.contest.take().ok_or_else(|| ContractError::InvalidInput {
+        error: "Bounty result is not contested".to_string(),
+    })?;
+
+    if uphold_selection {
+        ensure_escrows_not_frozen(deps.as_ref())?;
+        bounty.updated_at = env.block.time;
+        BOUNTIES.save(deps.storage, bounty_id, &bounty)?;
+        let response =
+            release_bounty_rewards(deps, &env, &info.sender, &bounty, "resolve_bounty_contest")?;
+        return Ok(response
+            .add_attribute("contested_by", contest.submitter.to_string())
+            .add_attribute("uphold_selection", "true"));
+    }
+
+    // Overturned: the winners go back under review and the poster selects again
+    for submission_id in std::mem::take(&mut bounty.selected_winners) {
+        let mut submission = BOUNTY_SUBMISSIONS.load(deps.storage, submission_id)?;
+        submission.status = BountySubmissionStatus::UnderReview;
+        submission.winner_position = None;
+        BOUNTY_SUBMISSIONS.save(deps.storage, submission_id, &submission)?;
+    }
+    bounty.status = BountyStatus::Open;
+    bounty.winners_selected_at = None;
+    bounty.updated_at = env.block.time;
+    BOUNTIES.save(deps.storage, bounty_id, &bounty)?;
+    sync_active_bounty(deps.storage, &bounty)?;
+
+    Ok(build_success_response!(
+        "resolve_bounty_contest",
+        bounty_id,
+        &info.sender,
+        "contested_by" => contest.submitter,
+        "uphold_selection" => false,
+        "status" => bounty.status.as_code()
+    ))
+}
+
+/// Rewards can only be released once, for a completed bounty with winners
+fn validate_bounty_releasable(deps: Deps, bounty: &Bounty) -> Result<(), ContractError> {
     // Check if bounty is completed
     if bounty.status != BountyStatus::Completed {
         return Err(ContractError::InvalidInput {
@@ -768,38 +1295,118 @@ pub fn execute_release_bounty_rewards(
         });
     }
 
-    // Release escrow
+    let escrow_id = format!("bounty_{}", bounty.id);
+    if ESCROWS
+        .may_load(deps.storage, &escrow_id)?
+        .is_some_and(|escrow| escrow.released)
+    {
+        return Err(ContractError::InvalidInput {
+            error: "Bounty rewards already released".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Pay each selected winner their tier and return the remainder to the poster
+fn release_bounty_rewards(
+    deps: DepsMut,
+    env: &Env,
+    actor: &Addr,
+    bounty: &Bounty,
+    method: &str,
+) -> Result<Response, ContractError> {
+    let bounty_id = bounty.id;
+
+    // Release escrow, keeping the fee it was funded with
     let escrow_id = format!("bounty_{}", bounty_id);
+    let mut platform_fee = Uint128::zero();
     if let Ok(mut escrow) = ESCROWS.load(deps.storage, &escrow_id) {
+        platform_fee = escrow.platform_fee;
         escrow.released = true;
         ESCROWS.save(deps.storage, &escrow_id, &escrow)?;
+        record_escrow_event(
+            deps.storage,
+            &escrow_id,
+            EscrowEventKind::Released,
+            actor,
+            bounty.total_reward,
+            env.block.time,
+        )?;
     }
 
-    // Create bank messages for winners
+    // Create payout messages for winners
     let mut response = Response::new()
-        .add_attribute("method", "release_bounty_rewards")
+        .add_attribute("method", method)
         .add_attribute("bounty_id", bounty_id.to_string());
 
-    // Distribute rewards to winners
-    for (i, &submission_id) in bounty.selected_winners.iter().enumerate() {
+    // Distribute rewards to winners; winners beyond the reward tiers receive nothing.
+    // Each awarded tier pays its share of the platform fee, as a job payout does.
+    let mut payouts: Vec<(Addr, Uint128)> = Vec::new();
+    let mut total_distributed = Uint128::zero();
+    let mut fee_accrued = Uint128::zero();
+    for (&submission_id, reward_tier) in bounty
+        .selected_winners
+        .iter()
+        .zip(bounty.reward_distribution.iter())
+    {
         if let Ok(submission) = BOUNTY_SUBMISSIONS.load(deps.storage, submission_id) {
-            if let Some(reward_tier) = bounty.reward_distribution.get(i) {
-                response = response.add_message(BankMsg::Send {
-                    to_address: submission.submitter.to_string(),
-                    amount: coins(reward_tier.amount.u128(), "uusdc"),
-                });
-            }
+            let tier_fee = platform_fee.multiply_ratio(reward_tier.amount, bounty.total_reward);
+            add_payout(
+                &mut payouts,
+                &submission.submitter,
+                reward_tier.amount.checked_sub(tier_fee)?,
+            )?;
+            total_distributed = total_distributed.checked_add(reward_tier.amount)?;
+            fee_accrued = fee_accrued.checked_add(tier_fee)?;
         }
     }
+    accrue_platform_fee(deps.storage, &bounty.reward_denom, fee_accrued)?;
 
-    Ok(response)
+    // Return unclaimed tiers and rounding dust to the poster
+    let remainder = bounty.total_reward.checked_sub(total_distributed)?;
+    add_payout(&mut payouts, &bounty.poster, remainder)?;
+
+    // Tiers are capped at creation, so this only guards against older bounties
+    if payouts.len() > MAX_BOUNTY_PAYOUTS {
+        return Err(ContractError::InvalidInput {
+            error: format!(
+                "Release needs {} transfers, more than the {} allowed",
+                payouts.len(),
+                MAX_BOUNTY_PAYOUTS
+            ),
+        });
+    }
+    for (recipient, amount) in &payouts {
+        response = response.add_message(payout_msg(&bounty.reward_denom, recipient, *amount)?);
+    }
+
+    Ok(response
+        .add_attribute("total_distributed", total_distributed.to_string())
+        .add_attribute("platform_fee", fee_accrued.to_string())
+        .add_attribute("refunded", remainder.to_string()))
+}
+
+/// Fold `amount` into the recipient's transfer, keeping one per recipient and skipping zeros
+fn add_payout(
+    payouts: &mut Vec<(Addr, Uint128)>,
+    recipient: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+    match payouts.iter_mut().find(|(addr, _)| addr == recipient) {
+        Some((_, total)) => *total = total.checked_add(amount)?,
+        None => payouts.push((recipient.clone(), amount)),
+    }
+    Ok(())
 }
 
 // Query functions
 
 /// Query a specific bounty
 pub fn query_bounty(deps: Deps, bounty_id: u64) -> StdResult<BountyResponse> {
-    let bounty = BOUNTIES.load(deps.storage, bounty_id)?;
+    let bounty = BOUNTIES.may_load(deps.storage, bounty_id)?;
     Ok(BountyResponse { bounty })
 }
 
@@ -898,31 +1505,28 @@ pub fn query_user_bounty_submissions(
     deps: Deps,
     user: String,
     status: Option<BountySubmissionStatus>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
 ) -> StdResult<BountySubmissionsResponse> {
     let user_addr = deps.api.addr_validate(&user)?;
+    let limit = limit.unwrap_or(50).min(100) as usize;
 
-    let submissions: Vec<_> = BOUNTY_SUBMISSIONS
-        .range(deps.storage, None, None, Order::Descending)
-        .filter_map(|item| {
-            if let Ok((_, submission)) = item {
-                if submission.submitter == user_addr {
-                    if let Some(ref filter_status) = status {
-                        if &submission.status == filter_status {
-                            Some(submission)
-                        } else {
-                            None
-                        }
-                    } else {
-                        Some(submission)
-                    }
-                } else {
-                    None
-                }
-            } else {
-                None
+    // USER_BOUNTY_SUBMISSIONS is append-only, so ids are already ascending
+    let mut submissions = Vec::new();
+    for id in USER_BOUNTY_SUBMISSIONS
+        .may_load(deps.storage, &user_addr)?
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|&id| start_after.is_none_or(|after| id > after))
+    {
+        let submission = BOUNTY_SUBMISSIONS.load(deps.storage, id)?;
+        if status.as_ref().is_none_or(|status| &submission.status == status) {
+            submissions.push(submission);
+            if submissions.len() == limit {
+                break;
             }
-        })
-        .collect();
+        }
+    }
 
     Ok(BountySubmissionsResponse { submissions })
 }
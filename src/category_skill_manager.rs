@@ -1,7 +1,96 @@
-use cosmwasm_std::{Response, StdResult};
+use crate::msg::{CategoryRegistryResponse, RegistryEntry};
+use crate::state::JOBS_BY_SKILL;
+use cosmwasm_std::{StdResult, Storage};
 
-/// ULTRA-MINIMAL: All category and skill management moved off-chain
-/// This is a placeholder module to maintain module structure
-pub fn placeholder_function() -> StdResult<Response> {
-    Ok(Response::new())
+/// Id assigned to categories outside the registry
+pub const OTHER_CATEGORY_ID: u8 = 99;
+
+/// Canonical category table; ids are stored on-chain so never renumber existing entries
+pub const CATEGORIES: &[(u8, &str)] = &[
+    (1, "Web Development"),
+    (2, "Mobile Development"),
+    (3, "Design"),
+    (4, "Writing"),
+    (5, "Marketing"),
+    (6, "Blockchain"),
+    (7, "Data Science"),
+    (OTHER_CATEGORY_ID, "Other"),
+];
+
+/// Canonical skill table; skills outside it are not tagged on-chain
+pub const SKILLS: &[(u8, &str)] = &[
+    (1, "Rust"),
+    (2, "Solidity"),
+    (3, "JavaScript"),
+    (4, "TypeScript"),
+    (5, "Python"),
+    (6, "React"),
+    (7, "Smart Contracts"),
+    (8, "CosmWasm"),
+    (9, "UI/UX"),
+    (10, "Graphic Design"),
+    (11, "Copywriting"),
+    (12, "SEO"),
+    (13, "Go"),
+    (14, "Mobile"),
+    (15, "DevOps"),
+];
+
+fn lookup(table: &[(u8, &str)], name: &str) -> Option<u8> {
+    let name = name.trim();
+    table
+        .iter()
+        .find(|(_, entry)| entry.eq_ignore_ascii_case(name))
+        .map(|(id, _)| *id)
+}
+
+/// Case-insensitive category lookup, falling back to "Other"
+pub fn category_id(name: &str) -> u8 {
+    lookup(CATEGORIES, name).unwrap_or(OTHER_CATEGORY_ID)
+}
+
+/// Case-insensitive skill lookup; `None` for skills outside the registry
+pub fn skill_id(name: &str) -> Option<u8> {
+    lookup(SKILLS, name)
+}
+
+/// Sorted, de-duplicated tag ids for the registered skills in `skills`
+pub fn skill_tags(skills: &[String]) -> Vec<u8> {
+    let mut tags: Vec<u8> = skills.iter().filter_map(|skill| skill_id(skill)).collect();
+    tags.sort_unstable();
+    tags.dedup();
+    tags
+}
+
+/// Add a job to the skill index under each of its tags
+pub fn index_job_skills(storage: &mut dyn Storage, job_id: u64, tags: &[u8]) -> StdResult<()> {
+    for tag in tags {
+        JOBS_BY_SKILL.save(storage, (*tag, job_id), &true)?;
+    }
+    Ok(())
+}
+
+/// Remove a job from the skill index under each of its tags
+pub fn unindex_job_skills(storage: &mut dyn Storage, job_id: u64, tags: &[u8]) {
+    for tag in tags {
+        JOBS_BY_SKILL.remove(storage, (*tag, job_id));
+    }
+}
+
+fn to_entries(table: &[(u8, &str)]) -> Vec<RegistryEntry> {
+    table
+        .iter()
+        .map(|(id, name)| RegistryEntry {
+            id: *id,
+            name: name.to_string(),
+        })
+        .collect()
+}
+
+/// Full category and skill registry
+pub fn query_category_registry() -> StdResult<CategoryRegistryResponse> {
+    Ok(CategoryRegistryResponse {
+        categories: to_entries(CATEGORIES),
+        skills: to_entries(SKILLS),
+    })
 }
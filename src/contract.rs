@@ -1,50 +1,87 @@
+use crate::admin_management::{
+    execute_accept_admin, execute_grant_role, execute_propose_admin, execute_revoke_role,
+    execute_withdraw_fees,
+};
 use crate::bounty_management::{
-    execute_create_bounty, execute_edit_bounty, execute_submit_to_bounty,
+    execute_create_bounty, execute_create_bounty_cw20, execute_edit_bounty, execute_submit_to_bounty,
     execute_review_bounty_submission, execute_select_bounty_winners, execute_cancel_bounty,
+    execute_expire_bounty,
     execute_edit_bounty_submission, execute_withdraw_bounty_submission,
-    execute_create_bounty_escrow, execute_release_bounty_rewards,
+    execute_contest_bounty_result, execute_create_bounty_escrow, execute_release_bounty_rewards,
+    execute_resolve_bounty_contest, execute_set_bounty_featured,
+    query_user_bounty_submissions, validate_bounty_reward_bounds, DEFAULT_MAX_BOUNTY_REWARD,
 };
+use crate::category_skill_manager::query_category_registry;
 use crate::error::ContractError;
 use crate::escrow::{
-    create_escrow_cw20, create_escrow_native, raise_dispute, refund_escrow, release_escrow,
-    resolve_dispute,
+    appeal_dispute, payout_attributes, claim_dispute_timeout, create_escrow_cw20, create_escrow_native, finalize_dispute, raise_dispute, top_up_escrow,
+    record_payout_stats, refund_escrow, release_escrow, resolve_dispute, settle_platform_fee,
 };
 use crate::helpers::{
-    ensure_not_paused, get_future_timestamp, query_jobs_paginated, query_user_proposals,
-    validate_budget, validate_duration, validate_job_description,
-    validate_job_title,
+    dispute_status_key, ensure_admin, ensure_escrows_not_frozen, ensure_not_paused, ensure_role, escrow_denom, get_future_timestamp, is_expired, payout_msg, query_jobs_paginated, query_user_proposals, record_escrow_event, record_job_assignment, record_job_completion, record_removal, remove_entity_hashes, sync_active_job, validate_allowed_cw20_tokens, validate_allowed_denoms, validate_denom,
+};
+use crate::job_management::{
+    escrow_terms, execute_archive_jobs, execute_edit_job, execute_edit_proposal, execute_submit_proposal,
+    validate_fee_split_bps, validate_max_proposals_per_job, validate_proposal_validity_days,
+    DEFAULT_EDIT_GRACE_PERIOD_DAYS, DEFAULT_JOB_ARCHIVE_RETENTION_DAYS,
+    DEFAULT_MAX_OPEN_JOBS_PER_USER, DEFAULT_MAX_PROPOSALS_PER_JOB, DEFAULT_MIN_EDIT_INTERVAL_SECONDS, DEFAULT_PROPOSAL_VALIDITY_DAYS,
+    DEFAULT_REPUTATION_HALF_LIFE_DAYS,
 };
-use crate::job_management::{execute_edit_job, execute_edit_proposal, execute_submit_proposal};
 use crate::msg::{
-    BountiesResponse, BountyResponse, BountySubmissionResponse, BountySubmissionsResponse,
-    ConfigResponse, DisputeResponse, DisputesResponse, EscrowResponse, ExecuteMsg, InstantiateMsg,
-    JobResponse, JobsResponse, MilestoneInput, PlatformStatsResponse, ProposalResponse,
-    ProposalsResponse, QueryMsg, RatingsResponse, UserStatsResponse,
+    AccruedFeesResponse, BountiesResponse, BountyResponse, BountySubmissionResponse, BountySubmissionsResponse,
+    BountyWinner, BountyWinnersResponse,
+    ConfigResponse, ContractInfoResponse, DisputeDetailResponse, DisputeResponse, DisputesResponse, EscrowHistoryResponse, EscrowResponse, EscrowsResponse,
+    ExecuteMsg, FeePreviewResponse, InstantiateMsg, JobDetailResponse, JobResponse, JobsResponse,
+    ProposalResponse, ProposalsResponse, QueryMsg, RatingsResponse, ReceiveMsg, SolvencyResponse,
+    UserReputationResponse, UserStatsResponse,
+};
+use crate::query_helpers::{
+    query_jobs_by_budget_range, query_jobs_by_skills, query_platform_stats, query_resolve_content_hash, query_user_activity,
+    query_user_jobs,
 };
 use crate::security::{
-    check_rate_limit, reentrancy_guard, validate_job_duration, validate_text_inputs,
-    RateLimitAction,
+    reentrancy_guard, release_reentrancy_guard, with_reentrancy_guard, MAX_JOBS_PER_USER_PER_DAY,
+    REENTRANCY_RELEASE_REPLY_ID,
 };
 use crate::state::{
-    Bounty, BountyStatus, BountySubmission, BountySubmissionStatus, Config, Job, JobStatus,
-    Rating, BLOCKED_ADDRESSES, BOUNTIES, BOUNTY_SUBMISSIONS,
-    BOUNTY_SUBMISSIONS_BY_BOUNTY, CONFIG, DISPUTES, ESCROWS, JOBS,
-    JOB_COUNTER, JOB_PROPOSALS, PROPOSALS, PROPOSAL_COUNTER, RATE_LIMITS, RATINGS,
-    USER_BOUNTY_SUBMISSIONS, USER_PROPOSALS, USER_STATS,
+    AdminRole, ARCHIVED_JOBS, Bounty, BountyStatus, BountySubmission, BountySubmissionStatus, Config, DisputeStatus,
+    EscrowEventKind, EscrowState, Job, JobStatus, Proposal, ProposalStatus, Rating, ReleasePolicy, ACCRUED_FEES, ACTIVE_BOUNTIES, ACTIVE_JOBS,
+    BLOCKED_ADDRESSES, BOUNTIES, BOUNTY_SUBMISSIONS, BOUNTY_SUBMISSIONS_BY_BOUNTY, CONFIG, DISPUTES,
+    DISPUTES_BY_STATUS,
+    ESCROWS, ESCROW_EVENTS, FEATURED_BOUNTIES, JOBS, JOB_COUNTER, JOB_PROPOSALS, PROPOSALS, PROPOSAL_COUNTER,
+    RATE_LIMITS, RATINGS, USER_PROPOSALS, USER_RATINGS, USER_STATS,
+    VERIFIED_USERS,
+};
+use crate::user_management::{
+    execute_set_user_verified, execute_update_user_profile, query_user_profile,
+    query_verified_users,
 };
-use crate::user_management::execute_update_user_profile;
 
 use cosmwasm_std::{
-    entry_point, to_json_binary, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response,
-    StdResult, Uint128,
+    entry_point, from_json, to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo,
+    Reply, Response, StdError, StdResult, Storage, Timestamp, Uint128,
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
+use cw20::{Cw20ReceiveMsg, Denom};
 use cw_storage_plus::Bound;
+use std::collections::BTreeMap;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:xworks-freelance-contract";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// Ratings stay blind until both parties rate or this window lapses
+pub const RATING_REVEAL_PERIOD_DAYS: u64 = 7;
+
+// Upper bound on GetBountiesExpiringSoon's look-ahead
+const MAX_EXPIRY_WINDOW_DAYS: u64 = 365;
+
+// Upper bound on GetJobsByIds' batch size
+const MAX_JOBS_BY_IDS: usize = 50;
+
+// GetUserReputation only weighs this many of a user's latest ratings
+const MAX_REPUTATION_RATINGS: usize = 50;
+
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut,
@@ -64,13 +101,72 @@ pub fn instantiate(
         return Err(ContractError::PlatformFeeTooHigh { max: 10 });
     }
 
+    let denom = msg.denom.unwrap_or_else(|| "uxion".to_string());
+    validate_denom(&denom)?;
+
+    let max_proposals_per_job = msg
+        .max_proposals_per_job
+        .unwrap_or(DEFAULT_MAX_PROPOSALS_PER_JOB);
+    validate_max_proposals_per_job(max_proposals_per_job)?;
+
+    let fee_split_bps = msg.fee_split_bps.unwrap_or(0);
+    validate_fee_split_bps(fee_split_bps)?;
+
+    let min_escrow_amount = msg.min_escrow_amount.unwrap_or(Uint128::new(1000));
+    let max_bounty_reward = msg.max_bounty_reward.unwrap_or(DEFAULT_MAX_BOUNTY_REWARD);
+    validate_bounty_reward_bounds(min_escrow_amount, max_bounty_reward)?;
+
+    let proposal_validity_days = msg
+        .proposal_validity_days
+        .unwrap_or(DEFAULT_PROPOSAL_VALIDITY_DAYS);
+    validate_proposal_validity_days(proposal_validity_days)?;
+
+    let allowed_denoms = msg.allowed_denoms.unwrap_or_else(|| vec![denom.clone()]);
+    validate_allowed_denoms(&allowed_denoms, &denom)?;
+    let allowed_cw20_tokens =
+        validate_allowed_cw20_tokens(deps.api, &msg.allowed_cw20_tokens.unwrap_or_default())?;
+
+    let treasury = match msg.treasury {
+        Some(treasury) => deps.api.addr_validate(&treasury)?,
+        None => admin.clone(),
+    };
+
     let config = Config {
         admin: admin.clone(),
         platform_fee_percent,
-        min_escrow_amount: msg.min_escrow_amount.unwrap_or(Uint128::new(1000)),
+        min_escrow_amount,
         dispute_period_days: msg.dispute_period_days.unwrap_or(7u64),
         max_job_duration_days: msg.max_job_duration_days.unwrap_or(365u64),
+        denom,
+        allowed_denoms,
+        allowed_cw20_tokens,
+        treasury,
+        dispute_timeout_release_to_freelancer: msg
+            .dispute_timeout_release_to_freelancer
+            .unwrap_or(false),
+        max_proposals_per_job,
+        job_archive_retention_days: msg
+            .job_archive_retention_days
+            .unwrap_or(DEFAULT_JOB_ARCHIVE_RETENTION_DAYS),
+        fee_split_bps,
+        max_bounty_reward,
+        edit_grace_period_days: msg
+            .edit_grace_period_days
+            .unwrap_or(DEFAULT_EDIT_GRACE_PERIOD_DAYS),
+        proposal_validity_days,
+        min_edit_interval_seconds: msg
+            .min_edit_interval_seconds
+            .unwrap_or(DEFAULT_MIN_EDIT_INTERVAL_SECONDS),
+        dispute_deposit: msg.dispute_deposit.unwrap_or_default(),
+        reputation_half_life_days: msg
+            .reputation_half_life_days
+            .unwrap_or(DEFAULT_REPUTATION_HALF_LIFE_DAYS),
+        dispute_cooldown_seconds: msg.dispute_cooldown_seconds.unwrap_or_default(),
+        max_open_jobs_per_user: msg
+            .max_open_jobs_per_user
+            .unwrap_or(DEFAULT_MAX_OPEN_JOBS_PER_USER),
         paused: false,
+        escrows_frozen: false,
     };
 
     CONFIG.save(deps.storage, &config)?;
@@ -101,6 +197,30 @@ pub fn execute(
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    // Held until the handler's outgoing messages have run, see with_reentrancy_guard
+    with_reentrancy_guard(deps, |deps| dispatch_execute(deps, env, info, msg))
+}
+
+/// Clears the reentrancy lock once the last message of a guarded `execute` succeeds
+#[entry_point]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        REENTRANCY_RELEASE_REPLY_ID => {
+            release_reentrancy_guard(deps)?;
+            Ok(Response::new().add_attribute("method", "release_reentrancy_guard"))
+        }
+        id => Err(ContractError::InvalidInput {
+            error: format!("Unknown reply id {}", id),
+        }),
+    }
+}
+
+fn dispatch_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
         // 🎯 Job Management (HYBRID)
@@ -119,6 +239,8 @@ pub fn execute(
             is_remote,
             urgency_level,
             off_chain_storage_key,
+            release_policy,
+            denom,
         } => crate::job_management::execute_post_job(
             deps,
             env,
@@ -137,6 +259,52 @@ pub fn execute(
             is_remote,
             urgency_level,
             off_chain_storage_key,
+            release_policy,
+            denom,
+        ),
+        ExecuteMsg::PostJobsBatch { jobs } => {
+            crate::job_management::execute_post_jobs_batch(deps, env, info, jobs)
+        }
+        ExecuteMsg::SaveJobTemplate {
+            name,
+            title,
+            description,
+            category,
+            skills_required,
+            budget,
+            duration_days,
+            experience_level,
+            is_remote,
+            urgency_level,
+        } => crate::job_management::execute_save_job_template(
+            deps,
+            env,
+            info,
+            name,
+            title,
+            description,
+            category,
+            skills_required,
+            budget,
+            duration_days,
+            experience_level,
+            is_remote,
+            urgency_level,
+        ),
+        ExecuteMsg::DeleteJobTemplate { name } => {
+            crate::job_management::execute_delete_job_template(deps, info, name)
+        }
+        ExecuteMsg::PostJobFromTemplate {
+            name,
+            overrides,
+            off_chain_storage_key,
+        } => crate::job_management::execute_post_job_from_template(
+            deps,
+            env,
+            info,
+            name,
+            overrides,
+            off_chain_storage_key,
         ),
 
         ExecuteMsg::EditJob {
@@ -191,7 +359,13 @@ pub fn execute(
             availability,
             off_chain_storage_key,
         ),
+        ExecuteMsg::SetUserVerified { address, verified } => {
+            execute_set_user_verified(deps, env, info, address, verified)
+        }
 
+        ExecuteMsg::ObjectToJobEdits { job_id } => {
+            crate::job_management::execute_object_to_job_edits(deps, env, info, job_id)
+        }
         ExecuteMsg::DeleteJob { job_id } => {
             crate::job_management::execute_delete_job(deps, env, info, job_id)
         }
@@ -210,6 +384,9 @@ pub fn execute(
             agreed_to_terms,
             agreed_to_escrow,
             estimated_hours: _,
+            payout_address,
+            referrer,
+            referral_bps,
             off_chain_storage_key,
         } => execute_submit_proposal(
             deps,
@@ -223,6 +400,9 @@ pub fn execute(
             agreed_to_escrow,
             milestones,
             portfolio_samples,
+            payout_address,
+            referrer,
+            referral_bps,
             off_chain_storage_key,
         ),
 
@@ -244,26 +424,44 @@ pub fn execute(
         ExecuteMsg::WithdrawProposal { proposal_id } => {
             execute_withdraw_proposal(deps, env, info, proposal_id)
         }
+        ExecuteMsg::RejectProposal {
+            proposal_id,
+            reason,
+        } => execute_reject_proposal(deps, env, info, proposal_id, reason),
 
         ExecuteMsg::AcceptProposal {
             job_id,
             proposal_id,
         } => execute_accept_proposal(deps, env, info, job_id, proposal_id),
+        ExecuteMsg::ReassignJob {
+            job_id,
+            new_proposal_id,
+        } => execute_reassign_job(deps, env, info, job_id, new_proposal_id),
 
         // Escrow Management
-        ExecuteMsg::CreateEscrow { job_id } => {
-            // Legacy support - create native escrow with job budget
-            let _job = JOBS.load(deps.storage, job_id)?;
-            create_escrow_native(deps, env, info, job_id)
-        }
+        ExecuteMsg::CreateEscrow {
+            job_id,
+            refund_overpayment,
+        } => execute_legacy_create_escrow(
+            deps,
+            env,
+            info,
+            job_id,
+            refund_overpayment.unwrap_or(false),
+        ),
         ExecuteMsg::FundEscrow { escrow_id: _ } => Err(ContractError::InvalidInput {
-            error: "FundEscrow is deprecated. Use CreateEscrowNative or CreateEscrowCw20 instead"
+            error: "FundEscrow is deprecated. Use CreateEscrowNative or a CW20 fund_job_escrow hook instead"
                 .to_string(),
         }),
+        ExecuteMsg::TopUpEscrow { job_id } => top_up_escrow(deps, env, info, job_id),
         ExecuteMsg::ReleaseEscrow { escrow_id } => release_escrow(deps, env, info, escrow_id),
         ExecuteMsg::RefundEscrow { escrow_id } => refund_escrow(deps, env, info, escrow_id),
 
         // Work Management
+        ExecuteMsg::SubmitWork { job_id } => execute_submit_work(deps, env, info, job_id),
+        ExecuteMsg::ApproveCompletion { job_id } => {
+            execute_approve_completion(deps, env, info, job_id)
+        }
         ExecuteMsg::CompleteJob { job_id } => execute_complete_job(deps, env, info, job_id),
         ExecuteMsg::CompleteMilestone {
             job_id,
@@ -280,6 +478,7 @@ pub fn execute(
             rating,
             comment,
         } => execute_submit_rating(deps, env, info, job_id, rating, comment),
+        ExecuteMsg::FinalizeRatings { job_id } => execute_finalize_ratings(deps, env, job_id),
 
         // Dispute Management
         ExecuteMsg::RaiseDispute {
@@ -291,6 +490,7 @@ pub fn execute(
             dispute_id,
             resolution,
             release_to_freelancer,
+            freelancer_bps,
         } => resolve_dispute(
             deps,
             env,
@@ -298,37 +498,87 @@ pub fn execute(
             dispute_id,
             resolution,
             release_to_freelancer,
+            freelancer_bps,
         ),
+        ExecuteMsg::AppealDispute { dispute_id } => appeal_dispute(deps, env, info, dispute_id),
+        ExecuteMsg::FinalizeDispute { dispute_id } => {
+            finalize_dispute(deps, env, info, dispute_id)
+        }
+        ExecuteMsg::ClaimDisputeTimeout { dispute_id } => {
+            claim_dispute_timeout(deps, env, info, dispute_id)
+        }
 
         // Admin Functions
         ExecuteMsg::UpdateConfig {
-            admin,
             platform_fee_percent,
             min_escrow_amount,
             dispute_period_days,
             max_job_duration_days,
+            treasury,
+            dispute_timeout_release_to_freelancer,
+            max_proposals_per_job,
+            job_archive_retention_days,
+            fee_split_bps,
+            max_bounty_reward,
+            edit_grace_period_days,
+            proposal_validity_days,
+            min_edit_interval_seconds,
+            dispute_deposit,
+            reputation_half_life_days,
+            dispute_cooldown_seconds,
+            max_open_jobs_per_user,
         } => execute_update_config(
             deps,
             env,
             info,
-            admin,
             platform_fee_percent,
             min_escrow_amount,
             dispute_period_days,
             max_job_duration_days,
+            treasury,
+            dispute_timeout_release_to_freelancer,
+            max_proposals_per_job,
+            job_archive_retention_days,
+            fee_split_bps,
+            max_bounty_reward,
+            edit_grace_period_days,
+            proposal_validity_days,
+            min_edit_interval_seconds,
+            dispute_deposit,
+            reputation_half_life_days,
+            dispute_cooldown_seconds,
+            max_open_jobs_per_user,
         ),
+        ExecuteMsg::SetAllowedDenoms { denoms } => execute_set_allowed_denoms(deps, info, denoms),
+        ExecuteMsg::SetAllowedCw20Tokens { tokens } => {
+            execute_set_allowed_cw20_tokens(deps, info, tokens)
+        }
         ExecuteMsg::PauseContract {} => execute_pause_contract(deps, env, info),
         ExecuteMsg::UnpauseContract {} => execute_unpause_contract(deps, env, info),
+        ExecuteMsg::FreezeEscrows {} => execute_set_escrows_frozen(deps, info, true),
+        ExecuteMsg::UnfreezeEscrows {} => execute_set_escrows_frozen(deps, info, false),
+        ExecuteMsg::ProposeAdmin { new_admin } => execute_propose_admin(deps, info, new_admin),
+        ExecuteMsg::AcceptAdmin {} => execute_accept_admin(deps, info),
+        ExecuteMsg::GrantRole { address, role } => {
+            execute_grant_role(deps, env, info, address, role)
+        }
+        ExecuteMsg::RevokeRole { address } => execute_revoke_role(deps, env, info, address),
+        ExecuteMsg::WithdrawFees { denom, amount } => {
+            execute_withdraw_fees(deps, env, info, denom, amount)
+        }
+        ExecuteMsg::ArchiveJobs { job_ids } => execute_archive_jobs(deps, env, info, job_ids),
 
         // New escrow functions
-        ExecuteMsg::CreateEscrowNative { job_id, amount: _ } => {
-            create_escrow_native(deps, env, info, job_id)
-        }
-        ExecuteMsg::CreateEscrowCw20 {
-            job_id: _,
-            token_address: _,
-            amount,
-        } => create_escrow_cw20(deps, env, info, amount, cosmwasm_std::Binary::default()),
+        ExecuteMsg::CreateEscrowNative {
+            job_id,
+            amount: _,
+            refund_overpayment,
+        } => create_escrow_native(deps, env, info, job_id, refund_overpayment.unwrap_or(false)),
+        // The sender can't prove it moved any tokens, so funding goes through the token's Send hook
+        ExecuteMsg::CreateEscrowCw20 { .. } => Err(ContractError::InvalidInput {
+            error: "CreateEscrowCw20 is deprecated. Send the tokens with a fund_job_escrow hook instead"
+                .to_string(),
+        }),
 
         // Security functions
         ExecuteMsg::BlockAddress { address, reason } => {
@@ -368,6 +618,7 @@ pub fn execute(
             reward_distribution,
             documents,
         ),
+        ExecuteMsg::Receive(wrapper) => execute_receive(deps, env, info, wrapper),
         ExecuteMsg::EditBounty {
             bounty_id,
             title,
@@ -389,6 +640,7 @@ pub fn execute(
             documents,
         ),
         ExecuteMsg::CancelBounty { bounty_id } => execute_cancel_bounty(deps, env, info, bounty_id),
+        ExecuteMsg::ExpireBounty { bounty_id } => execute_expire_bounty(deps, env, info, bounty_id),
         ExecuteMsg::SubmitToBounty {
             bounty_id,
             title,
@@ -436,137 +688,75 @@ pub fn execute(
         ExecuteMsg::ReleaseBountyRewards { bounty_id } => {
             execute_release_bounty_rewards(deps, env, info, bounty_id)
         }
+        ExecuteMsg::ContestBountyResult { bounty_id, reason } => {
+            execute_contest_bounty_result(deps, env, info, bounty_id, reason)
+        }
+        ExecuteMsg::ResolveBountyContest {
+            bounty_id,
+            uphold_selection,
+        } => execute_resolve_bounty_contest(deps, env, info, bounty_id, uphold_selection),
+        ExecuteMsg::SetBountyFeatured {
+            bounty_id,
+            featured,
+        } => execute_set_bounty_featured(deps, env, info, bounty_id, featured),
     }
 }
 
-#[allow(clippy::too_many_arguments)]
-#[allow(dead_code)]
-fn execute_post_job(
-    mut deps: DepsMut,
+/// Dispatch a CW20 `Send` hook; `info.sender` is the token contract
+fn execute_receive(
+    deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    title: String,
-    description: String,
-    budget: Uint128,
-    category: String,
-    skills_required: Vec<String>,
-    duration_days: u64,
-    documents: Option<Vec<String>>,
-    _milestones: Option<Vec<MilestoneInput>>,
+    wrapper: Cw20ReceiveMsg,
 ) -> Result<Response, ContractError> {
-    // Security checks
-    reentrancy_guard(deps.branch())?;
-    check_rate_limit(deps.branch(), &env, &info.sender, RateLimitAction::PostJob)?;
-
-    // Input validation and sanitization
-    validate_text_inputs(&title, &description, None, None)?;
-    validate_job_duration(duration_days)?;
-    ensure_not_paused(deps.as_ref())?;
-
-    let config = CONFIG.load(deps.storage)?;
-
-    // Validate inputs
-    validate_job_title(&title)?;
-    validate_job_description(&description)?;
-    validate_budget(budget)?;
-    validate_duration(duration_days, config.max_job_duration_days)?;
-
-    if category.is_empty() || category.len() > 50 {
-        return Err(ContractError::InvalidInput {
-            error: "Category must be between 1-50 characters".to_string(),
-        });
+    match from_json(&wrapper.msg)? {
+        ReceiveMsg::CreateBounty {
+            title,
+            description,
+            requirements,
+            category,
+            skills_required,
+            submission_deadline_days,
+            review_period_days,
+            max_winners,
+            reward_distribution,
+            documents,
+        } => execute_create_bounty_cw20(
+            deps,
+            env,
+            info,
+            wrapper.sender,
+            wrapper.amount,
+            title,
+            description,
+            requirements,
+            category,
+            skills_required,
+            submission_deadline_days,
+            review_period_days,
+            max_winners,
+            reward_distribution,
+            documents,
+        ),
+        ReceiveMsg::FundJobEscrow { job_id } => {
+            create_escrow_cw20(deps, env, info, wrapper.sender, wrapper.amount, job_id)
+        }
     }
+}
 
-    if skills_required.is_empty() || skills_required.len() > 20 {
-        return Err(ContractError::InvalidInput {
-            error: "Must specify 1-20 skills".to_string(),
-        });
+/// Funded jobs get their escrow at post time, so the legacy message tops it up instead
+fn execute_legacy_create_escrow(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    job_id: u64,
+    refund_overpayment: bool,
+) -> Result<Response, ContractError> {
+    let job = JOBS.load(deps.storage, job_id)?;
+    if job.escrow_id.is_none() {
+        return create_escrow_native(deps, env, info, job_id, refund_overpayment);
     }
-
-    // Get and increment job counter
-    let job_id = JOB_COUNTER.load(deps.storage)?;
-    JOB_COUNTER.save(deps.storage, &(job_id + 1))?;
-
-    let deadline = get_future_timestamp(env.block.time, duration_days);
-
-    // Process milestones
-    // In hybrid architecture, bundle content off-chain and just store flags
-    use crate::hash_utils::create_job_content_bundle;
-    
-    // Create off-chain content bundle
-    let timestamp = env.block.time.seconds();
-    let (_bundle, hash) = create_job_content_bundle(
-        job_id,
-        &title,
-        &description,
-        None, // company - not provided in this function signature
-        None, // location - not provided in this function signature
-        &category,
-        &skills_required,
-        &documents.clone().unwrap_or_default(),
-        timestamp,
-    )?;
-    
-    let content_hash = crate::hash_utils::create_content_hash(
-        &hash,
-        "job_content",
-        timestamp,
-    )?;
-
-    // Map category to ID (simplified mapping for now)
-    let _category_id = match category.to_lowercase().as_str() {
-        "web development" => 1,
-        "mobile development" => 2,
-        "design" => 3,
-        "writing" => 4,
-        "marketing" => 5,
-        _ => 99, // Other
-    };
-
-    // Map skills to tag IDs (simplified)
-    let _skill_tags: Vec<u8> = skills_required.iter().enumerate()
-        .map(|(i, _)| (i % 50) as u8) // Simple hash for now
-        .collect();
-
-    // Determine budget range
-    let _budget_range = if budget < Uint128::from(500u128) { 1 }
-        else if budget < Uint128::from(5000u128) { 2 }
-        else { 3 };
-
-    // Create and save job
-    let job = Job {
-        id: job_id,
-        poster: info.sender.clone(),
-        budget,
-        duration_days,
-        status: JobStatus::Open,
-        assigned_freelancer: None,
-        created_at: env.block.time,
-        updated_at: env.block.time,
-        deadline,
-        escrow_id: None,
-        total_proposals: 0,
-        content_hash,
-    };
-
-    JOBS.save(deps.storage, job_id, &job)?;
-
-    // Initialize empty proposals list for this job
-    JOB_PROPOSALS.save(deps.storage, job_id, &Vec::new())?;
-
-    // Update user stats
-    let mut user_stats = USER_STATS
-        .may_load(deps.storage, &info.sender)?
-        .unwrap_or_default();
-    user_stats.total_jobs_posted += 1;
-    USER_STATS.save(deps.storage, &info.sender, &user_stats)?;
-
-    Ok(Response::new()
-        .add_attribute("method", "post_job")
-        .add_attribute("job_id", job_id.to_string())
-        .add_attribute("poster", info.sender.to_string())
-        .add_attribute("budget", budget.to_string())
-        .add_attribute("budget", budget.to_string()))
+    top_up_escrow(deps, env, info, job_id)
 }
 
 fn execute_withdraw_proposal(
@@ -596,6 +786,8 @@ fn execute_withdraw_proposal(
 
     // Remove proposal from storage
     PROPOSALS.remove(deps.storage, proposal_id);
+    record_removal(deps.storage, &crate::state::REMOVED_PROPOSAL_COUNT)?;
+    remove_entity_hashes(deps.storage, &format!("proposal_{}", proposal_id))?;
 
     // Remove from job proposals list
     let mut job_proposals = JOB_PROPOSALS.load(deps.storage, proposal.job_id)?;
@@ -620,6 +812,50 @@ fn execute_withdraw_proposal(
         .add_attribute("freelancer", info.sender.to_string()))
 }
 
+fn execute_reject_proposal(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    reason: Option<String>,
+) -> Result<Response, ContractError> {
+    // Security checks
+    reentrancy_guard(deps.branch())?;
+    ensure_not_paused(deps.as_ref())?;
+
+    let mut proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+    let job = JOBS.load(deps.storage, proposal.job_id)?;
+
+    if job.poster != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if proposal.status != ProposalStatus::Submitted {
+        return Err(ContractError::InvalidInput {
+            error: "Proposal is no longer pending".to_string(),
+        });
+    }
+
+    if let Some(reason) = &reason {
+        if reason.len() > 1000 {
+            return Err(ContractError::InvalidInput {
+                error: "Rejection reason must be at most 1000 characters".to_string(),
+            });
+        }
+    }
+
+    proposal.status = ProposalStatus::Rejected;
+    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "reject_proposal")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("job_id", proposal.job_id.to_string())
+        .add_attribute("freelancer", proposal.freelancer.to_string())
+        .add_attribute("reason", reason.unwrap_or_default())
+        .add_attribute("timestamp", env.block.time.seconds().to_string()))
+}
+
 fn execute_accept_proposal(
     mut deps: DepsMut,
     env: Env,
@@ -644,27 +880,60 @@ fn execute_accept_proposal(
         });
     }
 
+    let funded_escrow = unreleased_escrow(deps.storage, &job)?;
+
     // Load and validate proposal
     let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+    ensure_proposal_can_start(&proposal, job_id, funded_escrow.is_some(), env.block.time)?;
 
-    if proposal.job_id != job_id {
+    if proposal.status != ProposalStatus::Submitted {
         return Err(ContractError::InvalidInput {
-            error: "Proposal does not belong to this job".to_string(),
+            error: "Proposal is no longer pending".to_string(),
         });
     }
 
+    let mut accepted = proposal.clone();
+    accepted.status = ProposalStatus::Accepted;
+    PROPOSALS.save(deps.storage, proposal_id, &accepted)?;
+
+    // Reject every other pending proposal; bounded by MAX_PROPOSALS_PER_JOB
+    let mut rejected_count = 0u64;
+    for other_id in JOB_PROPOSALS.may_load(deps.storage, job_id)?.unwrap_or_default() {
+        if other_id == proposal_id {
+            continue;
+        }
+        if let Some(mut other) = PROPOSALS.may_load(deps.storage, other_id)? {
+            if other.status == ProposalStatus::Submitted {
+                other.status = ProposalStatus::Rejected;
+                PROPOSALS.save(deps.storage, other_id, &other)?;
+                rejected_count += 1;
+            }
+        }
+    }
+
     // Update job status and assign freelancer
     job.status = JobStatus::InProgress;
     job.assigned_freelancer = Some(proposal.freelancer.clone());
+    job.payout_address = proposal.payout_address.clone();
+    job.referrer = proposal.referrer.clone();
+    job.referral_bps = proposal.referral_bps;
+    job.assigned_at = Some(env.block.time);
     job.updated_at = env.block.time;
 
     JOBS.save(deps.storage, job_id, &job)?;
+    sync_active_job(deps.storage, &job)?;
+
+    // Escrows funded at post time have no payee until now
+    if let Some((escrow_id, mut escrow)) = funded_escrow {
+        escrow.freelancer = proposal.freelancer.clone();
+        ESCROWS.save(deps.storage, &escrow_id, &escrow)?;
+    }
 
-    // Update user stats
+    // The job counts toward the freelancer's stats as assigned; completion is recorded on release
     let mut freelancer_stats = USER_STATS
         .may_load(deps.storage, &proposal.freelancer)?
         .unwrap_or_default();
-    freelancer_stats.total_jobs_completed += 1;
+    record_job_assignment(&mut freelancer_stats)?;
     USER_STATS.save(deps.storage, &proposal.freelancer, &freelancer_stats)?;
 
     Ok(Response::new()
@@ -672,107 +941,339 @@ fn execute_accept_proposal(
         .add_attribute("job_id", job_id.to_string())
         .add_attribute("proposal_id", proposal_id.to_string())
         .add_attribute("freelancer", proposal.freelancer.to_string())
-        .add_attribute("delivery_time_days", proposal.delivery_time_days.to_string()))
+        .add_attribute("delivery_time_days", proposal.delivery_time_days.to_string())
+        .add_attribute("rejected_count", rejected_count.to_string())
+        .add_attribute("status", job.status.as_code()))
 }
 
-fn execute_complete_job(
+/// The job's escrow, if it still holds the budget
+fn unreleased_escrow(storage: &dyn Storage, job: &Job) -> StdResult<Option<(String, EscrowState)>> {
+    let Some(escrow_id) = &job.escrow_id else {
+        return Ok(None);
+    };
+    Ok(ESCROWS
+        .may_load(storage, escrow_id)?
+        .filter(|escrow| !escrow.released)
+        .map(|escrow| (escrow_id.clone(), escrow)))
+}
+
+/// Checks a proposal must pass before its freelancer starts on the job,
+/// whether on acceptance or on reassignment
+fn ensure_proposal_can_start(
+    proposal: &Proposal,
+    job_id: u64,
+    funded: bool,
+    now: Timestamp,
+) -> Result<(), ContractError> {
+    if proposal.job_id != job_id {
+        return Err(ContractError::InvalidInput {
+            error: "Proposal does not belong to this job".to_string(),
+        });
+    }
+
+    // Freelancers who agreed to escrow only start once the budget is held;
+    // the rest may start on an unfunded job that the poster funds later
+    if proposal.agreed_to_escrow && !funded {
+        return Err(ContractError::JobNotFunded { job_id });
+    }
+
+    if is_expired(proposal.expires_at, now) {
+        return Err(ContractError::ProposalExpired {});
+    }
+
+    Ok(())
+}
+
+fn execute_reassign_job(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     job_id: u64,
+    new_proposal_id: u64,
 ) -> Result<Response, ContractError> {
     // Security checks
     reentrancy_guard(deps.branch())?;
     ensure_not_paused(deps.as_ref())?;
 
-    // Load and validate job
     let mut job = JOBS.load(deps.storage, job_id)?;
 
-    // Only assigned freelancer can mark job as complete
-    if job.assigned_freelancer.is_none() || job.assigned_freelancer.as_ref() != Some(&info.sender) {
+    if job.poster != info.sender {
         return Err(ContractError::Unauthorized {});
     }
 
     if job.status != JobStatus::InProgress {
         return Err(ContractError::InvalidInput {
-            error: "Job is not in progress".to_string(),
+            error: "Only jobs in progress can be reassigned".to_string(),
         });
     }
 
-    // In hybrid architecture, milestone completion is tracked off-chain
-    // The contract trusts that the frontend has verified milestone completion
-    // before allowing job completion
+    // The current freelancer keeps the job until its deadline
+    if env.block.time <= job.deadline {
+        return Err(ContractError::InvalidInput {
+            error: "Job deadline has not passed yet".to_string(),
+        });
+    }
 
-    // Update job status
-    job.status = JobStatus::Completed;
-    job.updated_at = env.block.time;
+    let previous_freelancer = job.assigned_freelancer.clone().ok_or_else(|| {
+        ContractError::InvalidInput {
+            error: "Job is not assigned to anyone".to_string(),
+        }
+    })?;
 
-    JOBS.save(deps.storage, job_id, &job)?;
+    let funded_escrow = unreleased_escrow(deps.storage, &job)?;
 
-    // Update freelancer stats
-    if let Some(freelancer) = &job.assigned_freelancer {
-        let mut freelancer_stats = USER_STATS
-            .may_load(deps.storage, freelancer)?
-            .unwrap_or_default();
-        freelancer_stats.total_earned = freelancer_stats.total_earned.checked_add(job.budget)?;
-        freelancer_stats.completion_rate = Decimal::from_ratio(
-            freelancer_stats.total_jobs_completed,
-            freelancer_stats.total_jobs_completed + 1,
-        );
-        USER_STATS.save(deps.storage, freelancer, &freelancer_stats)?;
+    let mut proposal = PROPOSALS.load(deps.storage, new_proposal_id)?;
+    ensure_proposal_can_start(&proposal, job_id, funded_escrow.is_some(), env.block.time)?;
+
+    // Proposals passed over on the original acceptance can still be picked
+    if !matches!(
+        proposal.status,
+        ProposalStatus::Submitted | ProposalStatus::Rejected
+    ) || proposal.freelancer == previous_freelancer
+    {
+        return Err(ContractError::InvalidInput {
+            error: "Proposal cannot take over this job".to_string(),
+        });
     }
 
-    // Update poster stats
-    let mut poster_stats = USER_STATS
-        .may_load(deps.storage, &job.poster)?
-        .unwrap_or_default();
-    poster_stats.total_spent = poster_stats.total_spent.checked_add(job.budget)?;
-    USER_STATS.save(deps.storage, &job.poster, &poster_stats)?;
+    // Retire the abandoned proposal
+    for other_id in JOB_PROPOSALS.may_load(deps.storage, job_id)?.unwrap_or_default() {
+        if let Some(mut other) = PROPOSALS.may_load(deps.storage, other_id)? {
+            if other.status == ProposalStatus::Accepted {
+                other.status = ProposalStatus::Rejected;
+                PROPOSALS.save(deps.storage, other_id, &other)?;
+            }
+        }
+    }
 
-    let mut response = Response::new()
-        .add_attribute("method", "complete_job")
-        .add_attribute("job_id", job_id.to_string())
-        .add_attribute("freelancer", info.sender.to_string())
-        .add_attribute("budget", job.budget.to_string());
+    proposal.status = ProposalStatus::Accepted;
+    PROPOSALS.save(deps.storage, new_proposal_id, &proposal)?;
 
-    // Automatically release escrow if it exists
-    if let Some(escrow_id) = &job.escrow_id {
-        // Load escrow and check if it can be released
-        if let Ok(escrow) = ESCROWS.load(deps.storage, escrow_id) {
-            if !escrow.released && escrow.dispute_status == crate::state::DisputeStatus::None {
-                // Auto-release escrow to freelancer upon job completion
-                let config = CONFIG.load(deps.storage)?;
+    // The new freelancer gets a fresh window for the job
+    job.previous_freelancers.push(previous_freelancer.clone());
+    job.assigned_freelancer = Some(proposal.freelancer.clone());
+    job.payout_address = proposal.payout_address.clone();
+    job.referrer = proposal.referrer.clone();
+    job.referral_bps = proposal.referral_bps;
+    job.assigned_at = Some(env.block.time);
+    job.edits_objected = false;
+    job.deadline = get_future_timestamp(env.block.time, job.duration_days)?;
+    job.updated_at = env.block.time;
+    JOBS.save(deps.storage, job_id, &job)?;
 
-                // Generate payment messages
-                let freelancer_payment = cosmwasm_std::BankMsg::Send {
-                    to_address: escrow.freelancer.to_string(),
-                    amount: vec![cosmwasm_std::Coin {
-                        denom: "uxion".to_string(),
-                        amount: escrow.amount,
-                    }],
-                };
-
-                let platform_fee_payment = cosmwasm_std::BankMsg::Send {
-                    to_address: config.admin.to_string(),
-                    amount: vec![cosmwasm_std::Coin {
-                        denom: "uxion".to_string(),
-                        amount: escrow.platform_fee,
-                    }],
-                };
+    let mut freelancer_stats = USER_STATS
+        .may_load(deps.storage, &proposal.freelancer)?
+        .unwrap_or_default();
+    record_job_assignment(&mut freelancer_stats)?;
+    USER_STATS.save(deps.storage, &proposal.freelancer, &freelancer_stats)?;
+
+    // Payouts follow the new freelancer
+    if let Some((escrow_id, mut escrow)) = funded_escrow {
+        escrow.freelancer = proposal.freelancer.clone();
+        ESCROWS.save(deps.storage, &escrow_id, &escrow)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "reassign_job")
+        .add_attribute("job_id", job_id.to_string())
+        .add_attribute("proposal_id", new_proposal_id.to_string())
+        .add_attribute("previous_freelancer", previous_freelancer.to_string())
+        .add_attribute("freelancer", proposal.freelancer.to_string()))
+}
+
+fn execute_submit_work(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    job_id: u64,
+) -> Result<Response, ContractError> {
+    // Security checks
+    reentrancy_guard(deps.branch())?;
+    ensure_not_paused(deps.as_ref())?;
+
+    let mut job = JOBS.load(deps.storage, job_id)?;
+
+    // Only assigned freelancer can submit work
+    if job.assigned_freelancer.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if job.status != JobStatus::InProgress {
+        return Err(ContractError::InvalidInput {
+            error: "Job is not in progress".to_string(),
+        });
+    }
+
+    // In hybrid architecture, milestone completion is tracked off-chain
+    // The contract trusts that the frontend has verified the deliverables
+    job.status = JobStatus::WorkSubmitted;
+    job.work_submitted_at = Some(env.block.time);
+    job.updated_at = env.block.time;
+    JOBS.save(deps.storage, job_id, &job)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "submit_work")
+        .add_attribute("job_id", job_id.to_string())
+        .add_attribute("freelancer", info.sender.to_string())
+        .add_attribute("status", job.status.as_code())
+        .add_attribute("late", (env.block.time > job.deadline).to_string()))
+}
+
+fn execute_approve_completion(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    job_id: u64,
+) -> Result<Response, ContractError> {
+    // Security checks
+    reentrancy_guard(deps.branch())?;
+    ensure_not_paused(deps.as_ref())?;
+
+    let job = JOBS.load(deps.storage, job_id)?;
+
+    // Only the client signs off on delivered work
+    if job.poster != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if job.status != JobStatus::WorkSubmitted {
+        return Err(ContractError::InvalidInput {
+            error: "No submitted work to approve".to_string(),
+        });
+    }
+
+    complete_and_release(deps, env, &info.sender, job, "approve_completion")
+}
+
+fn execute_complete_job(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    job_id: u64,
+) -> Result<Response, ContractError> {
+    // Security checks
+    reentrancy_guard(deps.branch())?;
+    ensure_not_paused(deps.as_ref())?;
+
+    // Load and validate job
+    let job = JOBS.load(deps.storage, job_id)?;
+
+    // Only assigned freelancer can claim an unanswered submission
+    if job.assigned_freelancer.is_none() || job.assigned_freelancer.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if job.status != JobStatus::WorkSubmitted {
+        return Err(ContractError::InvalidInput {
+            error: "Work must be submitted before the job can be completed".to_string(),
+        });
+    }
+
+    // Late deliveries only complete through ApproveCompletion
+    let submitted_at = job.work_submitted_at.unwrap_or(job.updated_at);
+    if submitted_at > job.deadline {
+        return Err(ContractError::LateCompletionRequiresApproval {});
+    }
+
+    // The client can approve or dispute until the review window lapses
+    let config = CONFIG.load(deps.storage)?;
+    let review_deadline = get_future_timestamp(submitted_at, config.dispute_period_days)?;
+    if env.block.time < review_deadline {
+        return Err(ContractError::DisputePeriodActive {});
+    }
+
+    complete_and_release(deps, env, &info.sender, job, "complete_job")
+}
+
+/// Mark a job completed and, under `ReleasePolicy::Auto`, release its escrow to the freelancer
+fn complete_and_release(
+    deps: DepsMut,
+    env: Env,
+    actor: &Addr,
+    mut job: Job,
+    method: &str,
+) -> Result<Response, ContractError> {
+    let job_id = job.id;
+    ensure_escrows_not_frozen(deps.as_ref())?;
+
+    // Update job status
+    job.status = JobStatus::Completed;
+    job.updated_at = env.block.time;
+
+    JOBS.save(deps.storage, job_id, &job)?;
+    sync_active_job(deps.storage, &job)?;
+
+    // Count the completion here under either policy; earnings and spending are
+    // credited by whichever path pays the escrow out
+    if let Some(freelancer) = &job.assigned_freelancer {
+        let mut freelancer_stats = USER_STATS
+            .may_load(deps.storage, freelancer)?
+            .unwrap_or_default();
+        record_job_completion(&mut freelancer_stats)?;
+        USER_STATS.save(deps.storage, freelancer, &freelancer_stats)?;
+    }
+    let auto_release = job.release_policy == ReleasePolicy::Auto;
+
+    let freelancer = job
+        .assigned_freelancer
+        .as_ref()
+        .map(|addr| addr.to_string())
+        .unwrap_or_default();
+    let mut response = Response::new()
+        .add_attribute("method", method)
+        .add_attribute("job_id", job_id.to_string())
+        .add_attribute("freelancer", freelancer)
+        .add_attribute("budget", job.budget.to_string())
+        .add_attribute("status", job.status.as_code());
+
+    if !auto_release {
+        return Ok(response
+            .add_attribute("release_policy", "manual")
+            .add_attribute("escrow_released", "false"));
+    }
+
+    // Automatically release escrow if it exists
+    if let Some(escrow_id) = &job.escrow_id {
+        // Load escrow and check if it can be released
+        if let Ok(escrow) = ESCROWS.load(deps.storage, escrow_id) {
+            if !escrow.released && escrow.dispute_status == crate::state::DisputeStatus::None {
+                // Auto-release escrow to freelancer upon job completion
+                // Generate payment messages
+                let payee = job.payout_address.clone().unwrap_or_else(|| escrow.freelancer.clone());
+                let freelancer_payment = payout_msg(&escrow.denom, &payee, escrow.amount)?;
+
+                // Referrer is paid out of the platform fee; the rest accrues for the treasury
+                let fees = settle_platform_fee(deps.storage, &job, &escrow.denom, escrow.platform_fee)?;
+                record_payout_stats(deps.storage, &escrow.client, &escrow.freelancer, escrow.amount)?;
 
                 // Mark escrow as released
                 let mut updated_escrow = escrow;
                 updated_escrow.released = true;
                 ESCROWS.save(deps.storage, escrow_id, &updated_escrow)?;
+                record_escrow_event(
+                    deps.storage,
+                    escrow_id,
+                    EscrowEventKind::Released,
+                    actor,
+                    updated_escrow.amount.checked_add(updated_escrow.platform_fee)?,
+                    env.block.time,
+                )?;
 
                 // Add payment messages and attributes
                 response = response
                     .add_message(freelancer_payment)
-                    .add_message(platform_fee_payment)
+                    .add_messages(fees.messages)
                     .add_attribute("escrow_released", "true")
                     .add_attribute("escrow_id", escrow_id)
                     .add_attribute("payment_amount", updated_escrow.amount.to_string())
-                    .add_attribute("platform_fee", updated_escrow.platform_fee.to_string());
+                    .add_attribute("platform_fee", updated_escrow.platform_fee.to_string())
+                    .add_attributes(payout_attributes(
+                        &CONFIG.load(deps.storage)?.treasury,
+                        fees.retained,
+                        &payee,
+                        updated_escrow.amount,
+                    ))
+                    .add_attributes(fees.attributes);
             }
         }
     }
@@ -876,39 +1377,112 @@ fn execute_submit_rating(
         rating,
         comment: comment.clone(),
         created_at: env.block.time,
+        pending: true,
+    };
+
+    let mut user_ratings = USER_RATINGS
+        .may_load(deps.storage, &rated_user)?
+        .unwrap_or_default();
+    user_ratings.push(rating_key.clone());
+    USER_RATINGS.save(deps.storage, &rated_user, &user_ratings)?;
+
+    // The first rating stays out of the averages so the other party cannot
+    // retaliate; the second one reveals both together
+    let counterpart_key = format!("{}_{}", job_id, rated_user);
+    let revealed = match RATINGS.may_load(deps.storage, &counterpart_key)? {
+        Some(counterpart) => {
+            if counterpart.pending {
+                reveal_rating(deps.storage, counterpart)?;
+            }
+            true
+        }
+        None => false,
+    };
+    if revealed {
+        reveal_rating(deps.storage, rating_record)?;
+    } else {
+        RATINGS.save(deps.storage, &rating_key, &rating_record)?;
+    }
+
+    let rating_type = if is_poster_rating {
+        "freelancer"
+    } else {
+        "poster"
     };
 
-    RATINGS.save(deps.storage, &rating_key, &rating_record)?;
+    Ok(Response::new()
+        .add_attribute("method", "submit_rating")
+        .add_attribute("job_id", job_id.to_string())
+        .add_attribute("rater", info.sender.to_string())
+        .add_attribute("rated", rated_user.to_string())
+        .add_attribute("rating", rating.to_string())
+        .add_attribute("rating_type", rating_type)
+        .add_attribute("revealed", revealed.to_string()))
+}
+
+/// Clear the pending flag and fold the rating into the rated user's stats
+fn reveal_rating(storage: &mut dyn Storage, mut rating: Rating) -> StdResult<()> {
+    rating.pending = false;
+    RATINGS.save(storage, &rating.id, &rating)?;
 
-    // Update rated user's stats
     let mut user_stats = USER_STATS
-        .may_load(deps.storage, &rated_user)?
+        .may_load(storage, &rating.rated)?
         .unwrap_or_default();
 
     let new_total_ratings = user_stats.total_ratings + 1;
     let new_average = (user_stats.average_rating
         * Decimal::from_ratio(user_stats.total_ratings, 1u128)
-        + Decimal::from_ratio(rating as u128, 1u128))
+        + Decimal::from_ratio(rating.rating as u128, 1u128))
         / Decimal::from_ratio(new_total_ratings, 1u128);
 
     user_stats.average_rating = new_average;
     user_stats.total_ratings = new_total_ratings;
 
-    USER_STATS.save(deps.storage, &rated_user, &user_stats)?;
+    USER_STATS.save(storage, &rating.rated, &user_stats)
+}
 
-    let rating_type = if is_poster_rating {
-        "freelancer"
-    } else {
-        "poster"
-    };
+fn execute_finalize_ratings(
+    deps: DepsMut,
+    env: Env,
+    job_id: u64,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref())?;
+
+    let job = JOBS.load(deps.storage, job_id)?;
+    let freelancer = job
+        .assigned_freelancer
+        .ok_or_else(|| ContractError::RatingError {
+            msg: "No pending ratings for this job".to_string(),
+        })?;
+
+    // Only one party can be pending: the second rating reveals both
+    let pending = [&job.poster, &freelancer]
+        .into_iter()
+        .map(|rater| RATINGS.may_load(deps.storage, &format!("{}_{}", job_id, rater)))
+        .collect::<StdResult<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .find(|rating| rating.pending)
+        .ok_or_else(|| ContractError::RatingError {
+            msg: "No pending ratings for this job".to_string(),
+        })?;
+
+    let reveal_at = pending
+        .created_at
+        .plus_seconds(RATING_REVEAL_PERIOD_DAYS * 24 * 60 * 60);
+    if env.block.time < reveal_at {
+        return Err(ContractError::RatingError {
+            msg: "Rating reveal window is still open".to_string(),
+        });
+    }
+
+    let rater = pending.rater.clone();
+    reveal_rating(deps.storage, pending)?;
 
     Ok(Response::new()
-        .add_attribute("method", "submit_rating")
+        .add_attribute("method", "finalize_ratings")
         .add_attribute("job_id", job_id.to_string())
-        .add_attribute("rater", info.sender.to_string())
-        .add_attribute("rated", rated_user.to_string())
-        .add_attribute("rating", rating.to_string())
-        .add_attribute("rating_type", rating_type))
+        .add_attribute("rater", rater.to_string()))
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -916,27 +1490,33 @@ fn execute_update_config(
     mut deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    admin: Option<String>,
     platform_fee_percent: Option<u64>,
     min_escrow_amount: Option<Uint128>,
     dispute_period_days: Option<u64>,
     max_job_duration_days: Option<u64>,
+    treasury: Option<String>,
+    dispute_timeout_release_to_freelancer: Option<bool>,
+    max_proposals_per_job: Option<u64>,
+    job_archive_retention_days: Option<u64>,
+    fee_split_bps: Option<u16>,
+    max_bounty_reward: Option<Uint128>,
+    edit_grace_period_days: Option<u64>,
+    proposal_validity_days: Option<u64>,
+    min_edit_interval_seconds: Option<u64>,
+    dispute_deposit: Option<Uint128>,
+    reputation_half_life_days: Option<u64>,
+    dispute_cooldown_seconds: Option<u64>,
+    max_open_jobs_per_user: Option<u64>,
 ) -> Result<Response, ContractError> {
     // Security checks
     reentrancy_guard(deps.branch())?;
 
-    let mut config = CONFIG.load(deps.storage)?;
-
-    // Only admin can update config
-    if config.admin != info.sender {
-        return Err(ContractError::Unauthorized {});
-    }
+    // Only super admins can update config
+    ensure_admin(deps.as_ref(), &info.sender)?;
 
-    // Update fields if provided
-    if let Some(new_admin) = admin {
-        config.admin = deps.api.addr_validate(&new_admin)?;
-    }
+    let mut config = CONFIG.load(deps.storage)?;
 
+    // Update fields if provided; the admin changes through ProposeAdmin/AcceptAdmin
     if let Some(fee_percent) = platform_fee_percent {
         if fee_percent > 10 {
             return Err(ContractError::PlatformFeeTooHigh { max: 10 });
@@ -956,6 +1536,62 @@ fn execute_update_config(
         config.max_job_duration_days = max_duration;
     }
 
+    if let Some(new_treasury) = treasury {
+        config.treasury = deps.api.addr_validate(&new_treasury)?;
+    }
+
+    if let Some(release_to_freelancer) = dispute_timeout_release_to_freelancer {
+        config.dispute_timeout_release_to_freelancer = release_to_freelancer;
+    }
+
+    if let Some(max_proposals) = max_proposals_per_job {
+        validate_max_proposals_per_job(max_proposals)?;
+        config.max_proposals_per_job = max_proposals;
+    }
+
+    if let Some(retention_days) = job_archive_retention_days {
+        config.job_archive_retention_days = retention_days;
+    }
+
+    if let Some(split_bps) = fee_split_bps {
+        validate_fee_split_bps(split_bps)?;
+        config.fee_split_bps = split_bps;
+    }
+
+    if let Some(max_reward) = max_bounty_reward {
+        config.max_bounty_reward = max_reward;
+    }
+    validate_bounty_reward_bounds(config.min_escrow_amount, config.max_bounty_reward)?;
+
+    if let Some(grace_days) = edit_grace_period_days {
+        config.edit_grace_period_days = grace_days;
+    }
+
+    if let Some(validity_days) = proposal_validity_days {
+        validate_proposal_validity_days(validity_days)?;
+        config.proposal_validity_days = validity_days;
+    }
+
+    if let Some(interval) = min_edit_interval_seconds {
+        config.min_edit_interval_seconds = interval;
+    }
+
+    if let Some(deposit) = dispute_deposit {
+        config.dispute_deposit = deposit;
+    }
+
+    if let Some(half_life) = reputation_half_life_days {
+        config.reputation_half_life_days = half_life;
+    }
+
+    if let Some(cooldown) = dispute_cooldown_seconds {
+        config.dispute_cooldown_seconds = cooldown;
+    }
+
+    if let Some(max_open_jobs) = max_open_jobs_per_user {
+        config.max_open_jobs_per_user = max_open_jobs;
+    }
+
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new()
@@ -972,12 +1608,10 @@ fn execute_pause_contract(
     _env: Env,
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
-    let mut config = CONFIG.load(deps.storage)?;
+    // Only super admins can pause contract
+    ensure_admin(deps.as_ref(), &info.sender)?;
 
-    // Only admin can pause contract
-    if config.admin != info.sender {
-        return Err(ContractError::Unauthorized {});
-    }
+    let mut config = CONFIG.load(deps.storage)?;
 
     config.paused = true;
     CONFIG.save(deps.storage, &config)?;
@@ -992,12 +1626,10 @@ fn execute_unpause_contract(
     _env: Env,
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
-    let mut config = CONFIG.load(deps.storage)?;
+    // Only super admins can unpause contract
+    ensure_admin(deps.as_ref(), &info.sender)?;
 
-    // Only admin can unpause contract
-    if config.admin != info.sender {
-        return Err(ContractError::Unauthorized {});
-    }
+    let mut config = CONFIG.load(deps.storage)?;
 
     config.paused = false;
     CONFIG.save(deps.storage, &config)?;
@@ -1007,10 +1639,67 @@ fn execute_unpause_contract(
         .add_attribute("admin", info.sender.to_string()))
 }
 
+fn execute_set_escrows_frozen(
+    deps: DepsMut,
+    info: MessageInfo,
+    frozen: bool,
+) -> Result<Response, ContractError> {
+    // Only super admins can freeze escrow payouts
+    ensure_admin(deps.as_ref(), &info.sender)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+
+    config.escrows_frozen = frozen;
+    CONFIG.save(deps.storage, &config)?;
+
+    let method = if frozen { "freeze_escrows" } else { "unfreeze_escrows" };
+    Ok(Response::new()
+        .add_attribute("method", method)
+        .add_attribute("admin", info.sender.to_string()))
+}
+
+fn execute_set_allowed_denoms(
+    deps: DepsMut,
+    info: MessageInfo,
+    denoms: Vec<String>,
+) -> Result<Response, ContractError> {
+    // Only super admins can change which denoms are accepted
+    ensure_admin(deps.as_ref(), &info.sender)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    validate_allowed_denoms(&denoms, &config.denom)?;
+    config.allowed_denoms = denoms;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_allowed_denoms")
+        .add_attribute("admin", info.sender.to_string())
+        .add_attribute("allowed_denoms", config.allowed_denoms.join(",")))
+}
+
+fn execute_set_allowed_cw20_tokens(
+    deps: DepsMut,
+    info: MessageInfo,
+    tokens: Vec<String>,
+) -> Result<Response, ContractError> {
+    // Only super admins can change which tokens are accepted
+    ensure_admin(deps.as_ref(), &info.sender)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    config.allowed_cw20_tokens = validate_allowed_cw20_tokens(deps.api, &tokens)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_allowed_cw20_tokens")
+        .add_attribute("admin", info.sender.to_string())
+        .add_attribute("allowed_cw20_tokens", tokens.join(",")))
+}
+
 #[entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::GetJob { job_id } => to_json_binary(&query_job(deps, job_id)?),
+        QueryMsg::GetJobsByIds { ids } => to_json_binary(&query_jobs_by_ids(deps, ids)?),
         QueryMsg::GetJobs {
             start_after,
             limit,
@@ -1025,33 +1714,132 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             status,
             poster,
         )?),
+        QueryMsg::GetJobsBySkills {
+            skills,
+            match_all,
+            limit,
+        } => to_json_binary(&query_jobs_by_skills(deps, skills, match_all, limit)?),
+        QueryMsg::GetJobsByBudgetRange {
+            range,
+            start_after,
+            limit,
+        } => to_json_binary(&query_jobs_by_budget_range(deps, range, start_after, limit)?),
+        QueryMsg::GetArchivedJob { job_id } => to_json_binary(&query_archived_job(deps, job_id)?),
+        QueryMsg::GetArchivedJobs { start_after, limit } => {
+            to_json_binary(&query_archived_jobs(deps, start_after, limit)?)
+        }
         QueryMsg::GetAllJobs { limit, category: _ } => {
             to_json_binary(&query_all_jobs(deps, limit)?)
         }
         QueryMsg::GetUserJobs { user, status } => {
             to_json_binary(&query_user_jobs(deps, user, status)?)
         }
+        QueryMsg::GetJobTemplates { poster } => {
+            to_json_binary(&crate::job_management::query_job_templates(deps, poster)?)
+        }
         QueryMsg::GetProposal { proposal_id } => {
             to_json_binary(&query_proposal(deps, proposal_id)?)
         }
-        QueryMsg::GetJobProposals { job_id } => to_json_binary(&query_job_proposals(deps, job_id)?),
+        QueryMsg::GetJobProposals {
+            job_id,
+            include_expired,
+        } => to_json_binary(&query_job_proposals(
+            deps,
+            &env,
+            job_id,
+            include_expired.unwrap_or(true),
+        )?),
+        QueryMsg::GetJobProposalsRanked { job_id, limit } => {
+            to_json_binary(&query_job_proposals_ranked(deps, &env, job_id, limit)?)
+        }
+        QueryMsg::GetJobDetail {
+            job_id,
+            proposals_limit,
+        } => to_json_binary(&query_job_detail(deps, job_id, proposals_limit)?),
+        QueryMsg::GetJobProposalsByStatus {
+            job_id,
+            status,
+            start_after,
+            limit,
+        } => to_json_binary(&query_job_proposals_by_status(
+            deps,
+            job_id,
+            status,
+            start_after,
+            limit,
+        )?),
         QueryMsg::GetUserProposals {
             user,
             start_after,
             limit,
         } => to_json_binary(&query_user_proposals_query(deps, user, start_after, limit)?),
+        QueryMsg::GetAcceptedProposals { freelancer, limit } => to_json_binary(
+            &crate::query_helpers::query_accepted_proposals(deps, freelancer, limit)?,
+        ),
         QueryMsg::GetEscrow { escrow_id } => to_json_binary(&query_escrow(deps, escrow_id)?),
         QueryMsg::GetJobEscrow { job_id } => to_json_binary(&query_job_escrow(deps, job_id)?),
+        QueryMsg::GetEscrowHistory { escrow_id } => {
+            to_json_binary(&query_escrow_history(deps, escrow_id)?)
+        }
+        QueryMsg::GetEscrows {
+            start_after,
+            limit,
+            released,
+            dispute_status,
+        } => to_json_binary(&query_escrows(
+            deps,
+            start_after,
+            limit,
+            released,
+            dispute_status,
+        )?),
+        QueryMsg::GetSolvency {} => to_json_binary(&query_solvency(deps)?),
+        // No per-category fee overrides exist, so the category doesn't change the terms
+        QueryMsg::PreviewFees {
+            amount,
+            category: _,
+        } => to_json_binary(&query_preview_fees(deps, amount)?),
         QueryMsg::GetUserRatings { user } => to_json_binary(&query_user_ratings(deps, user)?),
         QueryMsg::GetJobRating { job_id, rater } => {
             to_json_binary(&query_job_rating(deps, job_id, rater)?)
         }
+        QueryMsg::HasRated { job_id, user } => {
+            to_json_binary(&query_has_rated(deps, job_id, user)?)
+        }
         QueryMsg::GetUserStats { user } => to_json_binary(&query_user_stats(deps, user)?),
+        QueryMsg::GetUserReputation { user } => {
+            to_json_binary(&query_user_reputation(deps, env, user)?)
+        }
+        QueryMsg::GetUserActivity { user, limit } => {
+            to_json_binary(&query_user_activity(deps, user, limit)?)
+        }
+        QueryMsg::GetUserProfile { user } => to_json_binary(&query_user_profile(deps, user)?),
+        QueryMsg::GetVerifiedUsers { start_after, limit } => {
+            to_json_binary(&query_verified_users(deps, start_after, limit)?)
+        }
         QueryMsg::GetPlatformStats {} => to_json_binary(&query_platform_stats(deps)?),
+        QueryMsg::GetCounts {} => to_json_binary(&crate::query_helpers::query_counts(deps)?),
         QueryMsg::GetDispute { dispute_id } => to_json_binary(&query_dispute(deps, dispute_id)?),
+        QueryMsg::GetDisputeDetail { dispute_id } => {
+            to_json_binary(&query_dispute_detail(deps, dispute_id)?)
+        }
         QueryMsg::GetJobDisputes { job_id } => to_json_binary(&query_job_disputes(deps, job_id)?),
         QueryMsg::GetUserDisputes { user } => to_json_binary(&query_user_disputes(deps, user)?),
+        QueryMsg::GetDisputesByStatus {
+            status,
+            start_after,
+            limit,
+        } => to_json_binary(&query_disputes_by_status(deps, status, start_after, limit)?),
         QueryMsg::GetConfig {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::GetContractInfo {} => to_json_binary(&query_contract_info(deps)?),
+        QueryMsg::GetAccruedFees { denom } => to_json_binary(&query_accrued_fees(deps, denom)?),
+        QueryMsg::GetCategories {} => to_json_binary(&query_category_registry()?),
+        QueryMsg::ResolveContentHash { hash } => {
+            to_json_binary(&query_resolve_content_hash(deps, hash)?)
+        }
+        QueryMsg::GetContentReferences { start_after, limit } => to_json_binary(
+            &crate::query_helpers::query_content_references(deps, start_after, limit)?,
+        ),
         // Security queries
         QueryMsg::GetSecurityMetrics {} => to_json_binary(&query_security_metrics(deps)?),
         QueryMsg::GetAuditLogs {
@@ -1063,7 +1851,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             to_json_binary(&query_is_address_blocked(deps, address)?)
         }
         QueryMsg::GetRateLimitStatus { address } => {
-            to_json_binary(&query_rate_limit_status(deps, address)?)
+            to_json_binary(&query_rate_limit_status(deps, env, address)?)
         }
 
         // Bounty Queries
@@ -1093,48 +1881,87 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::GetBountySubmissions { bounty_id, status } => {
             to_json_binary(&query_bounty_submissions(deps, bounty_id, status)?)
         }
+        QueryMsg::GetBountyWinners { bounty_id } => {
+            to_json_binary(&query_bounty_winners(deps, bounty_id)?)
+        }
         QueryMsg::GetUserBountySubmissions {
             user,
+            status,
             start_after,
             limit,
         } => to_json_binary(&query_user_bounty_submissions(
             deps,
             user,
+            status,
             start_after,
             limit,
         )?),
+        QueryMsg::GetFeaturedBounties { limit } => {
+            to_json_binary(&query_featured_bounties(deps, limit)?)
+        }
+        QueryMsg::GetBountiesExpiringSoon { within_days, limit } => to_json_binary(
+            &query_bounties_expiring_soon(deps, env, within_days, limit)?,
+        ),
     }
 }
 
 // Query function implementations
 fn query_job(deps: Deps, job_id: u64) -> StdResult<JobResponse> {
-    let job = JOBS.load(deps.storage, job_id)?;
+    let job = JOBS.may_load(deps.storage, job_id)?;
     Ok(JobResponse { job })
 }
 
-fn query_all_jobs(
+fn query_jobs_by_ids(deps: Deps, ids: Vec<u64>) -> StdResult<JobsResponse> {
+    if ids.len() > MAX_JOBS_BY_IDS {
+        return Err(StdError::generic_err(format!(
+            "Can fetch at most {} jobs at a time",
+            MAX_JOBS_BY_IDS
+        )));
+    }
+
+    let mut jobs = Vec::with_capacity(ids.len());
+    for job_id in ids {
+        if let Some(job) = JOBS.may_load(deps.storage, job_id)? {
+            jobs.push(job);
+        }
+    }
+    Ok(JobsResponse { jobs })
+}
+
+fn query_archived_job(deps: Deps, job_id: u64) -> StdResult<JobResponse> {
+    let job = ARCHIVED_JOBS.may_load(deps.storage, job_id)?;
+    Ok(JobResponse { job })
+}
+
+fn query_archived_jobs(
     deps: Deps,
+    start_after: Option<u64>,
     limit: Option<u32>,
 ) -> StdResult<JobsResponse> {
-    let limit = limit.unwrap_or(50).min(100) as usize; // Max 100 jobs for frontend
-    let mut jobs = Vec::new();
+    let limit = limit.unwrap_or(50).min(100) as usize;
+    let start = start_after.map(Bound::exclusive);
 
-    let jobs_result: StdResult<Vec<_>> = JOBS
-        .range(deps.storage, None, None, cosmwasm_std::Order::Descending) // Most recent first
-        .collect();
+    let jobs = ARCHIVED_JOBS
+        .range(deps.storage, start, None, cosmwasm_std::Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, job)| job))
+        .collect::<StdResult<Vec<Job>>>()?;
 
-    if let Ok(job_pairs) = jobs_result {
-        for (_, job) in job_pairs {
-            // Only show open jobs for landing page
-            if job.status == JobStatus::Open {
-                jobs.push(job);
+    Ok(JobsResponse { jobs })
+}
 
-                if jobs.len() >= limit {
-                    break;
-                }
-            }
-        }
-    }
+fn query_all_jobs(
+    deps: Deps,
+    limit: Option<u32>,
+) -> StdResult<JobsResponse> {
+    let limit = limit.unwrap_or(50).min(100) as usize; // Max 100 jobs for frontend
+
+    // Only open jobs for landing page, most recent first
+    let jobs = ACTIVE_JOBS
+        .keys(deps.storage, None, None, cosmwasm_std::Order::Descending)
+        .take(limit)
+        .map(|job_id| JOBS.load(deps.storage, job_id?))
+        .collect::<StdResult<Vec<Job>>>()?;
 
     Ok(JobsResponse { jobs })
 }
@@ -1164,25 +1991,99 @@ fn query_jobs(
     Ok(JobsResponse { jobs })
 }
 
-fn query_user_jobs(deps: Deps, user: String, status: Option<JobStatus>) -> StdResult<JobsResponse> {
-    let user_addr = deps.api.addr_validate(&user)?;
-    let jobs = query_jobs_paginated(deps.storage, None, None, status, Some(user_addr))?;
-
-    Ok(JobsResponse { jobs })
-}
-
 fn query_proposal(deps: Deps, proposal_id: u64) -> StdResult<ProposalResponse> {
     let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
     Ok(ProposalResponse { proposal })
 }
 
-fn query_job_proposals(deps: Deps, job_id: u64) -> StdResult<ProposalsResponse> {
+fn query_job_proposals(
+    deps: Deps,
+    env: &Env,
+    job_id: u64,
+    include_expired: bool,
+) -> StdResult<ProposalsResponse> {
     let proposal_ids = JOB_PROPOSALS.load(deps.storage, job_id)?;
     let mut proposals = Vec::new();
 
     for proposal_id in proposal_ids {
         if let Ok(proposal) = PROPOSALS.load(deps.storage, proposal_id) {
-            proposals.push(proposal);
+            if include_expired || !is_expired(proposal.expires_at, env.block.time) {
+                proposals.push(proposal);
+            }
+        }
+    }
+
+    Ok(ProposalsResponse { proposals })
+}
+
+fn query_job_proposals_ranked(
+    deps: Deps,
+    env: &Env,
+    job_id: u64,
+    limit: Option<u32>,
+) -> StdResult<ProposalsResponse> {
+    let limit = limit.unwrap_or(10).min(50) as usize;
+
+    let mut proposals = query_job_proposals(deps, env, job_id, false)?.proposals;
+    // Ties go to the earlier proposal
+    proposals.sort_by(|a, b| b.score.cmp(&a.score).then(a.id.cmp(&b.id)));
+    proposals.truncate(limit);
+
+    Ok(ProposalsResponse { proposals })
+}
+
+fn query_job_detail(
+    deps: Deps,
+    job_id: u64,
+    proposals_limit: Option<u32>,
+) -> StdResult<JobDetailResponse> {
+    let limit = proposals_limit.unwrap_or(10).min(50) as usize;
+
+    let job = JOBS.load(deps.storage, job_id)?;
+    let escrow = query_job_escrow(deps, job_id)?.escrow;
+
+    let proposal_ids = JOB_PROPOSALS
+        .may_load(deps.storage, job_id)?
+        .unwrap_or_default();
+    let proposals = proposal_ids
+        .iter()
+        .take(limit)
+        .map(|proposal_id| PROPOSALS.load(deps.storage, *proposal_id))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(JobDetailResponse {
+        job,
+        escrow,
+        proposals,
+        proposal_count: proposal_ids.len() as u64,
+    })
+}
+
+fn query_job_proposals_by_status(
+    deps: Deps,
+    job_id: u64,
+    status: ProposalStatus,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ProposalsResponse> {
+    let limit = limit.unwrap_or(10).min(50) as usize;
+    let mut proposal_ids = JOB_PROPOSALS
+        .may_load(deps.storage, job_id)?
+        .unwrap_or_default();
+    proposal_ids.sort_unstable();
+
+    let mut proposals = Vec::new();
+    for proposal_id in proposal_ids
+        .into_iter()
+        .filter(|id| start_after.is_none_or(|after| *id > after))
+    {
+        if proposals.len() >= limit {
+            break;
+        }
+        if let Some(proposal) = PROPOSALS.may_load(deps.storage, proposal_id)? {
+            if proposal.status == status {
+                proposals.push(proposal);
+            }
         }
     }
 
@@ -1201,20 +2102,106 @@ fn query_user_proposals_query(
 }
 
 fn query_escrow(deps: Deps, escrow_id: String) -> StdResult<EscrowResponse> {
-    let escrow = ESCROWS.load(deps.storage, &escrow_id)?;
+    let escrow = ESCROWS.may_load(deps.storage, &escrow_id)?;
     Ok(EscrowResponse { escrow })
 }
 
 fn query_job_escrow(deps: Deps, job_id: u64) -> StdResult<EscrowResponse> {
-    let job = JOBS.load(deps.storage, job_id)?;
-    if let Some(escrow_id) = job.escrow_id {
-        let escrow = ESCROWS.load(deps.storage, &escrow_id)?;
-        Ok(EscrowResponse { escrow })
-    } else {
-        Err(cosmwasm_std::StdError::not_found(
-            "Escrow not found for job",
-        ))
+    let escrow_id = JOBS
+        .may_load(deps.storage, job_id)?
+        .and_then(|job| job.escrow_id);
+    let escrow = match escrow_id {
+        Some(escrow_id) => ESCROWS.may_load(deps.storage, &escrow_id)?,
+        None => None,
+    };
+    Ok(EscrowResponse { escrow })
+}
+
+fn query_escrow_history(deps: Deps, escrow_id: String) -> StdResult<EscrowHistoryResponse> {
+    let events = ESCROW_EVENTS
+        .prefix(&escrow_id)
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|item| item.map(|(_, event)| event))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(EscrowHistoryResponse { escrow_id, events })
+}
+
+fn query_escrows(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    released: Option<bool>,
+    dispute_status: Option<DisputeStatus>,
+) -> StdResult<EscrowsResponse> {
+    let limit = limit.unwrap_or(50).min(100) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let escrows: StdResult<Vec<EscrowState>> = ESCROWS
+        .range(deps.storage, start, None, cosmwasm_std::Order::Ascending)
+        .map(|item| item.map(|(_, escrow)| escrow))
+        .filter(|result| {
+            if let Ok(escrow) = result {
+                // Filter by release state
+                if let Some(rel) = released {
+                    if escrow.released != rel {
+                        return false;
+                    }
+                }
+
+                // Filter by dispute status
+                if let Some(ref stat) = dispute_status {
+                    if escrow.dispute_status != *stat {
+                        return false;
+                    }
+                }
+
+                true
+            } else {
+                true
+            }
+        })
+        .take(limit)
+        .collect();
+
+    Ok(EscrowsResponse { escrows: escrows? })
+}
+
+fn query_preview_fees(deps: Deps, amount: Uint128) -> StdResult<FeePreviewResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    // Same terms escrow creation charges
+    let terms = escrow_terms(amount, config.platform_fee_percent, config.fee_split_bps)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    Ok(FeePreviewResponse {
+        deposit: terms.deposit,
+        platform_fee: terms.platform_fee,
+        net_to_freelancer: terms.payout,
+        fee_percent_applied: config.platform_fee_percent,
+    })
+}
+
+fn query_solvency(deps: Deps) -> StdResult<SolvencyResponse> {
+    // Each unreleased escrow still holds its payout plus the platform fee
+    let mut per_denom: BTreeMap<String, Uint128> = BTreeMap::new();
+    for item in ESCROWS.range(deps.storage, None, None, cosmwasm_std::Order::Ascending) {
+        let (_, escrow) = item?;
+        if escrow.released {
+            continue;
+        }
+        let locked = per_denom.entry(escrow_denom(&escrow.denom)).or_default();
+        *locked = locked
+            .checked_add(escrow.amount)?
+            .checked_add(escrow.platform_fee)?;
+    }
+
+    let mut total_locked = Uint128::zero();
+    for amount in per_denom.values() {
+        total_locked = total_locked.checked_add(*amount)?;
     }
+
+    Ok(SolvencyResponse {
+        total_locked,
+        per_denom: per_denom.into_iter().collect(),
+    })
 }
 
 // Query functions implementation
@@ -1222,14 +2209,14 @@ fn query_user_ratings(deps: Deps, user: String) -> StdResult<RatingsResponse> {
     let user_addr = deps.api.addr_validate(&user)?;
     let mut ratings = Vec::new();
 
-    // Iterate through all ratings and find ones where the user is either rater or rated
+    // Iterate through all revealed ratings where the user is either rater or rated
     let all_ratings: StdResult<Vec<_>> = RATINGS
         .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
         .collect();
 
     if let Ok(rating_pairs) = all_ratings {
         for (_, rating) in rating_pairs {
-            if rating.rater == user_addr || rating.rated == user_addr {
+            if !rating.pending && (rating.rater == user_addr || rating.rated == user_addr) {
                 ratings.push(rating);
             }
         }
@@ -1242,82 +2229,100 @@ fn query_job_rating(deps: Deps, job_id: u64, rater: String) -> StdResult<Rating>
     let rater_addr = deps.api.addr_validate(&rater)?;
     let rating_key = format!("{}_{}", job_id, rater_addr);
     let rating = RATINGS.load(deps.storage, &rating_key)?;
+    // A held rating reads as missing so the counterpart can't see it before rating back
+    if rating.pending {
+        return Err(StdError::not_found("Rating"));
+    }
     Ok(rating)
 }
 
+fn query_has_rated(deps: Deps, job_id: u64, user: String) -> StdResult<bool> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    Ok(RATINGS.has(deps.storage, &format!("{}_{}", job_id, user_addr)))
+}
+
 fn query_user_stats(deps: Deps, user: String) -> StdResult<UserStatsResponse> {
     let user_addr = deps.api.addr_validate(&user)?;
     let stats = USER_STATS
         .may_load(deps.storage, &user_addr)?
         .unwrap_or_default();
-    Ok(UserStatsResponse { stats })
+    let is_verified = VERIFIED_USERS.has(deps.storage, &user_addr);
+    Ok(UserStatsResponse { stats, is_verified })
 }
 
-fn query_platform_stats(deps: Deps) -> StdResult<PlatformStatsResponse> {
-    let mut total_jobs = 0u64;
-    let mut open_jobs = 0u64;
-    let mut in_progress_jobs = 0u64;
-    let mut completed_jobs = 0u64;
-    let mut total_volume = Uint128::zero();
+fn query_user_reputation(deps: Deps, env: Env, user: String) -> StdResult<UserReputationResponse> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let half_life_days = CONFIG.load(deps.storage)?.reputation_half_life_days;
+    let half_life_secs = half_life_days.saturating_mul(24 * 60 * 60);
 
-    // Efficiently process jobs using iterator without collecting all into memory
-    for (_, job) in JOBS
-        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-        .flatten()
-    {
-        total_jobs += 1;
-        total_volume = total_volume.checked_add(job.budget)?;
-
-        match job.status {
-            JobStatus::Open => open_jobs += 1,
-            JobStatus::InProgress => in_progress_jobs += 1,
-            JobStatus::Completed => completed_jobs += 1,
-            _ => {}
+    let rating_keys = USER_RATINGS
+        .may_load(deps.storage, &user_addr)?
+        .unwrap_or_default();
+
+    // Only the most recent ratings count; pending ones are still hidden
+    let mut weighted_sum = Decimal::zero();
+    let mut total_weight = Decimal::zero();
+    let mut ratings_considered = 0u64;
+    for key in rating_keys.iter().rev().take(MAX_REPUTATION_RATINGS) {
+        let Some(rating) = RATINGS.may_load(deps.storage, key)? else {
+            continue;
+        };
+        if rating.pending {
+            continue;
         }
+        let age = env.block.time.seconds().saturating_sub(rating.created_at.seconds());
+        let weight = rating_decay_weight(age, half_life_secs);
+        weighted_sum += weight * Decimal::from_ratio(rating.rating as u128, 1u128);
+        total_weight += weight;
+        ratings_considered += 1;
     }
 
-    // Count unique users efficiently
-    // Count bounties
-    let bounty_stats = BOUNTIES
-        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-        .try_fold(
-            (0u64, 0u64, 0u64),
-            |acc, result| -> StdResult<(u64, u64, u64)> {
-                let (_, bounty) = result?;
-                let (total, open, completed) = acc;
-                Ok((
-                    total + 1,
-                    if bounty.status == BountyStatus::Open { open + 1 } else { open },
-                    if bounty.status == BountyStatus::Completed { completed + 1 } else { completed },
-                ))
-            },
-        )?;
-
-    let total_users = USER_STATS
-        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-        .count() as u64;
-
-    // Calculate total value locked in escrows (simplified - using total volume as proxy)
-    let total_value_locked = total_volume;
-
-    Ok(PlatformStatsResponse {
-        total_jobs,
-        open_jobs,
-        in_progress_jobs,
-        completed_jobs,
-        total_bounties: bounty_stats.0,
-        open_bounties: bounty_stats.1,
-        completed_bounties: bounty_stats.2,
-        total_users,
-        total_value_locked,
+    let reputation = if total_weight.is_zero() {
+        Decimal::zero()
+    } else {
+        weighted_sum / total_weight
+    };
+    Ok(UserReputationResponse {
+        user: user_addr,
+        reputation,
+        ratings_considered,
+        half_life_days,
     })
 }
 
+/// 2^(-age / half_life), interpolated linearly within each half-life
+fn rating_decay_weight(age_secs: u64, half_life_secs: u64) -> Decimal {
+    if half_life_secs == 0 {
+        return Decimal::one();
+    }
+    let half_lives = age_secs / half_life_secs;
+    if half_lives >= 60 {
+        return Decimal::zero();
+    }
+    let into_half_life = age_secs % half_life_secs;
+    Decimal::from_ratio(1u128, 1u128 << half_lives)
+        * Decimal::from_ratio(
+            2 * half_life_secs as u128 - into_half_life as u128,
+            2 * half_life_secs as u128,
+        )
+}
+
 fn query_dispute(deps: Deps, dispute_id: String) -> StdResult<DisputeResponse> {
     let dispute = DISPUTES.load(deps.storage, &dispute_id)?;
     Ok(DisputeResponse { dispute })
 }
 
+fn query_dispute_detail(deps: Deps, dispute_id: String) -> StdResult<DisputeDetailResponse> {
+    let dispute = DISPUTES.load(deps.storage, &dispute_id)?;
+    let job = JOBS.load(deps.storage, dispute.job_id)?;
+    let escrow = query_job_escrow(deps, dispute.job_id)?.escrow;
+    Ok(DisputeDetailResponse {
+        dispute,
+        job,
+        escrow,
+    })
+}
+
 fn query_job_disputes(deps: Deps, job_id: u64) -> StdResult<DisputesResponse> {
     let mut disputes = Vec::new();
 
@@ -1355,11 +2360,51 @@ fn query_user_disputes(deps: Deps, user: String) -> StdResult<DisputesResponse>
     Ok(DisputesResponse { disputes })
 }
 
+fn query_disputes_by_status(
+    deps: Deps,
+    status: DisputeStatus,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<DisputesResponse> {
+    let limit = limit.unwrap_or(50).min(100) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let disputes = DISPUTES_BY_STATUS
+        .prefix(dispute_status_key(&status))
+        .keys(deps.storage, start, None, cosmwasm_std::Order::Ascending)
+        .take(limit)
+        .map(|dispute_id| DISPUTES.load(deps.storage, &dispute_id?))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(DisputesResponse { disputes })
+}
+
 fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let config = CONFIG.load(deps.storage)?;
     Ok(ConfigResponse { config })
 }
 
+fn query_contract_info(deps: Deps) -> StdResult<ContractInfoResponse> {
+    let info = get_contract_version(deps.storage)?;
+    Ok(ContractInfoResponse {
+        contract: info.contract,
+        version: info.version,
+    })
+}
+
+fn query_accrued_fees(deps: Deps, denom: Option<Denom>) -> StdResult<AccruedFeesResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let denom = denom.unwrap_or(Denom::Native(config.denom));
+    let amount = ACCRUED_FEES
+        .may_load(deps.storage, &escrow_denom(&denom))?
+        .unwrap_or_default();
+    Ok(AccruedFeesResponse {
+        denom,
+        amount,
+        treasury: config.treasury,
+    })
+}
+
 // Security execute functions
 fn execute_block_address(
     deps: DepsMut,
@@ -1368,12 +2413,8 @@ fn execute_block_address(
     address: String,
     reason: String,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-
-    // Only admin can block addresses
-    if config.admin != info.sender {
-        return Err(ContractError::Unauthorized {});
-    }
+    // Only moderators can block addresses
+    ensure_role(deps.as_ref(), &info.sender, AdminRole::Moderator)?;
 
     let addr_to_block = deps.api.addr_validate(&address)?;
     BLOCKED_ADDRESSES.save(deps.storage, &addr_to_block, &env.block.time)?;
@@ -1391,12 +2432,8 @@ fn execute_unblock_address(
     info: MessageInfo,
     address: String,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-
-    // Only admin can unblock addresses
-    if config.admin != info.sender {
-        return Err(ContractError::Unauthorized {});
-    }
+    // Only moderators can unblock addresses
+    ensure_role(deps.as_ref(), &info.sender, AdminRole::Moderator)?;
 
     let addr_to_unblock = deps.api.addr_validate(&address)?;
     BLOCKED_ADDRESSES.remove(deps.storage, &addr_to_unblock);
@@ -1413,12 +2450,8 @@ fn execute_reset_rate_limit(
     info: MessageInfo,
     address: String,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-
-    // Only admin can reset rate limits
-    if config.admin != info.sender {
-        return Err(ContractError::Unauthorized {});
-    }
+    // Only moderators can reset rate limits
+    ensure_role(deps.as_ref(), &info.sender, AdminRole::Moderator)?;
 
     let addr_to_reset = deps.api.addr_validate(&address)?;
 
@@ -1525,29 +2558,35 @@ fn query_is_address_blocked(
 
 fn query_rate_limit_status(
     deps: Deps,
+    env: Env,
     address: String,
 ) -> StdResult<crate::msg::RateLimitStatusResponse> {
     let addr = deps.api.addr_validate(&address)?;
 
-    // Get rate limit state from the enhanced security system
-    let current_time = cosmwasm_std::Timestamp::from_seconds(0); // Use a default timestamp
-    let rate_limit = crate::security::USER_RATE_LIMITS
+    // Get rate limit state from the enhanced security system, as the next
+    // check_rate_limit call would see it
+    let mut rate_limit = crate::security::USER_RATE_LIMITS
         .may_load(deps.storage, &addr)?
-        .unwrap_or(crate::security::RateLimit {
-            daily_jobs: 0,
-            daily_proposals: 0,
-            daily_bounties: 0,
-            daily_disputes: 0,
-            daily_escrows: 0,
-            daily_admin_actions: 0,
-            last_reset: current_time,
-        });
+        .unwrap_or_else(|| crate::security::RateLimit::new(env.block.time));
+    rate_limit.reset_if_expired(env.block.time);
+
+    let counters = rate_limit
+        .counters()
+        .into_iter()
+        .map(|(action, count, limit)| crate::msg::RateLimitCounter {
+            action: action.to_string(),
+            count,
+            limit,
+            is_limited: count >= limit,
+        })
+        .collect();
 
     Ok(crate::msg::RateLimitStatusResponse {
         current_count: rate_limit.daily_jobs, // Use jobs as primary metric
-        limit: 5,                             // MAX_JOBS_PER_USER_PER_DAY
+        limit: MAX_JOBS_PER_USER_PER_DAY,
         window_start: rate_limit.last_reset,
-        is_limited: rate_limit.daily_jobs >= 5,
+        is_limited: rate_limit.daily_jobs >= MAX_JOBS_PER_USER_PER_DAY,
+        counters,
     })
 }
 
@@ -1556,7 +2595,7 @@ fn query_rate_limit_status(
 // ========================================
 
 fn query_bounty(deps: Deps, bounty_id: u64) -> StdResult<BountyResponse> {
-    let bounty = BOUNTIES.load(deps.storage, bounty_id)?;
+    let bounty = BOUNTIES.may_load(deps.storage, bounty_id)?;
     Ok(BountyResponse { bounty })
 }
 
@@ -1608,22 +2647,23 @@ fn query_all_bounties(
 ) -> StdResult<BountiesResponse> {
     let limit = limit.unwrap_or(50).min(100) as usize;
 
-    let bounties: StdResult<Vec<Bounty>> = BOUNTIES
-        .range(deps.storage, None, None, cosmwasm_std::Order::Descending)
+    // Only open bounties, most recent first
+    let bounties = ACTIVE_BOUNTIES
+        .keys(deps.storage, None, None, cosmwasm_std::Order::Descending)
         .take(limit)
-        .map(|item| item.map(|(_, bounty)| bounty))
-        .filter(|result| {
-            if let Ok(bounty) = result {
-                // Only show open bounties
-                if bounty.status != BountyStatus::Open {
-                    return false;
-                }
+        .map(|bounty_id| BOUNTIES.load(deps.storage, bounty_id?))
+        .collect::<StdResult<Vec<Bounty>>>()?;
 
-                true
-            } else {
-                true
-            }
-        })
+    Ok(BountiesResponse { bounties })
+}
+
+fn query_featured_bounties(deps: Deps, limit: Option<u32>) -> StdResult<BountiesResponse> {
+    let limit = limit.unwrap_or(10).min(50) as usize;
+
+    let bounties: StdResult<Vec<Bounty>> = FEATURED_BOUNTIES
+        .keys(deps.storage, None, None, cosmwasm_std::Order::Descending)
+        .take(limit)
+        .map(|bounty_id| BOUNTIES.load(deps.storage, bounty_id?))
         .collect();
 
     Ok(BountiesResponse {
@@ -1631,6 +2671,36 @@ fn query_all_bounties(
     })
 }
 
+fn query_bounties_expiring_soon(
+    deps: Deps,
+    env: Env,
+    within_days: u64,
+    limit: Option<u32>,
+) -> StdResult<BountiesResponse> {
+    let limit = limit.unwrap_or(10).min(50) as usize;
+    let horizon = env
+        .block
+        .time
+        .plus_seconds(within_days.min(MAX_EXPIRY_WINDOW_DAYS) * 24 * 60 * 60);
+
+    // Open bounties whose deadline has not passed yet but falls within the window
+    let mut bounties = ACTIVE_BOUNTIES
+        .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|bounty_id| BOUNTIES.load(deps.storage, bounty_id?))
+        .filter(|result| {
+            result.as_ref().map_or(true, |bounty| {
+                bounty.submission_deadline > env.block.time
+                    && bounty.submission_deadline <= horizon
+            })
+        })
+        .collect::<StdResult<Vec<Bounty>>>()?;
+
+    bounties.sort_by_key(|bounty| (bounty.submission_deadline, bounty.id));
+    bounties.truncate(limit);
+
+    Ok(BountiesResponse { bounties })
+}
+
 fn query_user_bounties(
     deps: Deps,
     user: String,
@@ -1703,36 +2773,31 @@ fn query_bounty_submissions(
     })
 }
 
-fn query_user_bounty_submissions(
-    deps: Deps,
-    user: String,
-    start_after: Option<u64>,
-    limit: Option<u32>,
-) -> StdResult<BountySubmissionsResponse> {
-    let user_addr = deps.api.addr_validate(&user)?;
-    let limit = limit.unwrap_or(50).min(100) as usize;
-
-    let submission_ids = USER_BOUNTY_SUBMISSIONS
-        .may_load(deps.storage, &user_addr)?
-        .unwrap_or_default();
-
-    let mut filtered_ids = submission_ids;
-
-    // Apply start_after filter
-    if let Some(after_id) = start_after {
-        filtered_ids.retain(|&id| id > after_id);
-    }
-
-    // Sort and limit
-    filtered_ids.sort_by(|a, b| b.cmp(a)); // Descending order
-    filtered_ids.truncate(limit);
+fn query_bounty_winners(deps: Deps, bounty_id: u64) -> StdResult<BountyWinnersResponse> {
+    let bounty = BOUNTIES.load(deps.storage, bounty_id)?;
 
-    let submissions: StdResult<Vec<BountySubmission>> = filtered_ids
-        .into_iter()
-        .map(|id| BOUNTY_SUBMISSIONS.load(deps.storage, id))
-        .collect();
+    let mut winners = bounty
+        .selected_winners
+        .iter()
+        .map(|submission_id| {
+            let submission = BOUNTY_SUBMISSIONS.load(deps.storage, *submission_id)?;
+            let position = submission.winner_position.unwrap_or_default();
+            let reward_amount = bounty
+                .reward_distribution
+                .iter()
+                .find(|tier| tier.position == position)
+                .map(|tier| tier.amount)
+                .unwrap_or_default();
+            Ok(BountyWinner {
+                position,
+                submitter: submission.submitter,
+                submission_id: *submission_id,
+                reward_amount,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    winners.sort_by_key(|winner| winner.position);
 
-    Ok(BountySubmissionsResponse {
-        submissions: submissions?,
-    })
+    Ok(BountyWinnersResponse { winners })
 }
+
@@ -31,10 +31,19 @@ macro_rules! apply_basic_security_checks {
 #[macro_export]
 macro_rules! ensure_admin {
     ($deps:expr, $info:expr) => {
-        let config = CONFIG.load($deps.storage)?;
-        if config.admin != $info.sender {
-            return Err(ContractError::Unauthorized {});
-        }
+        $crate::helpers::ensure_role(
+            $deps.as_ref(),
+            &$info.sender,
+            $crate::state::AdminRole::SuperAdmin,
+        )?;
+    };
+}
+
+/// Macro to check the sender holds a specific admin role
+#[macro_export]
+macro_rules! ensure_role {
+    ($deps:expr, $info:expr, $role:expr) => {
+        $crate::helpers::ensure_role($deps.as_ref(), &$info.sender, $role)?;
     };
 }
 
@@ -52,6 +61,7 @@ macro_rules! validate_content_inputs {
                 error: "Title too long".to_string(),
             });
         }
+        $crate::contract_helpers::validate_text_chars($title, "Title", false)?;
         if $description.is_empty() {
             return Err(ContractError::InvalidInput {
                 error: "Description cannot be empty".to_string(),
@@ -62,6 +72,7 @@ macro_rules! validate_content_inputs {
                 error: "Description too long".to_string(),
             });
         }
+        $crate::contract_helpers::validate_text_chars($description, "Description", true)?;
     };
 }
 
@@ -93,6 +104,33 @@ pub fn validate_string_field(
             ),
         });
     }
+    validate_text_chars(value, field_name, true)
+}
+
+/// Reject null bytes and other control characters; multi-line fields may
+/// keep tabs and line breaks
+pub fn validate_text_chars(
+    value: &str,
+    field_name: &str,
+    allow_line_breaks: bool,
+) -> Result<(), ContractError> {
+    let has_invalid = value.chars().any(|c| {
+        c.is_ascii_control() && !(allow_line_breaks && matches!(c, '\t' | '\n' | '\r'))
+    });
+    if has_invalid {
+        return Err(ContractError::InvalidInput {
+            error: format!("{} contains invalid control characters", field_name),
+        });
+    }
+    Ok(())
+}
+
+/// Categories and skills are single-line labels
+pub fn validate_labels(category: &str, skills: &[String]) -> Result<(), ContractError> {
+    validate_text_chars(category, "Category", false)?;
+    for skill in skills {
+        validate_text_chars(skill, "Skill", false)?;
+    }
     Ok(())
 }
 
@@ -121,6 +159,75 @@ pub fn validate_collection_size<T>(
     Ok(())
 }
 
+/// Limits shared by job and bounty skill/requirement lists
+pub const MAX_SKILLS: usize = 20;
+pub const MAX_SKILL_LENGTH: usize = 50;
+pub const MAX_REQUIREMENTS: usize = 10;
+pub const MAX_REQUIREMENT_LENGTH: usize = 500;
+
+/// Bound a list of strings in count and reject blank or oversized entries
+pub fn validate_string_list(
+    items: &[String],
+    field_name: &str,
+    min_count: usize,
+    max_count: usize,
+    max_len: usize,
+) -> Result<(), ContractError> {
+    validate_collection_size(items, field_name, min_count, max_count)?;
+    for item in items {
+        if item.trim().is_empty() || item.len() > max_len {
+            return Err(ContractError::InvalidInput {
+                error: format!("{} entries must be 1-{} characters", field_name, max_len),
+            });
+        }
+    }
+    Ok(())
+}
+
+pub fn validate_skills(skills: &[String]) -> Result<(), ContractError> {
+    validate_string_list(skills, "Skills required", 1, MAX_SKILLS, MAX_SKILL_LENGTH)
+}
+
+pub fn validate_requirements(requirements: &[String]) -> Result<(), ContractError> {
+    validate_string_list(
+        requirements,
+        "Requirements",
+        1,
+        MAX_REQUIREMENTS,
+        MAX_REQUIREMENT_LENGTH,
+    )
+}
+
+/// Limits for document and evidence references (URLs or content hashes)
+pub const MAX_DOCUMENTS: usize = 10;
+pub const MAX_EVIDENCE_ITEMS: usize = 10;
+pub const MAX_URI_LENGTH: usize = 512;
+
+/// Bound a list of URLs/hashes in count and per-item length, rejecting blank
+/// entries and anything containing whitespace or control characters
+pub fn validate_uri_list(
+    items: &[String],
+    field_name: &str,
+    max_items: usize,
+    max_len: usize,
+) -> Result<(), ContractError> {
+    validate_collection_size(items, field_name, 0, max_items)?;
+    for item in items {
+        if item.is_empty()
+            || item.len() > max_len
+            || item.chars().any(|c| c.is_whitespace() || c.is_control())
+        {
+            return Err(ContractError::InvalidInput {
+                error: format!(
+                    "{} entries must be 1-{} characters without whitespace",
+                    field_name, max_len
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn validate_job_creation_inputs(
     title: &str,
@@ -137,7 +244,8 @@ pub fn validate_job_creation_inputs(
     validate_budget(budget)?;
     validate_duration(duration_days, max_duration_days)?;
     validate_string_field(category, "Category", 1, 50)?;
-    validate_collection_size(skills_required, "Skills required", 1, 20)?;
+    validate_skills(skills_required)?;
+    validate_labels(category, skills_required)?;
     validate_optional_string_field(company, "Company name", 100)?;
     validate_optional_string_field(location, "Location", 100)?;
     Ok(())
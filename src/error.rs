@@ -1,4 +1,4 @@
-use cosmwasm_std::{DivideByZeroError, OverflowError, StdError};
+use cosmwasm_std::{DivideByZeroError, OverflowError, StdError, Timestamp, Uint128};
 use cw_utils::PaymentError;
 use thiserror::Error;
 
@@ -28,14 +28,23 @@ pub enum ContractError {
     #[error("Proposal not found")]
     ProposalNotFound {},
 
+    #[error("Job template {name} not found")]
+    JobTemplateNotFound { name: String },
+
     #[error("Escrow not found")]
     EscrowNotFound {},
 
     #[error("Insufficient funds: expected {expected}, got {actual}")]
     InsufficientFunds { expected: String, actual: String },
 
-    #[error("Invalid funds provided")]
-    InvalidFunds {},
+    #[error("Invalid funds: expected {expected}, received {received}")]
+    InvalidFunds { expected: String, received: String },
+
+    #[error("Denom mismatch: job is paid in {expected}, got {got}")]
+    DenomMismatch { expected: String, got: String },
+
+    #[error("Denom {denom} is not accepted by this contract")]
+    DenomNotAllowed { denom: String },
 
     #[error("Job status error: {msg}")]
     JobStatusError { msg: String },
@@ -46,6 +55,15 @@ pub enum ContractError {
     #[error("Escrow not funded for job {job_id}")]
     EscrowNotFunded { job_id: u64 },
 
+    #[error("Job {job_id} must be funded before a proposal can be accepted")]
+    JobNotFunded { job_id: u64 },
+
+    #[error("Job has reached the maximum of {max} proposals")]
+    MaxProposalsReached { max: u64 },
+
+    #[error("Poster already has the maximum of {max} open jobs")]
+    TooManyOpenJobs { max: u64 },
+
     #[error("Payment error: {msg}")]
     PaymentError { msg: String },
 
@@ -55,15 +73,36 @@ pub enum ContractError {
     #[error("Invalid deadline: deadline must be in the future")]
     InvalidDeadline {},
 
+    #[error("Duration of {days} days is too long")]
+    DurationTooLong { days: u64 },
+
     #[error("Job expired")]
     JobExpired {},
 
+    #[error("Proposal expired")]
+    ProposalExpired {},
+
+    #[error("Bounty result is contested and awaits admin review")]
+    BountyContested {},
+
+    #[error("Edited too recently, next edit allowed at {next_edit_at}")]
+    EditTooSoon { next_edit_at: Timestamp },
+
+    #[error("Disputed too recently, next dispute allowed at {retry_after}")]
+    DisputeCooldown { retry_after: Timestamp },
+
     #[error("Dispute period still active")]
     DisputePeriodActive {},
 
+    #[error("Work delivered after the deadline needs the poster's approval")]
+    LateCompletionRequiresApproval {},
+
     #[error("Contract is paused")]
     ContractPaused {},
 
+    #[error("Escrow releases are frozen")]
+    EscrowsFrozen {},
+
     #[error("Platform fee too high: maximum {max}%")]
     PlatformFeeTooHigh { max: u64 },
 
@@ -76,6 +115,15 @@ pub enum ContractError {
     #[error("Escrow amount too low: minimum {min}")]
     EscrowAmountTooLow { min: String },
 
+    #[error("Escrow amount below configured minimum of {min}")]
+    EscrowBelowMinimum { min: Uint128 },
+
+    #[error("Bounty reward below configured minimum of {min}")]
+    BountyRewardBelowMinimum { min: Uint128 },
+
+    #[error("Bounty reward above configured maximum of {max}")]
+    BountyRewardAboveMaximum { max: Uint128 },
+
     #[error("Milestone not found")]
     MilestoneNotFound {},
 
@@ -84,7 +132,7 @@ pub enum ContractError {
 
     // Security-specific errors
     #[error("Reentrancy attack detected")]
-    ReentrancyAttack {},
+    ReentrancyDetected {},
 
     #[error("Rate limit exceeded for {action}: maximum {limit} per day")]
     RateLimitExceeded { action: String, limit: u64 },
@@ -1,27 +1,27 @@
 use cosmwasm_std::{
-    Addr, BankMsg, Coin, DepsMut, Env, MessageInfo, Response, StdResult,
-    Uint128, WasmMsg, to_json_binary, Binary, Decimal
+    Addr, Attribute, BankMsg, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdResult,
+    Uint128,
 };
-use cw_utils::must_pay;
-use cw20::Cw20ExecuteMsg;
+use cw20::Denom;
 
+use crate::contract_helpers::{validate_uri_list, MAX_EVIDENCE_ITEMS, MAX_URI_LENGTH};
 use crate::error::ContractError;
+use crate::helpers::{
+    describe_funds, ensure_denom_allowed, ensure_escrows_not_frozen, ensure_not_paused, ensure_role, escrow_denom, payout_msg, record_escrow_event, record_job_completion, save_dispute,
+    sync_active_job, validate_exact_funds, validate_min_funds,
+};
+use crate::job_management::{budget_for_deposit, escrow_terms, set_job_budget};
 use crate::security::{
     reentrancy_guard, generate_escrow_id
 };
 use crate::state::{
-    EscrowState, DisputeStatus, Dispute,
-    AuditLog, ESCROWS, CONFIG, DISPUTES, AUDIT_LOGS,
-    JOBS, USER_STATS
+    AdminRole, EscrowState, EscrowEventKind, DisputeStatus, Dispute, Job,
+    AuditLog, ACCRUED_FEES, ESCROWS, CONFIG, DISPUTES, AUDIT_LOGS,
+    JOBS, LAST_DISPUTE, USER_STATS
 };
 
 const DISPUTE_PERIOD_SECONDS: u64 = 7 * 24 * 60 * 60; // 7 days
-const XION_DENOM: &str = "uxion";
-
-#[derive(serde::Deserialize)]
-struct EscrowHookMsg {
-    job_id: u64,
-}
+const BPS_DENOMINATOR: u16 = 10_000;
 
 // Enhanced escrow creation with CW20 support and security
 pub fn create_escrow_native(
@@ -29,11 +29,20 @@ pub fn create_escrow_native(
     env: Env,
     info: MessageInfo,
     job_id: u64,
+    refund_overpayment: bool,
 ) -> Result<Response, ContractError> {
     // Security check - reentrancy guard
     reentrancy_guard(deps.branch())?;
     
-    let result = create_escrow_internal(deps.branch(), env.clone(), info.clone(), job_id, None, None);
+    let result = create_escrow_internal(
+        deps.branch(),
+        env.clone(),
+        info.clone(),
+        job_id,
+        None,
+        None,
+        refund_overpayment,
+    );
     
     // Log the action
     let log_id = generate_escrow_id(job_id, &info.sender, &info.sender, env.block.time.seconds());
@@ -52,18 +61,24 @@ pub fn create_escrow_native(
     result
 }
 
-// CW20 token escrow support
+// CW20 token escrow support: `info.sender` is the token contract and
+// `poster` the account that sent the tokens
 pub fn create_escrow_cw20(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    poster: String,
     amount: Uint128,
-    msg: Binary,
+    job_id: u64,
 ) -> Result<Response, ContractError> {
-    let hook_msg: EscrowHookMsg = cosmwasm_std::from_json(&msg)?;
-    let token_contract = info.sender.clone();
+    let token_contract = info.sender;
+    let poster_info = MessageInfo {
+        sender: deps.api.addr_validate(&poster)?,
+        funds: vec![],
+    };
     
-    create_escrow_internal(deps, env, info, hook_msg.job_id, Some(amount), Some(token_contract))
+    // CW20 sends carry an exact amount, so there is never a surplus to refund
+    create_escrow_internal(deps, env, poster_info, job_id, Some(amount), Some(token_contract), false)
 }
 
 pub fn create_escrow_internal(
@@ -73,13 +88,11 @@ pub fn create_escrow_internal(
     job_id: u64,
     cw20_amount: Option<Uint128>,
     token_contract: Option<Addr>,
+    refund_overpayment: bool,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     
-    // Check if contract is paused
-    if config.paused {
-        return Err(ContractError::ContractPaused {});
-    }
+    ensure_not_paused(deps.as_ref())?;
 
     let job = JOBS.load(deps.storage, job_id)?;
     
@@ -95,53 +108,73 @@ pub fn create_escrow_internal(
         return Err(ContractError::Unauthorized {});
     }
     
-    // Job must be in progress
-    if job.status != crate::state::JobStatus::InProgress {
-        return Err(ContractError::InvalidInput {
-            error: "Job must be in progress to create escrow".to_string(),
-        });
-    }
-    
-    // Check if escrow already exists
-    if job.escrow_id.is_some() {
+    // One escrow per job; posting a funded job already creates `job_{id}`
+    let escrow_id = format!("job_{}", job_id);
+    if job.escrow_id.is_some() || ESCROWS.has(deps.storage, &escrow_id) {
         return Err(ContractError::EscrowAlreadyExists { job_id });
     }
     
-    // Validate payment amount
-    let payment_amount = if let Some(amount) = cw20_amount {
-        amount
-    } else {
-        must_pay(&info, XION_DENOM)?
-    };
-    
-    if payment_amount < job.budget {
-        return Err(ContractError::InsufficientFunds {
-            expected: job.budget.to_string(),
-            actual: payment_amount.to_string(),
+    // Unfunded jobs are funded while open, or once a freelancer who didn't
+    // require escrow has started
+    if !matches!(
+        job.status,
+        crate::state::JobStatus::Open | crate::state::JobStatus::InProgress
+    ) {
+        return Err(ContractError::InvalidInput {
+            error: "Job must be open or in progress to create escrow".to_string(),
         });
     }
     
-    if payment_amount < config.min_escrow_amount {
-        return Err(ContractError::EscrowAmountTooLow {
-            min: config.min_escrow_amount.to_string(),
+    if job.budget < config.min_escrow_amount {
+        return Err(ContractError::EscrowBelowMinimum {
+            min: config.min_escrow_amount,
         });
     }
     
-    // Calculate platform fee (max 10%)
-    let platform_fee = payment_amount
-        .checked_mul(Uint128::from(config.platform_fee_percent))?
-        .checked_div(Uint128::from(100u128))?;
-    let freelancer_amount = payment_amount.checked_sub(platform_fee)?;
+    // Funds must be in the denom the job was posted for
+    let funded_denom = match (&token_contract, info.funds.as_slice()) {
+        (Some(token), _) => Some(token.to_string()),
+        (None, [coin]) => Some(coin.denom.clone()),
+        _ => None,
+    };
+    let job_denom = escrow_denom(&job.denom);
+    if let Some(got) = funded_denom {
+        if got != job_denom {
+            return Err(ContractError::DenomMismatch {
+                expected: job_denom,
+                got,
+            });
+        }
+    }
     
-    // Generate unique escrow ID
-    let escrow_id = format!("escrow_{}_{}", job_id, env.block.time.seconds());
+    // The poster deposits the budget plus their share of the platform fee
+    let terms = escrow_terms(job.budget, config.platform_fee_percent, config.fee_split_bps)?;
+    let mut surplus = Uint128::zero();
+    let payment_amount = if let Some(amount) = cw20_amount {
+        if amount != terms.deposit {
+            return Err(ContractError::InsufficientFunds {
+                expected: terms.deposit.to_string(),
+                actual: amount.to_string(),
+            });
+        }
+        amount
+    } else if refund_overpayment {
+        surplus = validate_min_funds(&info, &config.allowed_denoms, &job_denom, terms.deposit)?;
+        terms.deposit
+    } else {
+        validate_exact_funds(&info, &config.allowed_denoms, &job_denom, terms.deposit)?;
+        terms.deposit
+    };
+    let platform_fee = terms.platform_fee;
+    let freelancer_amount = terms.payout;
     
     // Create escrow state
     let escrow = EscrowState {
         id: escrow_id.clone(),
         job_id,
         client: job.poster.clone(),
-        freelancer: job.assigned_freelancer.clone().unwrap(),
+        // Empty until AcceptProposal assigns the job, unless it already is
+        freelancer: job.assigned_freelancer.clone().unwrap_or_else(|| Addr::unchecked("")),
         amount: freelancer_amount,
         platform_fee,
         funded_at: env.block.time,
@@ -149,9 +182,18 @@ pub fn create_escrow_internal(
         dispute_status: DisputeStatus::None,
         dispute_raised_at: None,
         dispute_deadline: None,
+        denom: job.denom.clone(),
     };
     
     ESCROWS.save(deps.storage, &escrow_id, &escrow)?;
+    record_escrow_event(
+        deps.storage,
+        &escrow_id,
+        EscrowEventKind::Funded,
+        &info.sender,
+        escrow.amount.checked_add(escrow.platform_fee)?,
+        env.block.time,
+    )?;
     
     // Update job with escrow ID
     let mut updated_job = job;
@@ -159,33 +201,110 @@ pub fn create_escrow_internal(
     updated_job.updated_at = env.block.time;
     JOBS.save(deps.storage, job_id, &updated_job)?;
     
-    // Send CW20 tokens to escrow contract if applicable
-    if let Some(amount) = cw20_amount {
-        let msg = Cw20ExecuteMsg::Transfer {
-            recipient: escrow_id.clone(),
-            amount,
-        };
-        let transfer_msg = WasmMsg::Execute {
-            contract_addr: token_contract.unwrap().to_string(),
-            msg: to_json_binary(&msg)?,
-            funds: vec![],
-        };
-        
-        return Ok(Response::new()
-            .add_message(transfer_msg)
-            .add_attribute("method", "create_escrow")
-            .add_attribute("job_id", job_id.to_string())
-            .add_attribute("escrow_id", escrow_id)
-            .add_attribute("amount", payment_amount.to_string())
-            .add_attribute("platform_fee", platform_fee.to_string()));
+    // CW20 deposits arrive through the token's Send hook, so the contract
+    // already holds them; like native funds they stay here until payout
+    let mut response = Response::new()
+        .add_attribute("method", "create_escrow")
+        .add_attribute("job_id", job_id.to_string())
+        .add_attribute("escrow_id", escrow_id)
+        .add_attribute("amount", payment_amount.to_string())
+        .add_attribute("platform_fee", platform_fee.to_string());
+    
+    // Hand back anything paid beyond the deposit
+    if !surplus.is_zero() {
+        response = response
+            .add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: vec![Coin {
+                    denom: job_denom,
+                    amount: surplus,
+                }],
+            })
+            .add_attribute("refunded_surplus", surplus.to_string());
     }
     
+    Ok(response)
+}
+
+pub fn top_up_escrow(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    job_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    ensure_not_paused(deps.as_ref())?;
+
+    let mut job = JOBS.load(deps.storage, job_id)?;
+
+    // Only job poster can add funds
+    if job.poster != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let escrow_id = job.escrow_id.clone().ok_or(ContractError::EscrowNotFound {})?;
+    let mut escrow = ESCROWS.load(deps.storage, &escrow_id)?;
+
+    if escrow.released {
+        return Err(ContractError::InvalidInput {
+            error: "Escrow already released".to_string(),
+        });
+    }
+
+    if escrow.dispute_status != DisputeStatus::None {
+        return Err(ContractError::InvalidInput {
+            error: "Cannot top up a disputed escrow".to_string(),
+        });
+    }
+
+    let Denom::Native(denom) = &escrow.denom else {
+        return Err(ContractError::InvalidInput {
+            error: "CW20 escrows cannot be topped up with native funds".to_string(),
+        });
+    };
+    ensure_denom_allowed(&config.allowed_denoms, denom)?;
+    let top_up = match info.funds.as_slice() {
+        [coin] if &coin.denom == denom && !coin.amount.is_zero() => coin.amount,
+        _ => {
+            return Err(ContractError::InvalidFunds {
+                expected: format!("a single {} coin", denom),
+                received: describe_funds(&info.funds),
+            })
+        }
+    };
+
+    // The attached coin is the added budget plus the poster's fee share on it. Only
+    // the increase is priced under the current config; what is already escrowed keeps
+    // the terms it was funded under.
+    let increase = budget_for_deposit(top_up, config.platform_fee_percent, config.fee_split_bps)?;
+    let increase_terms = escrow_terms(increase, config.platform_fee_percent, config.fee_split_bps)?;
+    let new_budget = job.budget.checked_add(increase)?;
+
+    escrow.amount = escrow.amount.checked_add(increase_terms.payout)?;
+    escrow.platform_fee = escrow.platform_fee.checked_add(increase_terms.platform_fee)?;
+    ESCROWS.save(deps.storage, &escrow_id, &escrow)?;
+    record_escrow_event(
+        deps.storage,
+        &escrow_id,
+        EscrowEventKind::ToppedUp,
+        &info.sender,
+        top_up,
+        env.block.time,
+    )?;
+
+    set_job_budget(deps.storage, &mut job, new_budget)?;
+    job.updated_at = env.block.time;
+    JOBS.save(deps.storage, job_id, &job)?;
+
     Ok(Response::new()
-        .add_attribute("method", "create_escrow")
+        .add_attribute("method", "top_up_escrow")
         .add_attribute("job_id", job_id.to_string())
         .add_attribute("escrow_id", escrow_id)
-        .add_attribute("amount", payment_amount.to_string())
-        .add_attribute("platform_fee", platform_fee.to_string()))
+        .add_attribute("top_up", top_up.to_string())
+        .add_attribute("amount", escrow.amount.to_string())
+        .add_attribute("platform_fee", escrow.platform_fee.to_string())
+        .add_attribute("budget", job.budget.to_string()))
 }
 
 pub fn release_escrow(
@@ -198,10 +317,8 @@ pub fn release_escrow(
     let mut escrow = ESCROWS.load(deps.storage, &escrow_id)?;
     let job = JOBS.load(deps.storage, escrow.job_id)?;
     
-    // Check if contract is paused
-    if config.paused {
-        return Err(ContractError::ContractPaused {});
-    }
+    ensure_not_paused(deps.as_ref())?;
+    ensure_escrows_not_frozen(deps.as_ref())?;
     
     // Check authorization
     let can_release = info.sender == escrow.client || 
@@ -220,53 +337,53 @@ pub fn release_escrow(
     
     // Check if dispute is active
     if escrow.dispute_status == DisputeStatus::Raised || 
-       escrow.dispute_status == DisputeStatus::UnderReview {
+       escrow.dispute_status == DisputeStatus::UnderReview ||
+       escrow.dispute_status == DisputeStatus::ResolutionProposed ||
+       escrow.dispute_status == DisputeStatus::Appealed {
         return Err(ContractError::DisputePeriodActive {});
     }
     
     escrow.released = true;
     ESCROWS.save(deps.storage, &escrow_id, &escrow)?;
+    record_escrow_event(
+        deps.storage,
+        &escrow_id,
+        EscrowEventKind::Released,
+        &info.sender,
+        escrow.amount.checked_add(escrow.platform_fee)?,
+        env.block.time,
+    )?;
     
-    let freelancer_msg = BankMsg::Send {
-        to_address: escrow.freelancer.to_string(),
-        amount: vec![Coin {
-            denom: XION_DENOM.to_string(),
-            amount: escrow.amount,
-        }],
-    };
+    let payee = job.payout_address.as_ref().unwrap_or(&escrow.freelancer);
+    let freelancer_msg = payout_msg(&escrow.denom, payee, escrow.amount)?;
     
-    let platform_msg = BankMsg::Send {
-        to_address: config.admin.to_string(),
-        amount: vec![Coin {
-            denom: XION_DENOM.to_string(),
-            amount: escrow.platform_fee,
-        }],
-    };
+    // Referrer is paid out of the platform fee; the rest accrues for a later treasury withdrawal
+    let fees = settle_platform_fee(deps.storage, &job, &escrow.denom, escrow.platform_fee)?;
     
-    // Update user stats
-    update_user_stats_on_completion(deps.storage, &escrow.client, &escrow.freelancer, escrow.amount)?;
+    // Update user stats; the completion itself was counted when the job completed
+    record_payout_stats(deps.storage, &escrow.client, &escrow.freelancer, escrow.amount)?;
     
     Ok(Response::new()
         .add_message(freelancer_msg)
-        .add_message(platform_msg)
+        .add_messages(fees.messages)
         .add_attribute("method", "release_escrow")
         .add_attribute("escrow_id", escrow_id)
-        .add_attribute("amount", escrow.amount.to_string()))
+        .add_attribute("amount", escrow.amount.to_string())
+        .add_attributes(payout_attributes(&config.treasury, fees.retained, payee, escrow.amount))
+        .add_attributes(fees.attributes))
 }
 
 pub fn refund_escrow(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     escrow_id: String,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
     let mut escrow = ESCROWS.load(deps.storage, &escrow_id)?;
     
-    // Only admin can refund (for dispute resolution)
-    if info.sender != config.admin {
-        return Err(ContractError::Unauthorized {});
-    }
+    // Only dispute resolvers can refund (for dispute resolution)
+    ensure_role(deps.as_ref(), &info.sender, AdminRole::DisputeResolver)?;
+    ensure_escrows_not_frozen(deps.as_ref())?;
     
     if escrow.released {
         return Err(ContractError::InvalidInput {
@@ -278,13 +395,15 @@ pub fn refund_escrow(
     ESCROWS.save(deps.storage, &escrow_id, &escrow)?;
     
     let total_amount = escrow.amount.checked_add(escrow.platform_fee)?;
-    let refund_msg = BankMsg::Send {
-        to_address: escrow.client.to_string(),
-        amount: vec![Coin {
-            denom: XION_DENOM.to_string(),
-            amount: total_amount,
-        }],
-    };
+    record_escrow_event(
+        deps.storage,
+        &escrow_id,
+        EscrowEventKind::Refunded,
+        &info.sender,
+        total_amount,
+        env.block.time,
+    )?;
+    let refund_msg = payout_msg(&escrow.denom, &escrow.client, total_amount)?;
     
     Ok(Response::new()
         .add_message(refund_msg)
@@ -304,10 +423,7 @@ pub fn raise_dispute(
     let config = CONFIG.load(deps.storage)?;
     let job = JOBS.load(deps.storage, job_id)?;
     
-    // Check if contract is paused
-    if config.paused {
-        return Err(ContractError::ContractPaused {});
-    }
+    ensure_not_paused(deps.as_ref())?;
     
     // Only client or freelancer can raise dispute
     if info.sender != job.poster && 
@@ -317,16 +433,26 @@ pub fn raise_dispute(
     
     // Job must be in progress or completed
     if job.status != crate::state::JobStatus::InProgress && 
+       job.status != crate::state::JobStatus::WorkSubmitted &&
        job.status != crate::state::JobStatus::Completed {
         return Err(ContractError::InvalidInput {
             error: "Can only dispute active or completed jobs".to_string(),
         });
     }
+
+    validate_uri_list(&evidence, "Evidence", MAX_EVIDENCE_ITEMS, MAX_URI_LENGTH)?;
     
     // Check if escrow exists
     let escrow_id = job.escrow_id.clone().ok_or(ContractError::EscrowNotFound {})?;
     let mut escrow = ESCROWS.load(deps.storage, &escrow_id)?;
     
+    // Funds already paid out are beyond the reach of a dispute
+    if escrow.released {
+        return Err(ContractError::InvalidInput {
+            error: "Escrow already released".to_string(),
+        });
+    }
+    
     // Check if dispute already exists
     if escrow.dispute_status != DisputeStatus::None {
         return Err(ContractError::InvalidInput {
@@ -341,6 +467,30 @@ pub fn raise_dispute(
         });
     }
     
+    // Each user waits out the cooldown between disputes, across all jobs
+    if let Some(last) = LAST_DISPUTE.may_load(deps.storage, &info.sender)? {
+        let retry_after = last.plus_seconds(config.dispute_cooldown_seconds);
+        if env.block.time < retry_after {
+            return Err(ContractError::DisputeCooldown { retry_after });
+        }
+    }
+    
+    // The deposit is paid in the escrow's denom, or the payout denom for CW20 escrows
+    let deposit = if config.dispute_deposit.is_zero() {
+        None
+    } else {
+        let denom = match &escrow.denom {
+            Denom::Native(denom) if config.allowed_denoms.contains(denom) => denom.clone(),
+            _ => config.denom.clone(),
+        };
+        validate_exact_funds(&info, &config.allowed_denoms, &denom, config.dispute_deposit)?;
+        Some(Coin {
+            denom,
+            amount: config.dispute_deposit,
+        })
+    };
+    LAST_DISPUTE.save(deps.storage, &info.sender, &env.block.time)?;
+    
     // Create dispute
     let dispute_id = format!("dispute_{}_{}", job_id, env.block.time.seconds());
     let dispute_deadline = env.block.time.plus_seconds(config.dispute_period_days * 24 * 60 * 60);
@@ -355,27 +505,43 @@ pub fn raise_dispute(
         created_at: env.block.time,
         resolved_at: None,
         resolution: None,
+        proposed_release_to_freelancer: None,
+        proposed_freelancer_bps: None,
+        appeal_deadline: None,
+        appealed_by: None,
+        deposit: deposit.clone(),
     };
     
-    DISPUTES.save(deps.storage, &dispute_id, &dispute)?;
+    save_dispute(deps.storage, &dispute)?;
     
     // Update escrow status
     escrow.dispute_status = DisputeStatus::Raised;
     escrow.dispute_raised_at = Some(env.block.time);
     escrow.dispute_deadline = Some(dispute_deadline);
     ESCROWS.save(deps.storage, &escrow_id, &escrow)?;
+    record_escrow_event(
+        deps.storage,
+        &escrow_id,
+        EscrowEventKind::Disputed,
+        &info.sender,
+        Uint128::zero(),
+        env.block.time,
+    )?;
     
     // Update job status
     let mut updated_job = job;
     updated_job.status = crate::state::JobStatus::Disputed;
     updated_job.updated_at = env.block.time;
     JOBS.save(deps.storage, job_id, &updated_job)?;
+    sync_active_job(deps.storage, &updated_job)?;
     
     Ok(Response::new()
         .add_attribute("method", "raise_dispute")
         .add_attribute("job_id", job_id.to_string())
         .add_attribute("dispute_id", dispute_id)
-        .add_attribute("raised_by", info.sender.to_string()))
+        .add_attribute("raised_by", info.sender.to_string())
+        .add_attribute("status", updated_job.status.as_code())
+        .add_attribute("deposit", deposit.map_or_else(|| "0".to_string(), |coin| coin.to_string())))
 }
 
 pub fn resolve_dispute(
@@ -385,45 +551,272 @@ pub fn resolve_dispute(
     dispute_id: String,
     resolution: String,
     release_to_freelancer: bool,
+    freelancer_bps: Option<u16>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     
-    // Only admin can resolve disputes
-    if info.sender != config.admin {
-        return Err(ContractError::Unauthorized {});
-    }
+    // Only dispute resolvers can resolve disputes
+    ensure_role(deps.as_ref(), &info.sender, AdminRole::DisputeResolver)?;
     
     let mut dispute = DISPUTES.load(deps.storage, &dispute_id)?;
     
-    if dispute.status != DisputeStatus::Raised && 
-       dispute.status != DisputeStatus::UnderReview {
+    // Validate resolution
+    if resolution.is_empty() || resolution.len() > 2000 {
+        return Err(ContractError::InvalidInput {
+            error: "Resolution must be between 1-2000 characters".to_string(),
+        });
+    }
+    
+    if freelancer_bps.is_some_and(|bps| bps > BPS_DENOMINATOR) {
         return Err(ContractError::InvalidInput {
+            error: "Freelancer share must be between 0 and 10000 basis points".to_string(),
+        });
+    }
+    
+    match dispute.status {
+        DisputeStatus::Raised | DisputeStatus::UnderReview => {
+            // First step: propose a decision and open the appeal window
+            let appeal_deadline = env.block.time.plus_seconds(config.dispute_period_days * 24 * 60 * 60);
+            dispute.status = DisputeStatus::ResolutionProposed;
+            dispute.proposed_release_to_freelancer = Some(release_to_freelancer);
+            dispute.proposed_freelancer_bps = freelancer_bps;
+            dispute.appeal_deadline = Some(appeal_deadline);
+            dispute.resolution = Some(resolution);
+            save_dispute(deps.storage, &dispute)?;
+            
+            set_escrow_dispute_status(deps.storage, dispute.job_id, DisputeStatus::ResolutionProposed)?;
+            
+            let mut response = Response::new()
+                .add_attribute("method", "propose_dispute_resolution")
+                .add_attribute("dispute_id", dispute_id)
+                .add_attribute("release_to_freelancer", release_to_freelancer.to_string())
+                .add_attribute("appeal_deadline", appeal_deadline.seconds().to_string());
+            if let Some(bps) = freelancer_bps {
+                response = response.add_attribute("freelancer_bps", bps.to_string());
+            }
+            Ok(response)
+        }
+        DisputeStatus::ResolutionProposed | DisputeStatus::Appealed => {
+            // Second admin confirmation settles the dispute immediately, overriding any appeal
+            dispute.resolution = Some(resolution);
+            settle_dispute(
+                deps,
+                &env,
+                &info.sender,
+                dispute,
+                release_to_freelancer,
+                freelancer_bps,
+                "resolve_dispute",
+            )
+        }
+        _ => Err(ContractError::InvalidInput {
             error: "Dispute already resolved".to_string(),
+        }),
+    }
+}
+
+pub fn appeal_dispute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    dispute_id: String,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref())?;
+    
+    let mut dispute = DISPUTES.load(deps.storage, &dispute_id)?;
+    let job = JOBS.load(deps.storage, dispute.job_id)?;
+    
+    // Only client or freelancer can appeal
+    if info.sender != job.poster && 
+       Some(info.sender.clone()) != job.assigned_freelancer {
+        return Err(ContractError::Unauthorized {});
+    }
+    
+    if dispute.status != DisputeStatus::ResolutionProposed {
+        return Err(ContractError::InvalidInput {
+            error: "Dispute has no proposed resolution to appeal".to_string(),
         });
     }
     
-    // Validate resolution
-    if resolution.is_empty() || resolution.len() > 2000 {
+    if let Some(deadline) = dispute.appeal_deadline {
+        if env.block.time > deadline {
+            return Err(ContractError::InvalidInput {
+                error: "Appeal window has closed".to_string(),
+            });
+        }
+    }
+    
+    dispute.status = DisputeStatus::Appealed;
+    dispute.appealed_by = Some(info.sender.clone());
+    save_dispute(deps.storage, &dispute)?;
+    
+    // The appeal reopens the dispute, so admins get a fresh period before it can time out
+    let config = CONFIG.load(deps.storage)?;
+    let escrow_id = job.escrow_id.ok_or(ContractError::EscrowNotFound {})?;
+    let mut escrow = ESCROWS.load(deps.storage, &escrow_id)?;
+    let dispute_deadline = env.block.time.plus_seconds(config.dispute_period_days * 24 * 60 * 60);
+    escrow.dispute_status = DisputeStatus::Appealed;
+    escrow.dispute_deadline = Some(dispute_deadline);
+    ESCROWS.save(deps.storage, &escrow_id, &escrow)?;
+    
+    Ok(Response::new()
+        .add_attribute("method", "appeal_dispute")
+        .add_attribute("dispute_id", dispute_id)
+        .add_attribute("appealed_by", info.sender.to_string())
+        .add_attribute("dispute_deadline", dispute_deadline.seconds().to_string()))
+}
+
+pub fn finalize_dispute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    dispute_id: String,
+) -> Result<Response, ContractError> {
+    let dispute = DISPUTES.load(deps.storage, &dispute_id)?;
+    
+    if dispute.status != DisputeStatus::ResolutionProposed {
         return Err(ContractError::InvalidInput {
-            error: "Resolution must be between 1-2000 characters".to_string(),
+            error: "Dispute has no unappealed resolution to finalize".to_string(),
         });
     }
     
+    // Anyone can finalize once the appeal window has passed
+    let appeal_deadline = dispute.appeal_deadline.ok_or_else(|| ContractError::InvalidInput {
+        error: "Dispute has no appeal deadline".to_string(),
+    })?;
+    if env.block.time <= appeal_deadline {
+        return Err(ContractError::DisputePeriodActive {});
+    }
+    
+    let release_to_freelancer = dispute.proposed_release_to_freelancer.unwrap_or(false);
+    let freelancer_bps = dispute.proposed_freelancer_bps;
+    settle_dispute(
+        deps,
+        &env,
+        &info.sender,
+        dispute,
+        release_to_freelancer,
+        freelancer_bps,
+        "finalize_dispute",
+    )
+}
+
+pub fn claim_dispute_timeout(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    dispute_id: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut dispute = DISPUTES.load(deps.storage, &dispute_id)?;
+    
+    // A proposed resolution is settled through FinalizeDispute instead
+    match dispute.status {
+        DisputeStatus::Raised | DisputeStatus::UnderReview | DisputeStatus::Appealed => {}
+        DisputeStatus::ResolutionProposed => {
+            return Err(ContractError::InvalidInput {
+                error: "Dispute has a proposed resolution; finalize it instead".to_string(),
+            });
+        }
+        _ => {
+            return Err(ContractError::InvalidInput {
+                error: "Dispute already resolved".to_string(),
+            });
+        }
+    }
+    
+    // Anyone can claim once the dispute deadline passes without an admin decision
+    let job = JOBS.load(deps.storage, dispute.job_id)?;
+    let escrow_id = job.escrow_id.ok_or(ContractError::EscrowNotFound {})?;
+    let escrow = ESCROWS.load(deps.storage, &escrow_id)?;
+    let dispute_deadline = escrow.dispute_deadline.ok_or_else(|| ContractError::InvalidInput {
+        error: "Dispute has no deadline".to_string(),
+    })?;
+    if env.block.time <= dispute_deadline {
+        return Err(ContractError::DisputePeriodActive {});
+    }
+    
+    dispute.resolution = Some("Dispute timed out without resolution".to_string());
+    settle_dispute(
+        deps,
+        &env,
+        &info.sender,
+        dispute,
+        config.dispute_timeout_release_to_freelancer,
+        None,
+        "claim_dispute_timeout",
+    )
+}
+
+// Mirror the dispute lifecycle onto the job's escrow
+fn set_escrow_dispute_status(
+    storage: &mut dyn cosmwasm_std::Storage,
+    job_id: u64,
+    status: DisputeStatus,
+) -> Result<(), ContractError> {
+    let job = JOBS.load(storage, job_id)?;
+    let escrow_id = job.escrow_id.ok_or(ContractError::EscrowNotFound {})?;
+    let mut escrow = ESCROWS.load(storage, &escrow_id)?;
+    escrow.dispute_status = status;
+    ESCROWS.save(storage, &escrow_id, &escrow)?;
+    Ok(())
+}
+
+// Close the dispute and pay out the escrow according to the final decision
+fn settle_dispute(
+    deps: DepsMut,
+    env: &Env,
+    actor: &Addr,
+    mut dispute: Dispute,
+    release_to_freelancer: bool,
+    freelancer_bps: Option<u16>,
+    method: &str,
+) -> Result<Response, ContractError> {
+    ensure_escrows_not_frozen(deps.as_ref())?;
+    let dispute_id = dispute.id.clone();
+    
+    // Never pay out an escrow twice, whatever state the dispute reached
+    let mut job = JOBS.load(deps.storage, dispute.job_id)?;
+    let escrow_id = job.escrow_id.clone().ok_or(ContractError::EscrowNotFound {})?;
+    let mut escrow = ESCROWS.load(deps.storage, &escrow_id)?;
+    if escrow.released {
+        return Err(ContractError::InvalidInput {
+            error: "Escrow already released".to_string(),
+        });
+    }
+    
+    // Share of the escrow the decision awards the freelancer, used to settle the deposit
+    let decided_freelancer_bps = freelancer_bps.unwrap_or(if release_to_freelancer {
+        BPS_DENOMINATOR
+    } else {
+        0
+    });
+    
+    // A split resolution counts as completed whenever the freelancer receives a share
+    let release_to_freelancer = freelancer_bps.map_or(release_to_freelancer, |bps| bps > 0);
+    
     // Update dispute
     dispute.status = DisputeStatus::Resolved;
     dispute.resolved_at = Some(env.block.time);
-    dispute.resolution = Some(resolution.clone());
-    DISPUTES.save(deps.storage, &dispute_id, &dispute)?;
+    dispute.proposed_release_to_freelancer = Some(release_to_freelancer);
+    dispute.proposed_freelancer_bps = freelancer_bps;
+    save_dispute(deps.storage, &dispute)?;
     
     // Get job and escrow
-    let mut job = JOBS.load(deps.storage, dispute.job_id)?;
-    let escrow_id = job.escrow_id.clone().ok_or(ContractError::EscrowNotFound {})?;
-    let mut escrow = ESCROWS.load(deps.storage, &escrow_id)?;
+    let freelancer = job.assigned_freelancer.clone().unwrap_or_else(|| escrow.freelancer.clone());
+    let payee = job.payout_address.clone().unwrap_or_else(|| freelancer.clone());
+    let treasury = CONFIG.load(deps.storage)?.treasury;
     
     // Update escrow and job status
     escrow.dispute_status = DisputeStatus::Resolved;
     ESCROWS.save(deps.storage, &escrow_id, &escrow)?;
     
+    // A job already completed before the dispute was counted then
+    if release_to_freelancer && job.status != crate::state::JobStatus::Completed {
+        let mut freelancer_stats = USER_STATS.may_load(deps.storage, &freelancer)?.unwrap_or_default();
+        record_job_completion(&mut freelancer_stats)?;
+        USER_STATS.save(deps.storage, &freelancer, &freelancer_stats)?;
+    }
     job.status = if release_to_freelancer {
         crate::state::JobStatus::Completed
     } else {
@@ -431,65 +824,189 @@ pub fn resolve_dispute(
     };
     job.updated_at = env.block.time;
     JOBS.save(deps.storage, dispute.job_id, &job)?;
+    sync_active_job(deps.storage, &job)?;
     
     // Release funds based on resolution
     let mut response = Response::new()
-        .add_attribute("method", "resolve_dispute")
+        .add_attribute("method", method)
         .add_attribute("dispute_id", dispute_id)
-        .add_attribute("resolution", resolution)
-        .add_attribute("release_to_freelancer", release_to_freelancer.to_string());
+        .add_attribute("resolution", dispute.resolution.unwrap_or_default())
+        .add_attribute("release_to_freelancer", release_to_freelancer.to_string())
+        .add_attribute("status", job.status.as_code());
     
-    if release_to_freelancer {
-        // Release to freelancer
-        response = response.add_message(BankMsg::Send {
-            to_address: escrow.freelancer.to_string(),
-            amount: vec![Coin {
-                denom: XION_DENOM.to_string(),
-                amount: escrow.amount,
-            }],
-        });
+    if let Some(bps) = freelancer_bps {
+        let (freelancer_share, client_share, fee_share) =
+            split_escrow(escrow.amount, escrow.platform_fee, bps)?;
         
-        // Platform fee to admin
-        response = response.add_message(BankMsg::Send {
-            to_address: config.admin.to_string(),
-            amount: vec![Coin {
-                denom: XION_DENOM.to_string(),
-                amount: escrow.platform_fee,
-            }],
-        });
+        accrue_platform_fee(deps.storage, &escrow.denom, fee_share)?;
+        
+        for (recipient, amount) in [
+            (&payee, freelancer_share),
+            (&escrow.client, client_share),
+        ] {
+            if !amount.is_zero() {
+                response = response.add_message(payout_msg(&escrow.denom, recipient, amount)?);
+            }
+        }
+        
+        if !freelancer_share.is_zero() {
+            record_payout_stats(deps.storage, &escrow.client, &freelancer, freelancer_share)?;
+        }
+        
+        response = response
+            .add_attribute("freelancer_bps", bps.to_string())
+            .add_attribute("freelancer_amount", freelancer_share.to_string())
+            .add_attribute("client_amount", client_share.to_string())
+            .add_attributes(payout_attributes(&treasury, fee_share, &payee, freelancer_share));
+    } else if release_to_freelancer {
+        // Release to freelancer
+        response = response.add_message(payout_msg(&escrow.denom, &payee, escrow.amount)?);
         
         // Update user stats for successful completion
-        update_user_stats_on_completion(deps.storage, &escrow.client, &escrow.freelancer, escrow.amount)?;
+        record_payout_stats(deps.storage, &escrow.client, &freelancer, escrow.amount)?;
+        
+        // Platform fee accrues for a later treasury withdrawal
+        accrue_platform_fee(deps.storage, &escrow.denom, escrow.platform_fee)?;
+        response = response.add_attributes(payout_attributes(
+            &treasury,
+            escrow.platform_fee,
+            &payee,
+            escrow.amount,
+        ));
     } else {
         // Refund to client (minus platform fee for dispute resolution)
         let refund_amount = escrow.amount;
-        response = response.add_message(BankMsg::Send {
-            to_address: escrow.client.to_string(),
-            amount: vec![Coin {
-                denom: XION_DENOM.to_string(),
-                amount: refund_amount,
-            }],
-        });
+        response = response.add_message(payout_msg(&escrow.denom, &escrow.client, refund_amount)?);
         
-        // Platform fee to admin
-        response = response.add_message(BankMsg::Send {
-            to_address: config.admin.to_string(),
-            amount: vec![Coin {
-                denom: XION_DENOM.to_string(),
-                amount: escrow.platform_fee,
-            }],
-        });
+        // Platform fee is still earned for handling the dispute
+        accrue_platform_fee(deps.storage, &escrow.denom, escrow.platform_fee)?;
+        response = response.add_attributes(payout_attributes(
+            &treasury,
+            escrow.platform_fee,
+            &escrow.client,
+            refund_amount,
+        ));
+    }
+    
+    // The raiser gets the deposit back when awarded at least half the escrow,
+    // otherwise it is kept as a platform fee
+    if let Some(deposit) = dispute.deposit {
+        let raiser_bps = if dispute.raised_by == job.poster {
+            BPS_DENOMINATOR - decided_freelancer_bps
+        } else {
+            decided_freelancer_bps
+        };
+        let refunded = raiser_bps * 2 >= BPS_DENOMINATOR;
+        if refunded {
+            response = response.add_message(BankMsg::Send {
+                to_address: dispute.raised_by.to_string(),
+                amount: vec![deposit.clone()],
+            });
+        } else {
+            accrue_platform_fee(deps.storage, &Denom::Native(deposit.denom.clone()), deposit.amount)?;
+        }
+        response = response
+            .add_attribute("deposit", deposit.to_string())
+            .add_attribute("deposit_refunded", refunded.to_string());
     }
     
     // Mark escrow as released
     escrow.released = true;
     ESCROWS.save(deps.storage, &escrow_id, &escrow)?;
+    record_escrow_event(
+        deps.storage,
+        &escrow_id,
+        EscrowEventKind::Resolved,
+        actor,
+        escrow.amount.checked_add(escrow.platform_fee)?,
+        env.block.time,
+    )?;
     
     Ok(response)
 }
 
-// Helper function to update user statistics
-fn update_user_stats_on_completion(
+/// Attributes every payout emits so indexers can reconcile fee revenue
+pub fn payout_attributes(
+    fee_recipient: &Addr,
+    fee_amount: Uint128,
+    payout_recipient: &Addr,
+    net_payout: Uint128,
+) -> Vec<Attribute> {
+    vec![
+        Attribute::new("fee_recipient", fee_recipient.to_string()),
+        Attribute::new("fee_amount", fee_amount.to_string()),
+        Attribute::new("payout_recipient", payout_recipient.to_string()),
+        Attribute::new("net_payout", net_payout.to_string()),
+    ]
+}
+
+/// Record a platform fee earned in `denom`; fees are swept to the treasury via WithdrawFees
+pub fn accrue_platform_fee(
+    storage: &mut dyn cosmwasm_std::Storage,
+    denom: &Denom,
+    fee: Uint128,
+) -> StdResult<()> {
+    if fee.is_zero() {
+        return Ok(());
+    }
+    let key = escrow_denom(denom);
+    let accrued = ACCRUED_FEES.may_load(storage, &key)?.unwrap_or_default();
+    ACCRUED_FEES.save(storage, &key, &accrued.checked_add(fee)?)
+}
+
+/// Platform fee left after paying the job's referrer, plus the referral payout if any
+pub struct FeeSettlement {
+    pub retained: Uint128,
+    pub messages: Vec<CosmosMsg>,
+    pub attributes: Vec<Attribute>,
+}
+
+/// Pay the job's referrer their share of the platform fee and accrue the rest.
+/// The freelancer's payout is untouched, so payout + retained + referral = amount + fee.
+pub fn settle_platform_fee(
+    storage: &mut dyn cosmwasm_std::Storage,
+    job: &Job,
+    denom: &Denom,
+    fee: Uint128,
+) -> StdResult<FeeSettlement> {
+    let referral = job
+        .referrer
+        .as_ref()
+        .map(|referrer| (referrer, fee.multiply_ratio(job.referral_bps, BPS_DENOMINATOR)))
+        .filter(|(_, cut)| !cut.is_zero());
+    let retained = fee.checked_sub(referral.map(|(_, cut)| cut).unwrap_or_default())?;
+    accrue_platform_fee(storage, denom, retained)?;
+
+    let mut settlement = FeeSettlement {
+        retained,
+        messages: vec![],
+        attributes: vec![],
+    };
+    if let Some((referrer, cut)) = referral {
+        settlement.messages.push(payout_msg(denom, referrer, cut)?);
+        settlement.attributes.push(Attribute::new("referrer", referrer.to_string()));
+        settlement.attributes.push(Attribute::new("referral_amount", cut.to_string()));
+    }
+    Ok(settlement)
+}
+
+// Split an escrow by the freelancer's basis points. The platform fee comes off
+// the top; only the net amount is divided between freelancer and client.
+// Returns (freelancer_share, client_share, fee_share), which sum to amount + platform_fee.
+fn split_escrow(
+    amount: Uint128,
+    platform_fee: Uint128,
+    freelancer_bps: u16,
+) -> Result<(Uint128, Uint128, Uint128), ContractError> {
+    let freelancer_share = amount.multiply_ratio(freelancer_bps, BPS_DENOMINATOR);
+    let client_share = amount.checked_sub(freelancer_share)?;
+    Ok((freelancer_share, client_share, platform_fee))
+}
+
+/// Credit an escrow payout to the client's spending and the freelancer's earnings.
+/// Every release path pays `amount` from the escrow, so earnings match under both
+/// release policies; completions are counted when the job completes, not here.
+pub fn record_payout_stats(
     storage: &mut dyn cosmwasm_std::Storage,
     client: &Addr,
     freelancer: &Addr,
@@ -503,21 +1020,6 @@ fn update_user_stats_on_completion(
     // Update freelancer stats
     let mut freelancer_stats = USER_STATS.may_load(storage, freelancer)?.unwrap_or_default();
     freelancer_stats.total_earned = freelancer_stats.total_earned.checked_add(amount)?;
-    freelancer_stats.total_jobs_completed = freelancer_stats.total_jobs_completed.checked_add(1)
-        .ok_or_else(|| cosmwasm_std::StdError::overflow(cosmwasm_std::OverflowError::new(
-            cosmwasm_std::OverflowOperation::Add, 
-            "jobs completed counter", 
-            "overflow"
-        )))?;
-    
-    // Calculate completion rate
-    if freelancer_stats.total_jobs_posted > 0 {
-        freelancer_stats.completion_rate = Decimal::from_ratio(
-            freelancer_stats.total_jobs_completed, 
-            freelancer_stats.total_jobs_posted
-        );
-    }
-    
     USER_STATS.save(storage, freelancer, &freelancer_stats)?;
     
     Ok(())
@@ -530,5 +1032,5 @@ pub fn create_escrow(
     info: MessageInfo,
     job_id: u64,
 ) -> Result<Response, ContractError> {
-    create_escrow_native(deps, env, info, job_id)
+    create_escrow_native(deps, env, info, job_id, false)
 }
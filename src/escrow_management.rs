@@ -1,6 +1,6 @@
 use crate::contract_helpers::*;
 use crate::error::ContractError;
-use crate::helpers::ensure_not_paused;
+use crate::helpers::{describe_funds, ensure_not_paused};
 use crate::job_management::calculate_platform_fee;
 use crate::msg::{EscrowResponse, EscrowsResponse};
 use crate::security::{check_rate_limit, reentrancy_guard, RateLimitAction};
@@ -10,6 +10,7 @@ use cosmwasm_std::{
     coins, Addr, BankMsg, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult,
     Uint128,
 };
+use cw20::Denom;
 
 /// Create escrow for job or bounty
 #[allow(clippy::too_many_arguments)]
@@ -42,7 +43,10 @@ pub fn execute_create_escrow(
 
     // Validate payment
     if info.funds.len() != 1 || info.funds[0].amount != amount {
-        return Err(ContractError::InvalidFunds {});
+        return Err(ContractError::InvalidFunds {
+            expected: format!("{} in a single denom", amount),
+            received: describe_funds(&info.funds),
+        });
     }
 
     // Check if escrow already exists
@@ -74,6 +78,7 @@ pub fn execute_create_escrow(
         dispute_status: DisputeStatus::None,
         dispute_raised_at: None,
         dispute_deadline: None,
+        denom: Denom::Native(info.funds[0].denom.clone()),
     };
 
     ESCROWS.save(deps.storage, &escrow_id, &escrow)?;
@@ -184,7 +189,7 @@ pub fn execute_refund_escrow(
 
 /// Query a specific escrow
 pub fn query_escrow(deps: Deps, escrow_id: String) -> StdResult<EscrowResponse> {
-    let escrow = ESCROWS.load(deps.storage, &escrow_id)?;
+    let escrow = ESCROWS.may_load(deps.storage, &escrow_id)?;
     Ok(EscrowResponse { escrow })
 }
 
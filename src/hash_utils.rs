@@ -178,6 +178,44 @@ pub fn create_proposal_content_bundle(
     )
 }
 
+/// Helper to create the bundle for a proposal edit. Only the edited fields are
+/// included; the rest carry over from the bundle at `previous_hash`
+pub fn create_proposal_edit_bundle(
+    proposal_id: u64,
+    cover_letter: Option<&str>,
+    milestones: Option<&[serde_json::Value]>,
+    previous_hash: Option<&str>,
+    timestamp: u64,
+) -> Result<(OffChainBundle, String), StdError> {
+    let mut fields = std::collections::HashMap::new();
+    if let Some(cover_letter) = cover_letter {
+        fields.insert(
+            "cover_letter".to_string(),
+            serde_json::Value::String(cover_letter.to_string()),
+        );
+    }
+    if let Some(milestones) = milestones {
+        fields.insert(
+            "milestones".to_string(),
+            serde_json::Value::Array(milestones.to_vec()),
+        );
+    }
+    if let Some(previous_hash) = previous_hash {
+        fields.insert(
+            "previous_hash".to_string(),
+            serde_json::Value::String(previous_hash.to_string()),
+        );
+    }
+
+    create_off_chain_bundle(
+        proposal_id.to_string(),
+        "proposal".to_string(),
+        "proposal_content".to_string(),
+        fields,
+        timestamp,
+    )
+}
+
 /// Helper to create bounty content bundle
 pub fn create_bounty_content_bundle(
     bounty_id: u64,
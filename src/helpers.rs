@@ -1,11 +1,13 @@
 use cosmwasm_std::{
-    Addr, Deps, Order, StdResult, Storage, Timestamp, Uint128, Decimal
+    coins, to_json_binary, Addr, Api, BankMsg, Coin, CosmosMsg, Deps, MessageInfo, Order, StdResult, Storage, Timestamp, Uint128, Uint64, Decimal, WasmMsg
 };
-use cw_storage_plus::Bound;
+use cw20::{Cw20ExecuteMsg, Denom};
+use cw_storage_plus::{Bound, Item};
 
 use crate::state::{
-    Job, JobStatus, Proposal, 
-    JOBS, PROPOSALS, RATINGS, USER_STATS
+    AdminRole, Bounty, BountyStatus, Config, Dispute, DisputeStatus, EscrowEvent, EscrowEventKind, Job,
+    JobStatus, Proposal, ACTIVE_BOUNTIES, ACTIVE_JOBS, OPEN_JOB_COUNT, DISPUTES, DISPUTES_BY_STATUS, ESCROW_EVENTS,
+    CONTENT_HASHES, ENTITY_TO_HASH, HASH_TO_ENTITY, JOBS, LAST_EDIT, PROPOSALS, RATINGS, USER_STATS, UserStats
 };
 use crate::error::ContractError;
 
@@ -28,6 +30,239 @@ pub fn validate_job_description(description: &str) -> Result<(), ContractError>
     Ok(())
 }
 
+/// Drop an entity's content-hash mappings once the entity is deleted or withdrawn
+pub fn remove_entity_hashes(storage: &mut dyn Storage, entity_key: &str) -> StdResult<()> {
+    if let Some(hash) = ENTITY_TO_HASH.may_load(storage, entity_key)? {
+        CONTENT_HASHES.remove(storage, &hash);
+        HASH_TO_ENTITY.remove(storage, &hash);
+    }
+    ENTITY_TO_HASH.remove(storage, entity_key);
+    LAST_EDIT.remove(storage, entity_key);
+    Ok(())
+}
+
+/// Count one removed entity so `GetCounts` can subtract it from the id counter
+pub fn record_removal(storage: &mut dyn Storage, tally: &Item<u64>) -> StdResult<()> {
+    let removed = tally.may_load(storage)?.unwrap_or_default();
+    tally.save(storage, &increment_counter(removed)?)
+}
+
+/// Enforce a cooldown between edits of one entity, then record this edit
+pub fn throttle_edit(
+    storage: &mut dyn Storage,
+    entity_key: &str,
+    min_interval_seconds: u64,
+    now: Timestamp,
+) -> Result<(), ContractError> {
+    if let Some(last_edit) = LAST_EDIT.may_load(storage, entity_key)? {
+        let next_edit_at = last_edit.plus_seconds(min_interval_seconds);
+        if now < next_edit_at {
+            return Err(ContractError::EditTooSoon { next_edit_at });
+        }
+    }
+    LAST_EDIT.save(storage, entity_key, &now)?;
+    Ok(())
+}
+
+/// Reject native denoms outside `Config::allowed_denoms`
+pub fn ensure_denom_allowed(allowed_denoms: &[String], denom: &str) -> Result<(), ContractError> {
+    if !allowed_denoms.iter().any(|allowed| allowed == denom) {
+        return Err(ContractError::DenomNotAllowed {
+            denom: denom.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Validate a native denom the way the bank module does; `ibc/` vouchers must carry a
+/// 64 character uppercase hex trace hash. Denoms are case-sensitive and stored verbatim.
+pub fn validate_denom(denom: &str) -> Result<(), ContractError> {
+    let invalid = || ContractError::InvalidInput {
+        error: format!("Invalid denom: {}", denom),
+    };
+    if !(3..=128).contains(&denom.len()) || !denom.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return Err(invalid());
+    }
+    if !denom
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c))
+    {
+        return Err(invalid());
+    }
+    if let Some(hash) = denom.strip_prefix("ibc/") {
+        if hash.len() != 64 || !hash.chars().all(|c| matches!(c, '0'..='9' | 'A'..='F')) {
+            return Err(invalid());
+        }
+    }
+    Ok(())
+}
+
+/// Validate a denom whitelist; the payout `denom` must stay on it
+pub fn validate_allowed_denoms(allowed_denoms: &[String], denom: &str) -> Result<(), ContractError> {
+    if allowed_denoms.is_empty() {
+        return Err(ContractError::InvalidInput {
+            error: "Allowed denoms cannot be empty".to_string(),
+        });
+    }
+    for (i, allowed) in allowed_denoms.iter().enumerate() {
+        validate_denom(allowed)?;
+        if allowed_denoms[..i].contains(allowed) {
+            return Err(ContractError::InvalidInput {
+                error: "Allowed denoms must be unique".to_string(),
+            });
+        }
+    }
+    if !allowed_denoms.iter().any(|allowed| allowed == denom) {
+        return Err(ContractError::InvalidInput {
+            error: format!("Allowed denoms must include the payout denom {}", denom),
+        });
+    }
+    Ok(())
+}
+
+/// Validate a CW20 token whitelist; unlike native denoms it may be empty
+pub fn validate_allowed_cw20_tokens(api: &dyn Api, tokens: &[String]) -> Result<Vec<Addr>, ContractError> {
+    let mut allowed = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        let token = api.addr_validate(token)?;
+        if allowed.contains(&token) {
+            return Err(ContractError::InvalidInput {
+                error: "Allowed CW20 tokens must be unique".to_string(),
+            });
+        }
+        allowed.push(token);
+    }
+    Ok(allowed)
+}
+
+/// Reject CW20 tokens outside `Config::allowed_cw20_tokens`
+pub fn ensure_cw20_allowed(allowed_tokens: &[Addr], token: &Addr) -> Result<(), ContractError> {
+    if !allowed_tokens.contains(token) {
+        return Err(ContractError::DenomNotAllowed {
+            denom: token.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// The denom a job is posted in: the payout denom unless a whitelisted native
+/// denom or CW20 token is named
+pub fn resolve_job_denom(
+    api: &dyn Api,
+    config: &Config,
+    denom: Option<Denom>,
+) -> Result<Denom, ContractError> {
+    match denom {
+        None => Ok(Denom::Native(config.denom.clone())),
+        Some(Denom::Native(denom)) => {
+            ensure_denom_allowed(&config.allowed_denoms, &denom)?;
+            Ok(Denom::Native(denom))
+        }
+        Some(Denom::Cw20(token)) => {
+            let token = api.addr_validate(token.as_str())?;
+            ensure_cw20_allowed(&config.allowed_cw20_tokens, &token)?;
+            Ok(Denom::Cw20(token))
+        }
+    }
+}
+
+/// Escrow denom label: the native denom, or the CW20 token address
+pub fn escrow_denom(denom: &Denom) -> String {
+    match denom {
+        Denom::Native(denom) => denom.clone(),
+        Denom::Cw20(token) => token.to_string(),
+    }
+}
+
+/// Build a payout in an escrow's or bounty's denom: a bank send for native
+/// denoms or a CW20 transfer for token-funded ones
+pub fn payout_msg(denom: &Denom, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(match denom {
+        Denom::Native(denom) => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(amount.u128(), denom),
+        }
+        .into(),
+        Denom::Cw20(token) => WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    })
+}
+
+/// Require exactly one whitelisted coin of `denom` matching `expected`
+pub fn validate_exact_funds(
+    info: &MessageInfo,
+    allowed_denoms: &[String],
+    denom: &str,
+    expected: Uint128,
+) -> Result<(), ContractError> {
+    ensure_denom_allowed(allowed_denoms, denom)?;
+    match info.funds.as_slice() {
+        [coin] if coin.denom == denom => {
+            if coin.amount < expected {
+                return Err(ContractError::InsufficientFunds {
+                    expected: expected.to_string(),
+                    actual: coin.amount.to_string(),
+                });
+            }
+            if coin.amount > expected {
+                return Err(ContractError::InvalidFunds {
+                    expected: format!("{}{}", expected, denom),
+                    received: describe_funds(&info.funds),
+                });
+            }
+            Ok(())
+        }
+        _ => Err(ContractError::InvalidFunds {
+            expected: format!("{}{}", expected, denom),
+            received: describe_funds(&info.funds),
+        }),
+    }
+}
+
+/// Like `validate_exact_funds`, but accepts overpayment and returns the surplus
+pub fn validate_min_funds(
+    info: &MessageInfo,
+    allowed_denoms: &[String],
+    denom: &str,
+    expected: Uint128,
+) -> Result<Uint128, ContractError> {
+    ensure_denom_allowed(allowed_denoms, denom)?;
+    match info.funds.as_slice() {
+        [coin] if coin.denom == denom => {
+            if coin.amount < expected {
+                return Err(ContractError::InsufficientFunds {
+                    expected: expected.to_string(),
+                    actual: coin.amount.to_string(),
+                });
+            }
+            Ok(coin.amount - expected)
+        }
+        _ => Err(ContractError::InvalidFunds {
+            expected: format!("{}{}", expected, denom),
+            received: describe_funds(&info.funds),
+        }),
+    }
+}
+
+/// Render attached funds for error messages, e.g. "100uxion, 5uatom"
+pub fn describe_funds(funds: &[Coin]) -> String {
+    if funds.is_empty() {
+        return "no funds".to_string();
+    }
+    funds
+        .iter()
+        .map(|coin| coin.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 pub fn validate_budget(budget: Uint128) -> Result<(), ContractError> {
     // Allow budget = 0 for free projects
     if budget.is_zero() {
@@ -114,6 +349,90 @@ pub fn query_jobs_paginated(
     jobs
 }
 
+// Active index helpers; call after every status change so only Open entities are listed
+/// Keep the ACTIVE_JOBS marker and the poster's OPEN_JOB_COUNT in step with the job's status
+pub fn sync_active_job(storage: &mut dyn Storage, job: &Job) -> StdResult<()> {
+    let was_open = ACTIVE_JOBS.has(storage, job.id);
+    let is_open = job.status == JobStatus::Open;
+    if is_open && !was_open {
+        ACTIVE_JOBS.save(storage, job.id, &true)?;
+        OPEN_JOB_COUNT.update(storage, &job.poster, |count| -> StdResult<_> {
+            Ok(count.unwrap_or_default() + 1)
+        })?;
+    } else if !is_open && was_open {
+        clear_active_job(storage, job)?;
+    }
+    Ok(())
+}
+
+/// Drop the job's ACTIVE_JOBS marker, releasing its slot in the poster's OPEN_JOB_COUNT
+pub fn clear_active_job(storage: &mut dyn Storage, job: &Job) -> StdResult<()> {
+    if ACTIVE_JOBS.has(storage, job.id) {
+        ACTIVE_JOBS.remove(storage, job.id);
+        OPEN_JOB_COUNT.update(storage, &job.poster, |count| -> StdResult<_> {
+            Ok(count.unwrap_or_default().saturating_sub(1))
+        })?;
+    }
+    Ok(())
+}
+
+pub fn sync_active_bounty(storage: &mut dyn Storage, bounty: &Bounty) -> StdResult<()> {
+    if bounty.status == BountyStatus::Open {
+        ACTIVE_BOUNTIES.save(storage, bounty.id, &true)
+    } else {
+        ACTIVE_BOUNTIES.remove(storage, bounty.id);
+        Ok(())
+    }
+}
+
+/// Stable index key for a dispute status; never renumber existing entries
+pub fn dispute_status_key(status: &DisputeStatus) -> u8 {
+    match status {
+        DisputeStatus::None => 0,
+        DisputeStatus::Raised => 1,
+        DisputeStatus::UnderReview => 2,
+        DisputeStatus::ResolutionProposed => 3,
+        DisputeStatus::Appealed => 4,
+        DisputeStatus::Resolved => 5,
+    }
+}
+
+// Persist a dispute and move it to its current status in DISPUTES_BY_STATUS
+pub fn save_dispute(storage: &mut dyn Storage, dispute: &Dispute) -> StdResult<()> {
+    if let Some(previous) = DISPUTES.may_load(storage, &dispute.id)? {
+        DISPUTES_BY_STATUS.remove(storage, (dispute_status_key(&previous.status), &previous.id));
+    }
+    DISPUTES_BY_STATUS.save(storage, (dispute_status_key(&dispute.status), &dispute.id), &true)?;
+    DISPUTES.save(storage, &dispute.id, dispute)
+}
+
+// Append a transition to an escrow's audit trail under the next sequence number
+pub fn record_escrow_event(
+    storage: &mut dyn Storage,
+    escrow_id: &str,
+    kind: EscrowEventKind,
+    actor: &Addr,
+    amount: Uint128,
+    timestamp: Timestamp,
+) -> StdResult<()> {
+    let next_seq = ESCROW_EVENTS
+        .prefix(escrow_id)
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(0, |seq| seq + 1);
+    ESCROW_EVENTS.save(
+        storage,
+        (escrow_id, next_seq),
+        &EscrowEvent {
+            kind,
+            actor: actor.clone(),
+            amount,
+            timestamp,
+        },
+    )
+}
+
 pub fn query_user_proposals(
     storage: &dyn Storage,
     user: &Addr,
@@ -152,7 +471,7 @@ pub fn calculate_user_average_rating(
         .filter_map(|item| {
             match item {
                 Ok((_, rating)) => {
-                    if rating.rated == *user {
+                    if rating.rated == *user && !rating.pending {
                         Some(Ok(rating))
                     } else {
                         None
@@ -176,6 +495,33 @@ pub fn calculate_user_average_rating(
     Ok((average, total_ratings))
 }
 
+/// Checked `+ 1` for the UserStats counters
+pub fn increment_counter(counter: u64) -> StdResult<u64> {
+    Ok(Uint64::new(counter).checked_add(Uint64::one())?.u64())
+}
+
+/// Count a job the user was hired for
+pub fn record_job_assignment(stats: &mut UserStats) -> StdResult<()> {
+    stats.total_jobs_assigned = increment_counter(stats.total_jobs_assigned)?;
+    refresh_completion_rate(stats);
+    Ok(())
+}
+
+/// Count a job the user delivered; only completion handlers call this
+pub fn record_job_completion(stats: &mut UserStats) -> StdResult<()> {
+    stats.total_jobs_completed = increment_counter(stats.total_jobs_completed)?;
+    refresh_completion_rate(stats);
+    Ok(())
+}
+
+fn refresh_completion_rate(stats: &mut UserStats) {
+    // Stats written before assignments were tracked can have more completions than assignments
+    let assigned = stats.total_jobs_assigned.max(stats.total_jobs_completed);
+    if assigned > 0 {
+        stats.completion_rate = Decimal::from_ratio(stats.total_jobs_completed, assigned);
+    }
+}
+
 pub fn update_user_rating_stats(
     storage: &mut dyn Storage,
     user: &Addr,
@@ -200,14 +546,32 @@ pub fn ensure_not_paused(deps: Deps) -> Result<(), ContractError> {
     Ok(())
 }
 
-pub fn ensure_admin(deps: Deps, sender: &Addr) -> Result<(), ContractError> {
+pub fn ensure_escrows_not_frozen(deps: Deps) -> Result<(), ContractError> {
     let config = crate::state::CONFIG.load(deps.storage)?;
-    if *sender != config.admin {
-        return Err(ContractError::Unauthorized {});
+    if config.escrows_frozen {
+        return Err(ContractError::EscrowsFrozen {});
     }
     Ok(())
 }
 
+pub fn ensure_admin(deps: Deps, sender: &Addr) -> Result<(), ContractError> {
+    ensure_role(deps, sender, AdminRole::SuperAdmin)
+}
+
+/// Config admin and super admins pass every role check
+pub fn ensure_role(deps: Deps, sender: &Addr, role: AdminRole) -> Result<(), ContractError> {
+    let config = crate::state::CONFIG.load(deps.storage)?;
+    if *sender == config.admin {
+        return Ok(());
+    }
+
+    match crate::state::ADMINS.may_load(deps.storage, sender)? {
+        Some(AdminRole::SuperAdmin) => Ok(()),
+        Some(granted) if granted == role => Ok(()),
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
+
 // Math helpers with overflow protection  
 pub fn safe_multiply_percentage(amount: Uint128, percentage: u64) -> Result<Uint128, ContractError> {
     if percentage > 100 {
@@ -233,8 +597,13 @@ pub fn safe_multiply_percentage(amount: Uint128, percentage: u64) -> Result<Uint
 }
 
 // Time helpers
-pub fn get_future_timestamp(current: Timestamp, days: u64) -> Timestamp {
-    current.plus_seconds(days * 24 * 60 * 60)
+/// `current` plus `days`, rejecting durations that would overflow the timestamp
+pub fn get_future_timestamp(current: Timestamp, days: u64) -> Result<Timestamp, ContractError> {
+    const NANOS_PER_DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+    days.checked_mul(NANOS_PER_DAY)
+        .and_then(|nanos| current.nanos().checked_add(nanos))
+        .map(Timestamp::from_nanos)
+        .ok_or(ContractError::DurationTooLong { days })
 }
 
 pub fn is_expired(deadline: Timestamp, current: Timestamp) -> bool {
@@ -276,3 +645,22 @@ pub fn estimate_hours_from_reward_and_difficulty(reward: Uint128, difficulty: u8
     
     ((amount / hourly_rate) as u16).max(1).min(500) // Cap at 500 hours
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::mock_env;
+
+    #[test]
+    fn test_get_future_timestamp() {
+        let now = mock_env().block.time;
+        assert_eq!(
+            get_future_timestamp(now, 2).unwrap(),
+            now.plus_seconds(2 * 24 * 60 * 60)
+        );
+        assert_eq!(
+            get_future_timestamp(now, u64::MAX).unwrap_err(),
+            ContractError::DurationTooLong { days: u64::MAX }
+        );
+    }
+}
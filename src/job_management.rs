@@ -1,28 +1,258 @@
+use crate::category_skill_manager::{
+    category_id, index_job_skills, skill_tags, unindex_job_skills,
+};
 use crate::contract_helpers::*;
 use crate::error::ContractError;
 use crate::hash_utils::{
     create_content_hash, create_job_content_bundle, create_proposal_content_bundle,
+    create_proposal_edit_bundle,
+};
+use crate::helpers::{
+    describe_funds, ensure_escrows_not_frozen, ensure_not_paused, escrow_denom, get_future_timestamp, payout_msg, record_escrow_event, sync_active_job, clear_active_job,
+    record_removal, remove_entity_hashes, resolve_job_denom, throttle_edit,
+    validate_budget, validate_duration, validate_exact_funds,
+};
+use crate::msg::{
+    JobTemplateOverrides, JobTemplatesResponse, MilestoneInput, PostJobItem,
+};
+use crate::security::{
+    check_rate_limit, reentrancy_guard, RateLimitAction,
 };
-use crate::helpers::{ensure_not_paused, get_future_timestamp, validate_budget, validate_duration};
-use crate::msg::{JobResponse, JobsResponse, MilestoneInput, ProposalResponse, ProposalsResponse};
-use crate::security::{check_rate_limit, reentrancy_guard, RateLimitAction};
 use crate::state::{
-    ContactPreference, Job, JobStatus, Proposal, ProposalMilestone, ProposalStatus, Rating, CONFIG,
-    CONTENT_HASHES, DISPUTES, ENTITY_TO_HASH, ESCROWS, HASH_TO_ENTITY, JOBS, JOB_PROPOSALS,
-    NEXT_JOB_ID, NEXT_PROPOSAL_ID, PROPOSALS, RATINGS,
+    AdminRole, Config, ContactPreference, EscrowEventKind, Job, JobStatus, JobTemplate, Proposal, ReleasePolicy, ProposalMilestone, ProposalStatus, UserStats, ACTIVE_JOBS,
+    ARCHIVED_COMPLETED_JOB_COUNT, ARCHIVED_JOBS, ARCHIVED_JOB_COUNT, CONFIG,
+    CONTENT_HASHES, ENTITY_TO_HASH, ESCROWS, HASH_TO_ENTITY, JOBS, JOBS_BY_BUDGET_RANGE,
+    JOB_PROPOSALS, JOB_TEMPLATES,
+    NEXT_JOB_ID, NEXT_PROPOSAL_ID, OPEN_JOB_COUNT, PROPOSALS, REMOVED_JOB_COUNT, USER_JOBS, USER_PROPOSALS, USER_STATS,
 };
 // Import macros explicitly
-use crate::{apply_security_checks, build_success_response, ensure_admin, validate_content_inputs};
+use crate::{apply_security_checks, build_success_response, ensure_role, validate_content_inputs};
 // Remove the explicit crate prefixes for macros
 use cosmwasm_std::{
-    coins, Addr, BankMsg, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Uint128,
+    Addr, Decimal, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage, Timestamp, Uint128,
 };
+use cw20::Denom;
+
+/// Hard ceiling for `Config::max_proposals_per_job`, keeping the reject-on-accept pass bounded
+pub const MAX_PROPOSALS_PER_JOB: u64 = 200;
+
+/// Largest number of listings accepted by a single `PostJobsBatch`
+pub const MAX_JOBS_PER_BATCH: usize = 20;
+
+/// Largest number of job templates a poster can keep
+pub const MAX_JOB_TEMPLATES_PER_POSTER: usize = 20;
+
+/// Heuristic 0-100 proposal ranking: up to 50 points for the freelancer's
+/// average rating, 30 for completed jobs and 20 for delivering within the job's duration
+pub fn proposal_score(stats: &UserStats, delivery_time_days: u64, job_duration_days: u64) -> u8 {
+    let rating_points = (stats.average_rating * Decimal::from_ratio(10u128, 1u128))
+        .to_uint_floor()
+        .u128()
+        .min(50) as u8;
+    let experience_points = (stats.total_jobs_completed.min(10) * 3) as u8;
+    let delivery_points = if delivery_time_days <= job_duration_days {
+        20
+    } else {
+        (20 * job_duration_days / delivery_time_days) as u8
+    };
+    rating_points + experience_points + delivery_points
+}
+
+/// Default for `Config::max_proposals_per_job`
+pub const DEFAULT_MAX_PROPOSALS_PER_JOB: u64 = 100;
+
+/// Validate a configured proposal cap against the hard ceiling
+pub fn validate_max_proposals_per_job(max: u64) -> Result<(), ContractError> {
+    if max == 0 || max > MAX_PROPOSALS_PER_JOB {
+        return Err(ContractError::InvalidInput {
+            error: format!(
+                "Max proposals per job must be between 1-{}",
+                MAX_PROPOSALS_PER_JOB
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Default for `Config::job_archive_retention_days`
+pub const DEFAULT_JOB_ARCHIVE_RETENTION_DAYS: u64 = 90;
+
+/// Default for `Config::edit_grace_period_days`
+pub const DEFAULT_EDIT_GRACE_PERIOD_DAYS: u64 = 2;
+
+/// Default for `Config::min_edit_interval_seconds`
+pub const DEFAULT_MIN_EDIT_INTERVAL_SECONDS: u64 = 60;
+
+/// Default for `Config::reputation_half_life_days`
+pub const DEFAULT_REPUTATION_HALF_LIFE_DAYS: u64 = 180;
+
+/// Default for `Config::max_open_jobs_per_user`
+pub const DEFAULT_MAX_OPEN_JOBS_PER_USER: u64 = 20;
+
+/// Default for `Config::proposal_validity_days`
+pub const DEFAULT_PROPOSAL_VALIDITY_DAYS: u64 = 30;
+
+/// Proposals must stay acceptable for at least a day
+pub fn validate_proposal_validity_days(days: u64) -> Result<(), ContractError> {
+    if days == 0 {
+        return Err(ContractError::InvalidInput {
+            error: "Proposal validity must be at least 1 day".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// A proposal stays acceptable for the configured window, or its own delivery time if longer
+pub fn proposal_expires_at(
+    config: &Config,
+    submitted_at: Timestamp,
+    delivery_time_days: u64,
+) -> Result<Timestamp, ContractError> {
+    get_future_timestamp(
+        submitted_at,
+        config.proposal_validity_days.max(delivery_time_days),
+    )
+}
+
+/// Upper bound on jobs moved per `ArchiveJobs` call
+pub const MAX_ARCHIVE_BATCH: usize = 50;
+
+/// Budget tier for `JOBS_BY_BUDGET_RANGE`: 1 is under 500, 2 is under 5000, 3 is 5000 and up
+pub fn budget_range(budget: Uint128) -> u8 {
+    if budget < Uint128::new(500) {
+        1
+    } else if budget < Uint128::new(5000) {
+        2
+    } else {
+        3
+    }
+}
+
+/// Change a job's budget, moving it to the matching tier in `JOBS_BY_BUDGET_RANGE`
+pub fn set_job_budget(
+    storage: &mut dyn Storage,
+    job: &mut Job,
+    new_budget: Uint128,
+) -> StdResult<()> {
+    let new_range = budget_range(new_budget);
+    if new_range != job.budget_range {
+        JOBS_BY_BUDGET_RANGE.remove(storage, (job.budget_range, job.id));
+        JOBS_BY_BUDGET_RANGE.save(storage, (new_range, job.id), &true)?;
+        job.budget_range = new_range;
+    }
+    job.budget = new_budget;
+    Ok(())
+}
 
 /// Helper function to calculate platform fee
 pub fn calculate_platform_fee(amount: Uint128, fee_percent: u64) -> Uint128 {
     amount * Uint128::from(fee_percent) / Uint128::from(100u64)
 }
 
+/// Basis point denominator for `Config::fee_split_bps`
+pub const FEE_SPLIT_BPS_DENOMINATOR: u16 = 10_000;
+
+/// How a job budget is funded and paid out once the platform fee is split
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EscrowTerms {
+    /// Budget plus the poster's share of the fee, deposited when funding
+    pub deposit: Uint128,
+    /// Budget minus the freelancer's share of the fee, paid on release
+    pub payout: Uint128,
+    pub platform_fee: Uint128,
+}
+
+/// Split the platform fee on `budget` between poster and freelancer per `fee_split_bps`
+pub fn escrow_terms(
+    budget: Uint128,
+    fee_percent: u64,
+    fee_split_bps: u16,
+) -> Result<EscrowTerms, ContractError> {
+    let platform_fee = calculate_platform_fee(budget, fee_percent);
+    let poster_share = platform_fee.multiply_ratio(fee_split_bps, FEE_SPLIT_BPS_DENOMINATOR);
+    let freelancer_share = platform_fee.checked_sub(poster_share)?;
+    Ok(EscrowTerms {
+        deposit: budget.checked_add(poster_share)?,
+        payout: budget.checked_sub(freelancer_share)?,
+        platform_fee,
+    })
+}
+
+/// Inverse of `escrow_terms`: the budget whose deposit is exactly `deposit`.
+/// Fee rounding skips some deposits, and those are rejected with the nearest ones that work.
+pub fn budget_for_deposit(
+    deposit: Uint128,
+    fee_percent: u64,
+    fee_split_bps: u16,
+) -> Result<Uint128, ContractError> {
+    // The poster's share never exceeds its unrounded rate, so this starts at or below the answer
+    let scale = 100u128 * u128::from(FEE_SPLIT_BPS_DENOMINATOR);
+    let mut budget = deposit.multiply_ratio(
+        scale,
+        scale + u128::from(fee_percent) * u128::from(fee_split_bps),
+    );
+    let mut below = Uint128::zero();
+    loop {
+        let terms = escrow_terms(budget, fee_percent, fee_split_bps)?;
+        match terms.deposit.cmp(&deposit) {
+            std::cmp::Ordering::Less => {
+                below = terms.deposit;
+                budget = budget.checked_add(Uint128::one())?;
+            }
+            std::cmp::Ordering::Equal => return Ok(budget),
+            std::cmp::Ordering::Greater => {
+                return Err(ContractError::InvalidInput {
+                    error: format!(
+                        "{} does not fund a whole budget plus the poster's fee share; attach {} or {}",
+                        deposit, below, terms.deposit
+                    ),
+                })
+            }
+        }
+    }
+}
+
+/// Validate a configured fee split
+pub fn validate_fee_split_bps(fee_split_bps: u16) -> Result<(), ContractError> {
+    if fee_split_bps > FEE_SPLIT_BPS_DENOMINATOR {
+        return Err(ContractError::InvalidInput {
+            error: format!(
+                "Fee split must be between 0-{} basis points",
+                FEE_SPLIT_BPS_DENOMINATOR
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Upper bound on a referrer's share of the platform fee, in basis points
+pub const MAX_REFERRAL_BPS: u16 = 5_000;
+
+/// Validate a proposal's referral; a referrer is required for a non-zero share and may not be the submitter
+pub fn validate_referral(
+    deps: Deps,
+    submitter: &Addr,
+    referrer: Option<String>,
+    referral_bps: Option<u16>,
+) -> Result<(Option<Addr>, u16), ContractError> {
+    let referral_bps = referral_bps.unwrap_or_default();
+    if referral_bps > MAX_REFERRAL_BPS {
+        return Err(ContractError::InvalidInput {
+            error: format!("Referral share must be between 0-{} basis points", MAX_REFERRAL_BPS),
+        });
+    }
+    let referrer = referrer.map(|addr| deps.api.addr_validate(&addr)).transpose()?;
+    match &referrer {
+        None if referral_bps > 0 => Err(ContractError::InvalidInput {
+            error: "Referral share requires a referrer".to_string(),
+        }),
+        Some(addr) if addr == submitter => Err(ContractError::InvalidInput {
+            error: "Cannot refer your own proposal".to_string(),
+        }),
+        _ => Ok((referrer, referral_bps)),
+    }
+}
+
 /// 🎯 Create a new job posting with hybrid on-chain/off-chain storage
 #[allow(clippy::too_many_arguments)]
 pub fn execute_post_job(
@@ -38,46 +268,358 @@ pub fn execute_post_job(
     company: Option<String>,
     location: Option<String>,
     documents: Option<Vec<String>>,
-    _milestones: Option<Vec<MilestoneInput>>,
-    _experience_level: u8,
-    _is_remote: bool,
-    _urgency_level: u8,
+    milestones: Option<Vec<MilestoneInput>>,
+    experience_level: u8,
+    is_remote: bool,
+    urgency_level: u8,
     off_chain_storage_key: String,
+    release_policy: Option<ReleasePolicy>,
+    denom: Option<Denom>,
 ) -> Result<Response, ContractError> {
     // 🔒 Apply security checks
     apply_security_checks!(deps, env, info, RateLimitAction::PostJob);
 
     // 📋 Load configuration and validate
     let config = CONFIG.load(deps.storage)?;
-    validate_content_inputs!(&title, &description);
-    validate_budget(budget)?;
-    validate_duration(duration_days, config.max_job_duration_days)?;
-
-    // 💰 Validate payment
-    if budget.is_zero() {
-        if !info.funds.is_empty() {
-            return Err(ContractError::InvalidFunds {});
+    let item = PostJobItem {
+        title,
+        description,
+        company,
+        location,
+        category,
+        skills_required,
+        documents,
+        milestones,
+        budget,
+        duration_days,
+        experience_level,
+        is_remote,
+        urgency_level,
+        off_chain_storage_key,
+        release_policy,
+        denom,
+    };
+    let terms = validate_job_item(&config, &item)?;
+    let denom = resolve_job_denom(deps.api, &config, item.denom.clone())?;
+
+    // 💰 Validate payment; paid jobs posted without funds are listed unfunded
+    // and must be funded through CreateEscrowNative, or the token's Send hook
+    // for CW20 jobs, before accepting a proposal
+    if !info.funds.is_empty() {
+        match &denom {
+            Denom::Native(native) if !budget.is_zero() => {
+                validate_exact_funds(&info, &config.allowed_denoms, native, terms.deposit)?;
+            }
+            _ => {
+                return Err(ContractError::InvalidFunds {
+                    expected: "no funds".to_string(),
+                    received: describe_funds(&info.funds),
+                });
+            }
         }
-    } else {
-        if info.funds.len() != 1 || info.funds[0].amount != budget {
-            return Err(ContractError::InvalidFunds {});
+    }
+    let fund_now = budget.is_zero() || !info.funds.is_empty();
+
+    let off_chain_storage_key = item.off_chain_storage_key.clone();
+    let (job, content_hash_str) = store_job(deps, &env, &info.sender, item, &terms, denom, fund_now)?;
+
+    // 🎉 Return success response with essential info
+    let mut response = build_success_response!(
+        "post_job",
+        job.id,
+        &info.sender,
+        "budget" => budget.to_string(),
+        "content_hash" => content_hash_str,
+        "off_chain_key" => off_chain_storage_key
+    );
+    if let Some(escrow_id) = job.escrow_id {
+        response = response.add_attribute("escrow_id", escrow_id);
+    }
+
+    Ok(response)
+}
+
+/// 📦 Post several jobs at once; the batch counts as one job post against the rate limit
+pub fn execute_post_jobs_batch(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    jobs: Vec<PostJobItem>,
+) -> Result<Response, ContractError> {
+    // 🔒 Apply security checks
+    apply_security_checks!(deps, env, info, RateLimitAction::PostJob);
+
+    if jobs.is_empty() || jobs.len() > MAX_JOBS_PER_BATCH {
+        return Err(ContractError::InvalidInput {
+            error: format!("Batch must contain between 1 and {} jobs", MAX_JOBS_PER_BATCH),
+        });
+    }
+
+    // 📋 Validate every listing before storing any of them
+    let config = CONFIG.load(deps.storage)?;
+    let terms = jobs
+        .iter()
+        .map(|item| validate_job_item(&config, item))
+        .collect::<Result<Vec<_>, _>>()?;
+    let denoms = jobs
+        .iter()
+        .map(|item| resolve_job_denom(deps.api, &config, item.denom.clone()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // 💰 Funds attached to a batch must cover every paid job, all posted in
+    // the attached coin's denom; a batch sent without funds lists its paid jobs unfunded
+    let fund_paid_jobs = !info.funds.is_empty();
+    if fund_paid_jobs {
+        let funded = Denom::Native(info.funds[0].denom.clone());
+        let mismatched = jobs
+            .iter()
+            .zip(&denoms)
+            .find(|(item, denom)| !item.budget.is_zero() && **denom != funded);
+        if let Some((_, denom)) = mismatched {
+            return Err(ContractError::DenomMismatch {
+                expected: escrow_denom(denom),
+                got: escrow_denom(&funded),
+            });
         }
+        let total_deposit = terms
+            .iter()
+            .try_fold(Uint128::zero(), |total, t| total.checked_add(t.deposit))?;
+        validate_exact_funds(&info, &config.allowed_denoms, &escrow_denom(&funded), total_deposit)?;
     }
 
+    let mut job_ids = Vec::with_capacity(jobs.len());
+    for ((item, terms), denom) in jobs.into_iter().zip(terms).zip(denoms) {
+        let fund_now = item.budget.is_zero() || fund_paid_jobs;
+        let (job, _) =
+            store_job(deps.branch(), &env, &info.sender, item, &terms, denom, fund_now)?;
+        job_ids.push(job.id.to_string());
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "post_jobs_batch")
+        .add_attribute("poster", info.sender.to_string())
+        .add_attribute("count", job_ids.len().to_string())
+        .add_attribute("job_ids", job_ids.join(",")))
+}
+
+/// Validate a single listing and return the escrow terms for its budget
+fn validate_job_item(config: &Config, item: &PostJobItem) -> Result<EscrowTerms, ContractError> {
+    validate_content_inputs!(&item.title, &item.description);
+    validate_skills(&item.skills_required)?;
+    validate_labels(&item.category, &item.skills_required)?;
+    if let Some(docs) = &item.documents {
+        validate_uri_list(docs, "Documents", MAX_DOCUMENTS, MAX_URI_LENGTH)?;
+    }
+    // Paid jobs are escrowed eventually, so hold them to the escrow minimum
+    if !item.budget.is_zero() && item.budget < config.min_escrow_amount {
+        return Err(ContractError::EscrowBelowMinimum {
+            min: config.min_escrow_amount,
+        });
+    }
+    validate_budget(item.budget)?;
+    validate_duration(item.duration_days, config.max_job_duration_days)?;
+
+    escrow_terms(item.budget, config.platform_fee_percent, config.fee_split_bps)
+}
+
+/// 📝 Save a reusable listing, replacing the sender's template of the same name
+#[allow(clippy::too_many_arguments)]
+pub fn execute_save_job_template(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    title: String,
+    description: String,
+    category: String,
+    skills_required: Vec<String>,
+    budget: Uint128,
+    duration_days: u64,
+    experience_level: u8,
+    is_remote: bool,
+    urgency_level: u8,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref())?;
+    reentrancy_guard(deps.branch())?;
+
+    // Templates hold no funds
+    if !info.funds.is_empty() {
+        return Err(ContractError::InvalidFunds {
+            expected: "no funds".to_string(),
+            received: describe_funds(&info.funds),
+        });
+    }
+    validate_string_field(&name, "Template name", 1, 64)?;
+    validate_text_chars(&name, "Template name", false)?;
+
+    // Templates are checked like a listing so posting from one only fails on overrides
+    let config = CONFIG.load(deps.storage)?;
+    let item = PostJobItem {
+        title,
+        description,
+        company: None,
+        location: None,
+        category,
+        skills_required,
+        documents: None,
+        milestones: None,
+        budget,
+        duration_days,
+        experience_level,
+        is_remote,
+        urgency_level,
+        off_chain_storage_key: String::new(),
+        release_policy: None,
+        denom: None,
+    };
+    validate_job_item(&config, &item)?;
+
+    let key = (&info.sender, name.as_str());
+    if !JOB_TEMPLATES.has(deps.storage, key)
+        && JOB_TEMPLATES
+            .prefix(&info.sender)
+            .keys_raw(deps.storage, None, None, Order::Ascending)
+            .count()
+            >= MAX_JOB_TEMPLATES_PER_POSTER
+    {
+        return Err(ContractError::InvalidInput {
+            error: format!(
+                "Posters can keep at most {} job templates",
+                MAX_JOB_TEMPLATES_PER_POSTER
+            ),
+        });
+    }
+
+    let template = JobTemplate {
+        name: name.clone(),
+        title: item.title,
+        description: item.description,
+        category: item.category,
+        skills_required: item.skills_required,
+        budget: item.budget,
+        duration_days: item.duration_days,
+        experience_level: item.experience_level,
+        is_remote: item.is_remote,
+        urgency_level: item.urgency_level,
+        updated_at: env.block.time,
+    };
+    JOB_TEMPLATES.save(deps.storage, key, &template)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "save_job_template")
+        .add_attribute("poster", info.sender.to_string())
+        .add_attribute("name", name))
+}
+
+/// 🗑️ Delete one of the sender's job templates
+pub fn execute_delete_job_template(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    name: String,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref())?;
+    reentrancy_guard(deps.branch())?;
+
+    let key = (&info.sender, name.as_str());
+    if !JOB_TEMPLATES.has(deps.storage, key) {
+        return Err(ContractError::JobTemplateNotFound { name });
+    }
+    JOB_TEMPLATES.remove(deps.storage, key);
+
+    Ok(Response::new()
+        .add_attribute("method", "delete_job_template")
+        .add_attribute("poster", info.sender.to_string())
+        .add_attribute("name", name))
+}
+
+/// 🎯 Post a job from one of the sender's templates, applying any overrides
+pub fn execute_post_job_from_template(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    overrides: JobTemplateOverrides,
+    off_chain_storage_key: String,
+) -> Result<Response, ContractError> {
+    let template = JOB_TEMPLATES
+        .may_load(deps.storage, (&info.sender, name.as_str()))?
+        .ok_or_else(|| ContractError::JobTemplateNotFound { name: name.clone() })?;
+
+    let response = execute_post_job(
+        deps,
+        env,
+        info,
+        overrides.title.unwrap_or(template.title),
+        overrides.description.unwrap_or(template.description),
+        overrides.budget.unwrap_or(template.budget),
+        overrides.category.unwrap_or(template.category),
+        overrides.skills_required.unwrap_or(template.skills_required),
+        overrides.duration_days.unwrap_or(template.duration_days),
+        overrides.company,
+        overrides.location,
+        overrides.documents,
+        overrides.milestones,
+        overrides.experience_level.unwrap_or(template.experience_level),
+        overrides.is_remote.unwrap_or(template.is_remote),
+        overrides.urgency_level.unwrap_or(template.urgency_level),
+        off_chain_storage_key,
+        overrides.release_policy,
+        overrides.denom,
+    )?;
+
+    Ok(response.add_attribute("template", name))
+}
+
+/// Query a poster's job templates
+pub fn query_job_templates(deps: Deps, poster: String) -> StdResult<JobTemplatesResponse> {
+    let poster = deps.api.addr_validate(&poster)?;
+    let templates = JOB_TEMPLATES
+        .prefix(&poster)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, template)| template))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(JobTemplatesResponse { templates })
+}
+
+/// Reject a new listing once the poster has `Config::max_open_jobs_per_user` jobs still Open
+fn ensure_open_job_slot(deps: Deps, poster: &Addr) -> Result<(), ContractError> {
+    let max = CONFIG.load(deps.storage)?.max_open_jobs_per_user;
+    if max == 0 {
+        return Ok(());
+    }
+    let open_jobs = OPEN_JOB_COUNT.may_load(deps.storage, poster)?.unwrap_or_default();
+    if open_jobs >= max {
+        return Err(ContractError::TooManyOpenJobs { max });
+    }
+    Ok(())
+}
+
+/// Store a validated listing with its content hash and, when funded, its escrow
+fn store_job(
+    deps: DepsMut,
+    env: &Env,
+    poster: &Addr,
+    item: PostJobItem,
+    terms: &EscrowTerms,
+    denom: Denom,
+    fund_now: bool,
+) -> Result<(Job, String), ContractError> {
+    ensure_open_job_slot(deps.as_ref(), poster)?;
+
     // 🆔 Generate job ID
     let job_id = NEXT_JOB_ID.load(deps.storage)?;
     NEXT_JOB_ID.save(deps.storage, &(job_id + 1))?;
 
     // 🌐 Create off-chain content bundle
-    let documents_vec = documents.unwrap_or_default();
+    let documents_vec = item.documents.unwrap_or_default();
     let (off_chain_bundle, content_hash_str) = create_job_content_bundle(
         job_id,
-        &title,
-        &description,
-        company.as_deref(),
-        location.as_deref(),
-        &category,
-        &skills_required,
+        &item.title,
+        &item.description,
+        item.company.as_deref(),
+        item.location.as_deref(),
+        &item.category,
+        &item.skills_required,
         &documents_vec,
         env.block.time.seconds(),
     )?;
@@ -100,49 +642,69 @@ pub fn execute_post_job(
     // 🎯 Create optimized on-chain job record
     let job = Job {
         id: job_id,
-        poster: info.sender.clone(),
-        budget,
-        duration_days,
+        poster: poster.clone(),
+        budget: item.budget,
+        duration_days: item.duration_days,
         status: JobStatus::Open,
         assigned_freelancer: None,
         created_at: env.block.time,
         updated_at: env.block.time,
-        deadline: get_future_timestamp(env.block.time, duration_days),
-        escrow_id: Some(format!("job_{}", job_id)),
+        deadline: get_future_timestamp(env.block.time, item.duration_days)?,
+        escrow_id: fund_now.then(|| format!("job_{}", job_id)),
         total_proposals: 0,
+        category_id: category_id(&item.category),
+        skill_tags: skill_tags(&item.skills_required),
+        previous_freelancers: Vec::new(),
+        work_submitted_at: None,
+        assigned_at: None,
+        edits_objected: false,
+        payout_address: None,
+        referrer: None,
+        referral_bps: 0,
+        budget_range: budget_range(item.budget),
+        denom,
+        release_policy: item.release_policy.unwrap_or_default(),
         content_hash,
     };
 
     JOBS.save(deps.storage, job_id, &job)?;
+    index_job_skills(deps.storage, job_id, &job.skill_tags)?;
+    JOBS_BY_BUDGET_RANGE.save(deps.storage, (job.budget_range, job_id), &true)?;
+    sync_active_job(deps.storage, &job)?;
+
+    let mut user_jobs = USER_JOBS.may_load(deps.storage, poster)?.unwrap_or_default();
+    user_jobs.push(job_id);
+    USER_JOBS.save(deps.storage, poster, &user_jobs)?;
+
+    //  Create escrow when the job was funded with the post
+    if let Some(escrow_id) = &job.escrow_id {
+        let escrow = crate::state::EscrowState {
+            id: escrow_id.clone(),
+            job_id,
+            client: poster.clone(),
+            freelancer: Addr::unchecked(""), // Will be set when job is assigned
+            amount: terms.payout,
+            platform_fee: terms.platform_fee,
+            funded_at: env.block.time,
+            released: false,
+            dispute_status: crate::state::DisputeStatus::None,
+            dispute_raised_at: None,
+            dispute_deadline: None,
+            denom: job.denom.clone(),
+        };
+
+        ESCROWS.save(deps.storage, escrow_id, &escrow)?;
+        record_escrow_event(
+            deps.storage,
+            escrow_id,
+            EscrowEventKind::Funded,
+            poster,
+            terms.deposit,
+            env.block.time,
+        )?;
+    }
 
-    //  Create escrow
-    let escrow_id = format!("job_{}", job_id);
-    let escrow = crate::state::EscrowState {
-        id: escrow_id.clone(),
-        job_id,
-        client: info.sender.clone(),
-        freelancer: Addr::unchecked(""), // Will be set when job is assigned
-        amount: budget,
-        platform_fee: calculate_platform_fee(budget, config.platform_fee_percent),
-        funded_at: env.block.time,
-        released: false,
-        dispute_status: crate::state::DisputeStatus::None,
-        dispute_raised_at: None,
-        dispute_deadline: None,
-    };
-
-    ESCROWS.save(deps.storage, &escrow_id, &escrow)?;
-
-    // 🎉 Return success response with essential info
-    Ok(build_success_response!(
-        "post_job",
-        job_id,
-        &info.sender,
-        "budget" => budget.to_string(),
-        "content_hash" => content_hash_str,
-        "off_chain_key" => off_chain_storage_key,
-        "escrow_id" => escrow_id
-    ))
+    Ok((job, content_hash_str))
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -160,6 +722,9 @@ pub fn execute_submit_proposal(
     agreed_to_escrow: bool,
     milestones: Option<Vec<crate::state::ProposalMilestone>>,
     _portfolio_samples: Option<Vec<String>>,
+    payout_address: Option<String>,
+    referrer: Option<String>,
+    referral_bps: Option<u16>,
     _off_chain_storage_key: String,
 ) -> Result<Response, ContractError> {
     // 🔒 Apply security checks
@@ -172,10 +737,14 @@ pub fn execute_submit_proposal(
     validate_job_status_for_operation(&job.status, &[JobStatus::Open], "submit proposal to")?;
 
     // 🔍 Validate inputs
-    validate_content_inputs!(&cover_letter, &cover_letter);
+    validate_string_field(&cover_letter, "Cover letter", 1, 200)?;
 
     let config = CONFIG.load(deps.storage)?;
     validate_duration(delivery_time_days, config.max_job_duration_days)?;
+    let payout_address = payout_address
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let (referrer, referral_bps) = validate_referral(deps.as_ref(), &info.sender, referrer, referral_bps)?;
 
     // ❌ Check if user already has a proposal for this job
     let existing_proposals: Vec<_> = PROPOSALS
@@ -241,18 +810,42 @@ pub fn execute_submit_proposal(
         agreed_to_terms,
         agreed_to_escrow,
         submitted_at: env.block.time,
+        status: ProposalStatus::Submitted,
+        score: proposal_score(
+            &USER_STATS
+                .may_load(deps.storage, &info.sender)?
+                .unwrap_or_default(),
+            delivery_time_days,
+            job.duration_days,
+        ),
+        payout_address,
+        referrer,
+        referral_bps,
+        expires_at: proposal_expires_at(&config, env.block.time, delivery_time_days)?,
         content_hash,
     };
 
-    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
-
-    // Update job proposals mapping
+    // Update job proposals mapping; capped so accepting a proposal can reject the rest in one pass
     let mut job_proposals = JOB_PROPOSALS
         .may_load(deps.storage, job_id)?
         .unwrap_or_default();
+    if job_proposals.len() as u64 >= config.max_proposals_per_job {
+        return Err(ContractError::MaxProposalsReached {
+            max: config.max_proposals_per_job,
+        });
+    }
+
+    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+
     job_proposals.push(proposal_id);
     JOB_PROPOSALS.save(deps.storage, job_id, &job_proposals)?;
 
+    let mut user_proposals = USER_PROPOSALS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    user_proposals.push(proposal_id);
+    USER_PROPOSALS.save(deps.storage, &info.sender, &user_proposals)?;
+
     // Update job proposal count
     job.total_proposals += 1;
     JOBS.save(deps.storage, job_id, &job)?;
@@ -291,19 +884,60 @@ pub fn execute_edit_job(
         .load(deps.storage, job_id)
         .map_err(|_| ContractError::JobNotFound {})?;
     validate_user_authorization(&job.poster, &info.sender)?;
-    validate_job_status_for_operation(&job.status, &[JobStatus::Open], "edit")?;
+    validate_job_status_for_operation(&job.status, &[JobStatus::Open, JobStatus::InProgress], "edit")?;
 
     let config = CONFIG.load(deps.storage)?;
 
+    // Accepted jobs only take content clarifications, and only early on
+    if job.status == JobStatus::InProgress {
+        if budget.is_some() || duration_days.is_some() || milestones.is_some() {
+            return Err(ContractError::InvalidInput {
+                error: "Only content can be edited once a job is in progress".to_string(),
+            });
+        }
+        if job.edits_objected {
+            return Err(ContractError::InvalidInput {
+                error: "Assigned freelancer has objected to further edits".to_string(),
+            });
+        }
+        let assigned_at = job.assigned_at.unwrap_or(job.updated_at);
+        let grace_deadline = get_future_timestamp(assigned_at, config.edit_grace_period_days)?;
+        if env.block.time >= grace_deadline {
+            return Err(ContractError::InvalidInput {
+                error: "Edit grace period for this job has ended".to_string(),
+            });
+        }
+    }
+
     // 🔄 Track what changed for off-chain updates
     let mut content_changed = false;
     let mut metadata_changed = false;
+    let mut new_content_hash = None;
+
+    if let Some(docs) = &documents {
+        validate_uri_list(docs, "Documents", MAX_DOCUMENTS, MAX_URI_LENGTH)?;
+    }
+    if let Some(skills) = &skills_required {
+        validate_skills(skills)?;
+    }
+    validate_labels(
+        category.as_deref().unwrap_or_default(),
+        skills_required.as_deref().unwrap_or_default(),
+    )?;
+
+    // Throttle repeated edits so content hashes can't be churned
+    throttle_edit(
+        deps.storage,
+        &format!("job_{}", job_id),
+        config.min_edit_interval_seconds,
+        env.block.time,
+    )?;
 
     // ⚡ Update on-chain metadata fields if they changed
     if let Some(new_budget) = budget {
         validate_budget(new_budget)?;
         if job.budget != new_budget {
-            job.budget = new_budget;
+            set_job_budget(deps.storage, &mut job, new_budget)?;
             metadata_changed = true;
         }
     }
@@ -312,12 +946,23 @@ pub fn execute_edit_job(
         validate_duration(new_duration, config.max_job_duration_days)?;
         if job.duration_days != new_duration {
             job.duration_days = new_duration;
-            job.deadline = get_future_timestamp(env.block.time, new_duration);
+            job.deadline = get_future_timestamp(env.block.time, new_duration)?;
             metadata_changed = true;
         }
     }
 
     // 🏷️ Update category and skills if changed
+    if let Some(new_category) = &category {
+        job.category_id = category_id(new_category);
+        metadata_changed = true;
+    }
+    if let Some(new_skills) = &skills_required {
+        unindex_job_skills(deps.storage, job_id, &job.skill_tags);
+        job.skill_tags = skill_tags(new_skills);
+        index_job_skills(deps.storage, job_id, &job.skill_tags)?;
+        metadata_changed = true;
+    }
+
     // 🌐 If content fields changed, create new off-chain bundle
     if title.is_some()
         || description.is_some()
@@ -381,6 +1026,7 @@ pub fn execute_edit_job(
 
         // Update job content hash
         job.content_hash = content_hash;
+        new_content_hash = Some(content_hash_str);
     }
 
     // 🕒 Update timestamp and save
@@ -389,14 +1035,20 @@ pub fn execute_edit_job(
         JOBS.save(deps.storage, job_id, &job)?;
     }
 
-    Ok(build_success_response!(
+    let mut response = build_success_response!(
         "edit_job",
         job_id,
         &info.sender,
         "content_changed" => content_changed.to_string(),
         "metadata_changed" => metadata_changed.to_string(),
         "off_chain_key" => off_chain_storage_key
-    ))
+    );
+    // Let indexers follow the new off-chain bundle without re-querying
+    if let Some(hash) = new_content_hash {
+        response = response.add_attribute("content_hash", hash);
+    }
+
+    Ok(response)
 }
 
 /// Delete a job
@@ -408,6 +1060,7 @@ pub fn execute_delete_job(
 ) -> Result<Response, ContractError> {
     // Apply security checks
     apply_security_checks!(deps, env, info, RateLimitAction::DeleteJob);
+    ensure_escrows_not_frozen(deps.as_ref())?;
 
     // Load and validate job
     let job = JOBS
@@ -425,252 +1078,162 @@ pub fn execute_delete_job(
 
     // Remove job
     JOBS.remove(deps.storage, job_id);
-
-    // Release escrow
-    let escrow_id = format!("job_{}", job_id);
-    if let Ok(mut escrow) = ESCROWS.load(deps.storage, &escrow_id) {
-        // Note: The EscrowState struct doesn't have a status field, so we only update released
-        escrow.released = true;
-        ESCROWS.save(deps.storage, &escrow_id, &escrow)?;
-    }
+    record_removal(deps.storage, &REMOVED_JOB_COUNT)?;
+    remove_entity_hashes(deps.storage, &format!("job_{}", job_id))?;
+    unindex_job_skills(deps.storage, job_id, &job.skill_tags);
+    JOBS_BY_BUDGET_RANGE.remove(deps.storage, (job.budget_range, job_id));
+    clear_active_job(deps.storage, &job)?;
+    let mut user_jobs = USER_JOBS
+        .may_load(deps.storage, &job.poster)?
+        .unwrap_or_default();
+    user_jobs.retain(|&id| id != job_id);
+    USER_JOBS.save(deps.storage, &job.poster, &user_jobs)?;
 
     let mut response = build_success_response!("delete_job", job_id, &info.sender);
 
-    // Add bank message to return funds
-    response = response.add_message(BankMsg::Send {
-        to_address: info.sender.to_string(),
-        amount: coins(job.budget.u128(), "uusdc"),
-    });
+    // Refund the whole deposit in its own denom; unfunded listings have nothing to return
+    if let Some(escrow_id) = &job.escrow_id {
+        let mut escrow = ESCROWS.load(deps.storage, escrow_id)?;
+        if !escrow.released {
+            let refund = escrow.amount.checked_add(escrow.platform_fee)?;
+            escrow.released = true;
+            ESCROWS.save(deps.storage, escrow_id, &escrow)?;
+            record_escrow_event(
+                deps.storage,
+                escrow_id,
+                EscrowEventKind::Refunded,
+                &info.sender,
+                refund,
+                env.block.time,
+            )?;
+            response = response
+                .add_message(payout_msg(&escrow.denom, &escrow.client, refund)?)
+                .add_attribute("refund", refund.to_string());
+        }
+    }
 
     Ok(response)
 }
 
-/// Cancel a job
-pub fn execute_cancel_job(
-    mut deps: DepsMut,
+/// Let the assigned freelancer close the poster's edit grace period early
+pub fn execute_object_to_job_edits(
+    deps: DepsMut,
     env: Env,
     info: MessageInfo,
     job_id: u64,
 ) -> Result<Response, ContractError> {
-    // Apply security checks
-    apply_security_checks!(deps, env, info, RateLimitAction::CancelJob);
+    ensure_not_paused(deps.as_ref())?;
 
-    // Load and validate job
     let mut job = JOBS
         .load(deps.storage, job_id)
         .map_err(|_| ContractError::JobNotFound {})?;
-    validate_user_authorization(&job.poster, &info.sender)?;
-    validate_job_status_for_operation(
-        &job.status,
-        &[JobStatus::Open, JobStatus::InProgress],
-        "cancel",
-    )?;
+    if job.assigned_freelancer.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    validate_job_status_for_operation(&job.status, &[JobStatus::InProgress], "object to edits on")?;
 
-    // Update job status
-    job.status = JobStatus::Cancelled;
+    job.edits_objected = true;
     job.updated_at = env.block.time;
     JOBS.save(deps.storage, job_id, &job)?;
 
-    Ok(build_success_response!("cancel_job", job_id, &info.sender))
+    Ok(build_success_response!("object_to_job_edits", job_id, &info.sender))
 }
 
-/// Accept a proposal
-pub fn execute_accept_proposal(
-    mut deps: DepsMut,
+/// Move closed jobs past the retention window out of `JOBS` into `ARCHIVED_JOBS`
+pub fn execute_archive_jobs(
+    deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    proposal_id: u64,
+    job_ids: Vec<u64>,
 ) -> Result<Response, ContractError> {
-    // Apply security checks
-    apply_security_checks!(deps, env, info, RateLimitAction::AcceptProposal);
+    ensure_role!(deps, info, AdminRole::Moderator);
 
-    // Load and validate proposal
-    let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
-    let mut job = JOBS.load(deps.storage, proposal.job_id)?;
+    if job_ids.is_empty() || job_ids.len() > MAX_ARCHIVE_BATCH {
+        return Err(ContractError::InvalidInput {
+            error: format!("Can archive between 1-{} jobs at a time", MAX_ARCHIVE_BATCH),
+        });
+    }
 
-    validate_user_authorization(&job.poster, &info.sender)?;
-    validate_job_status_for_operation(&job.status, &[JobStatus::Open], "accept proposal for")?;
+    let config = CONFIG.load(deps.storage)?;
+    let retention_secs = config
+        .job_archive_retention_days
+        .saturating_mul(24 * 60 * 60);
+
+    let mut archived_completed = 0u64;
+    for job_id in &job_ids {
+        let job = JOBS
+            .load(deps.storage, *job_id)
+            .map_err(|_| ContractError::JobNotFound {})?;
+        validate_job_status_for_operation(
+            &job.status,
+            &[JobStatus::Completed, JobStatus::Cancelled],
+            "archive",
+        )?;
 
-    // Note: Proposal struct doesn't have status/updated_at fields, so we skip updating those
-    // We only update the job to reflect that it's assigned
+        // Closed jobs are no longer edited, so updated_at is when they closed
+        if env.block.time.seconds() < job.updated_at.seconds().saturating_add(retention_secs) {
+            return Err(ContractError::InvalidInput {
+                error: format!("Job {} is still within the retention window", job_id),
+            });
+        }
 
-    // Update job status
-    job.status = JobStatus::InProgress;
-    job.assigned_freelancer = Some(proposal.freelancer.clone());
-    job.updated_at = env.block.time;
-    JOBS.save(deps.storage, proposal.job_id, &job)?;
+        if job.status == JobStatus::Completed {
+            archived_completed += 1;
+        }
+        JOBS.remove(deps.storage, *job_id);
+        unindex_job_skills(deps.storage, *job_id, &job.skill_tags);
+        JOBS_BY_BUDGET_RANGE.remove(deps.storage, (job.budget_range, *job_id));
+        ACTIVE_JOBS.remove(deps.storage, *job_id);
+        ARCHIVED_JOBS.save(deps.storage, *job_id, &job)?;
+    }
 
-    // Note: We skip rejecting other proposals since Proposal struct doesn't have status/updated_at fields
-    // In a real implementation, we might want to store proposal status separately or modify the struct
+    let archived = job_ids.len() as u64;
+    let archived_total = ARCHIVED_JOB_COUNT.may_load(deps.storage)?.unwrap_or_default();
+    ARCHIVED_JOB_COUNT.save(deps.storage, &(archived_total + archived))?;
+    let completed_total = ARCHIVED_COMPLETED_JOB_COUNT
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    ARCHIVED_COMPLETED_JOB_COUNT.save(deps.storage, &(completed_total + archived_completed))?;
 
-    Ok(build_success_response!(
-        "accept_proposal",
-        proposal_id,
-        &info.sender,
-        "job_id" => proposal.job_id.to_string(),
-        "freelancer" => proposal.freelancer.to_string()
-    ))
+    Ok(Response::new()
+        .add_attribute("method", "archive_jobs")
+        .add_attribute("archived_count", archived.to_string())
+        .add_attribute("admin", info.sender.to_string()))
 }
 
-/// Complete a job
-pub fn execute_complete_job(
+/// Cancel a job
+pub fn execute_cancel_job(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     job_id: u64,
-    _completion_notes: Option<String>,
 ) -> Result<Response, ContractError> {
     // Apply security checks
-    apply_security_checks!(deps, env, info, RateLimitAction::CompleteJob);
+    apply_security_checks!(deps, env, info, RateLimitAction::CancelJob);
 
     // Load and validate job
-    let mut job = JOBS.load(deps.storage, job_id)?;
-    validate_job_status_for_operation(&job.status, &[JobStatus::InProgress], "complete")?;
-
-    // Check if user is assigned freelancer
-    if let Some(ref assigned_freelancer) = job.assigned_freelancer {
-        validate_user_authorization(assigned_freelancer, &info.sender)?;
-    } else {
-        return Err(ContractError::InvalidInput {
-            error: "Job is not assigned to anyone".to_string(),
-        });
-    }
+    let mut job = JOBS
+        .load(deps.storage, job_id)
+        .map_err(|_| ContractError::JobNotFound {})?;
+    validate_user_authorization(&job.poster, &info.sender)?;
+    validate_job_status_for_operation(
+        &job.status,
+        &[JobStatus::Open, JobStatus::InProgress],
+        "cancel",
+    )?;
 
     // Update job status
-    job.status = JobStatus::Completed;
-    // Note: Job struct doesn't have completed_at field, so we just update updated_at
+    job.status = JobStatus::Cancelled;
     job.updated_at = env.block.time;
     JOBS.save(deps.storage, job_id, &job)?;
+    sync_active_job(deps.storage, &job)?;
 
-    // Release escrow
-    let escrow_id = format!("job_{}", job_id);
-    if let Ok(mut escrow) = ESCROWS.load(deps.storage, &escrow_id) {
-        // Note: EscrowState struct doesn't have status, recipient, or released_at fields
-        escrow.released = true;
-        ESCROWS.save(deps.storage, &escrow_id, &escrow)?;
-    }
-
-    let mut response = build_success_response!(
-        "complete_job",
+    Ok(build_success_response!(
+        "cancel_job",
         job_id,
         &info.sender,
-        "budget" => job.budget.to_string()
-    );
-
-    // Add bank message to release funds to freelancer
-    if let Some(ref freelancer) = job.assigned_freelancer {
-        response = response.add_message(BankMsg::Send {
-            to_address: freelancer.to_string(),
-            amount: coins(job.budget.u128(), "uusdc"),
-        });
-    }
-
-    Ok(response)
-}
-
-// Query functions
-
-/// Query a specific job
-pub fn query_job(deps: Deps, job_id: u64) -> StdResult<JobResponse> {
-    let job = JOBS.load(deps.storage, job_id)?;
-    Ok(JobResponse { job })
-}
-
-/// Query jobs with pagination and filtering
-pub fn query_jobs(
-    deps: Deps,
-    start_after: Option<u64>,
-    limit: Option<u32>,
-    category: Option<String>,
-    status: Option<JobStatus>,
-    poster: Option<String>,
-) -> StdResult<JobsResponse> {
-    let poster_addr = if let Some(p) = poster {
-        Some(deps.api.addr_validate(&p)?)
-    } else {
-        None
-    };
-
-    build_jobs_response(
-        deps.storage,
-        start_after,
-        limit,
-        category,
-        status,
-        poster_addr,
-    )
-}
-
-/// Query user's jobs
-pub fn query_user_jobs(
-    deps: Deps,
-    user: String,
-    status: Option<JobStatus>,
-) -> StdResult<JobsResponse> {
-    let user_addr = deps.api.addr_validate(&user)?;
-    build_jobs_response(deps.storage, None, None, None, status, Some(user_addr))
-}
-
-/// Query a specific proposal
-pub fn query_proposal(deps: Deps, proposal_id: u64) -> StdResult<ProposalResponse> {
-    let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
-    Ok(ProposalResponse { proposal })
-}
-
-/// Query proposals for a job
-pub fn query_job_proposals(deps: Deps, job_id: u64) -> StdResult<ProposalsResponse> {
-    let proposals: Vec<_> = PROPOSALS
-        .range(deps.storage, None, None, Order::Descending)
-        .filter_map(|item| {
-            if let Ok((_, proposal)) = item {
-                if proposal.job_id == job_id {
-                    Some(proposal)
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        })
-        .collect();
-
-    Ok(ProposalsResponse { proposals })
-}
-
-/// Query user's proposals
-pub fn query_user_proposals(
-    deps: Deps,
-    user: String,
-    _status: Option<ProposalStatus>,
-) -> StdResult<ProposalsResponse> {
-    let user_addr = deps.api.addr_validate(&user)?;
-
-    let proposals: Vec<_> = PROPOSALS
-        .range(deps.storage, None, None, Order::Descending)
-        .filter_map(|item| {
-            if let Ok((_, proposal)) = item {
-                if proposal.freelancer == user_addr {
-                    // Note: Proposal struct doesn't have status field, so we return all proposals
-                    // In a real implementation, we'd need to store status separately or modify the struct
-                    Some(proposal)
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        })
-        .collect();
-    Ok(ProposalsResponse { proposals })
-}
-
-/// Query job rating
-pub fn query_job_rating(deps: Deps, job_id: u64, rater: String) -> StdResult<Rating> {
-    // Create the key for this specific job-rater combination
-    let key = format!("{}_{}", job_id, rater);
-
-    // Load the specific rating
-    RATINGS.load(deps.storage, &key)
+        "status" => job.status.as_code()
+    ))
 }
 
 // Additional Proposal Management Functions
@@ -681,9 +1244,9 @@ pub fn execute_edit_proposal(
     env: Env,
     info: MessageInfo,
     proposal_id: u64,
-    _cover_letter: Option<String>,
+    cover_letter: Option<String>,
     delivery_time_days: Option<u64>,
-    _milestones: Option<Vec<ProposalMilestone>>,
+    milestones: Option<Vec<ProposalMilestone>>,
 ) -> Result<Response, ContractError> {
     // Apply security checks
     apply_security_checks!(deps, env, info, RateLimitAction::EditProposal);
@@ -696,258 +1259,81 @@ pub fn execute_edit_proposal(
         return Err(ContractError::Unauthorized {});
     }
 
-    // Note: Since Proposal doesn't have status field, we assume it's editable if it exists
-    // In a full implementation, you would add status field to Proposal struct
-
-    // In hybrid architecture, updates would recreate the content bundle
-    // For now, we'll update available fields and recreate content hash
-
     // Update delivery time if provided
     if let Some(new_delivery_time) = delivery_time_days {
         proposal.delivery_time_days = new_delivery_time;
+        let job = JOBS.load(deps.storage, proposal.job_id)?;
+        let stats = USER_STATS
+            .may_load(deps.storage, &proposal.freelancer)?
+            .unwrap_or_default();
+        proposal.score = proposal_score(&stats, new_delivery_time, job.duration_days);
     }
 
-    // For content updates (cover_letter, milestones), we would need to:
-    // 1. Fetch current off-chain content using content_hash
-    // 2. Update the relevant fields
-    // 3. Create new content bundle and hash
-    // 4. Update the content_hash field
-
-    // For now, just update timestamp to indicate proposal was modified
-    proposal.submitted_at = env.block.time;
-
-    // Save updated proposal
-    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
-
-    // Build response
-    let response = build_success_response!("edit_proposal", proposal_id, &info.sender);
-
-    Ok(response)
-}
-
-pub fn execute_withdraw_proposal(
-    mut deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    proposal_id: u64,
-) -> Result<Response, ContractError> {
-    // Apply security checks
-    apply_security_checks!(deps, env, info, RateLimitAction::WithdrawProposal);
-
-    // Load and validate proposal
-    let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
-
-    // Check authorization - only proposer can withdraw
-    if proposal.freelancer != info.sender {
-        return Err(ContractError::Unauthorized {});
-    }
-
-    // Remove proposal from storage (withdrawal)
-    PROPOSALS.remove(deps.storage, proposal_id);
-
-    // Build response
-    let response = build_success_response!("withdraw_proposal", proposal_id, &info.sender);
-
-    Ok(response)
-}
-
-// Milestone Management Functions
-
-pub fn execute_complete_milestone(
-    _deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
-    _job_id: u64,
-    _milestone_id: u64,
-) -> Result<Response, ContractError> {
-    // In hybrid architecture, milestone completion is tracked off-chain
-    // This function is deprecated but kept for API compatibility
-    Err(ContractError::InvalidInput {
-        error: "Milestone management is now handled off-chain".to_string(),
-    })
-}
-
-pub fn execute_approve_milestone(
-    _deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
-    _job_id: u64,
-    _milestone_id: u64,
-) -> Result<Response, ContractError> {
-    // In hybrid architecture, milestone approval is tracked off-chain
-    // This function is deprecated but kept for API compatibility
-    Err(ContractError::InvalidInput {
-        error: "Milestone management is now handled off-chain".to_string(),
-    })
-}
-
-// Dispute Management Functions
-
-pub fn execute_raise_dispute(
-    mut deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    job_id: u64,
-    reason: String,
-    evidence: Vec<String>,
-) -> Result<Response, ContractError> {
-    // Apply security checks
-    apply_security_checks!(deps, env, info, RateLimitAction::RaiseDispute);
-
-    // Load job
-    let mut job = JOBS.load(deps.storage, job_id)?;
-
-    // Check authorization - only job poster or assigned freelancer can raise dispute
-    let is_authorized =
-        job.poster == info.sender || job.assigned_freelancer.as_ref() == Some(&info.sender);
-
-    if !is_authorized {
-        return Err(ContractError::Unauthorized {});
-    }
-
-    // Check job status - can only dispute in progress or completed jobs
-    if !matches!(job.status, JobStatus::InProgress | JobStatus::Completed) {
-        return Err(ContractError::InvalidInput {
-            error: "Can only dispute jobs that are in progress or completed".to_string(),
-        });
-    }
-
-    // Validate inputs
-    if reason.len() < 10 || reason.len() > 1000 {
-        return Err(ContractError::InvalidInput {
-            error: "Dispute reason must be 10-1000 characters".to_string(),
-        });
-    }
-
-    // Update job status to disputed
-    job.status = JobStatus::Disputed;
-    job.updated_at = env.block.time;
-    JOBS.save(deps.storage, job_id, &job)?;
-
-    // Create dispute record
-    let dispute_id = format!("dispute_{}_{}", job_id, env.block.time.seconds());
-
-    let dispute = crate::state::Dispute {
-        id: dispute_id.clone(),
-        job_id,
-        raised_by: info.sender.clone(),
-        reason,
-        evidence,
-        status: crate::state::DisputeStatus::Raised,
-        created_at: env.block.time,
-        resolved_at: None,
-        resolution: None,
-    };
-
-    DISPUTES.save(deps.storage, &dispute_id, &dispute)?;
-
-    // Update escrow to prevent release
-    if let Some(ref escrow_id) = job.escrow_id {
-        if let Ok(mut escrow) = ESCROWS.load(deps.storage, escrow_id) {
-            escrow.dispute_status = crate::state::DisputeStatus::Raised;
-            escrow.dispute_raised_at = Some(env.block.time);
-            // Set dispute deadline (e.g., 7 days for resolution)
-            escrow.dispute_deadline = Some(env.block.time.plus_days(7));
-            ESCROWS.save(deps.storage, escrow_id, &escrow)?;
+    // 🌐 Content edits replace the off-chain bundle and its hash mappings
+    let mut new_content_hash = None;
+    if cover_letter.is_some() || milestones.is_some() {
+        if let Some(cover_letter) = &cover_letter {
+            validate_string_field(cover_letter, "Cover letter", 1, 200)?;
         }
-    }
-
-    // Build response
-    let response = build_success_response!("raise_dispute", job_id, &info.sender)
-        .add_attribute("dispute_id", dispute_id.clone())
-        .add_attribute("job_id", job_id.to_string())
-        .add_attribute("reason_length", dispute.reason.len().to_string())
-        .add_attribute("evidence_count", dispute.evidence.len().to_string());
-
-    Ok(response)
-}
-
-pub fn execute_resolve_dispute(
-    mut deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    dispute_id: String,
-    resolution: String,
-    release_to_freelancer: bool,
-) -> Result<Response, ContractError> {
-    // Apply security checks and admin check
-    apply_security_checks!(deps, env, info, RateLimitAction::ResolveDispute);
-    ensure_admin!(deps, info);
 
-    // Validate inputs
-    if resolution.len() < 10 || resolution.len() > 2000 {
-        return Err(ContractError::InvalidInput {
-            error: "Resolution must be 10-2000 characters".to_string(),
-        });
-    }
-
-    // Load dispute from storage
-    let mut dispute = DISPUTES.load(deps.storage, &dispute_id)?;
+        // The previous bundle lives off-chain, so fields left as None are
+        // carried over from it by reference instead of being cleared
+        let entity_key = format!("proposal_{}", proposal_id);
+        let previous_hash = ENTITY_TO_HASH.may_load(deps.storage, &entity_key)?;
+        let milestones_json = milestones
+            .as_ref()
+            .map(|m| serde_json::to_value(m).unwrap_or(serde_json::Value::Null));
+
+        let (off_chain_bundle, content_hash_str) = create_proposal_edit_bundle(
+            proposal_id,
+            cover_letter.as_deref(),
+            milestones_json.as_ref().map(std::slice::from_ref),
+            previous_hash.as_deref(),
+            env.block.time.seconds(),
+        )?;
 
-    if dispute.status != crate::state::DisputeStatus::Raised {
-        return Err(ContractError::InvalidInput {
-            error: "Dispute is not in raised status".to_string(),
-        });
-    }
+        let content_hash = create_content_hash(
+            &serde_json::to_string(&off_chain_bundle).map_err(|e| ContractError::InvalidInput {
+                error: format!("Serialization error: {}", e),
+            })?,
+            "proposal_content",
+            env.block.time.seconds(),
+        )?;
 
-    // Load and update job
-    let mut job = JOBS.load(deps.storage, dispute.job_id)?;
+        // 🗄️ Swap hash mappings over to the new bundle
+        if let Some(old_hash) = previous_hash {
+            CONTENT_HASHES.remove(deps.storage, &old_hash);
+            HASH_TO_ENTITY.remove(deps.storage, &old_hash);
+        }
+        CONTENT_HASHES.save(deps.storage, &content_hash_str, &content_hash)?;
+        HASH_TO_ENTITY.save(deps.storage, &content_hash_str, &entity_key)?;
+        ENTITY_TO_HASH.save(deps.storage, &entity_key, &content_hash_str)?;
 
-    if job.status != JobStatus::Disputed {
-        return Err(ContractError::InvalidInput {
-            error: "Job is not disputed".to_string(),
-        });
+        proposal.content_hash = content_hash;
+        new_content_hash = Some(content_hash_str);
     }
 
-    // Update dispute record
-    dispute.status = crate::state::DisputeStatus::Resolved;
-    dispute.resolved_at = Some(env.block.time);
-    dispute.resolution = Some(resolution.clone());
-    DISPUTES.save(deps.storage, &dispute_id, &dispute)?;
-
-    // Handle escrow resolution and payment
-    let mut messages = Vec::new();
-
-    if let Some(ref escrow_id) = job.escrow_id {
-        if let Ok(mut escrow) = ESCROWS.load(deps.storage, escrow_id) {
-            // Update escrow status
-            escrow.dispute_status = crate::state::DisputeStatus::Resolved;
-            escrow.released = true;
-
-            // Create payment message based on resolution
-            let recipient = if release_to_freelancer {
-                job.assigned_freelancer.as_ref().unwrap_or(&job.poster)
-            } else {
-                &job.poster
-            };
-
-            let payment_msg = cosmwasm_std::BankMsg::Send {
-                to_address: recipient.to_string(),
-                amount: vec![cosmwasm_std::Coin {
-                    denom: "uxion".to_string(),
-                    amount: escrow.amount,
-                }],
-            };
-            messages.push(cosmwasm_std::SubMsg::new(payment_msg));
-
-            ESCROWS.save(deps.storage, escrow_id, &escrow)?;
-        }
-    }
+    // Refresh the timestamp to indicate proposal was modified
+    proposal.submitted_at = env.block.time;
+    proposal.expires_at = proposal_expires_at(
+        &CONFIG.load(deps.storage)?,
+        proposal.submitted_at,
+        proposal.delivery_time_days,
+    )?;
 
-    // Resolve dispute
-    job.status = JobStatus::Completed;
-    job.updated_at = env.block.time;
-    JOBS.save(deps.storage, dispute.job_id, &job)?;
+    // Save updated proposal
+    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
 
     // Build response
-    let mut response = build_success_response!("resolve_dispute", dispute.job_id, &info.sender);
-
-    response = response
-        .add_submessages(messages)
-        .add_attribute("dispute_id", dispute_id)
-        .add_attribute("job_id", dispute.job_id.to_string())
-        .add_attribute("release_to_freelancer", release_to_freelancer.to_string())
-        .add_attribute("resolution_length", resolution.len().to_string());
+    let mut response = build_success_response!(
+        "edit_proposal",
+        proposal_id,
+        &info.sender,
+        "content_changed" => new_content_hash.is_some().to_string()
+    );
+    if let Some(hash) = new_content_hash {
+        response = response.add_attribute("content_hash", hash);
+    }
 
     Ok(response)
 }
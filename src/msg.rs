@@ -1,22 +1,43 @@
 use crate::state::{
-    AuditLog, Bounty, BountyStatus, BountySubmission, BountySubmissionStatus, Config,
-    ContactPreference, Dispute, EscrowState, Job, JobStatus, Proposal, ProposalMilestone, Rating,
+    AdminRole, AuditLog, Bounty, BountyStatus, BountySubmission, BountySubmissionStatus, Config,
+    ContactPreference, Dispute, DisputeStatus, EscrowEvent, EscrowState, Job, JobStatus, JobTemplate, Proposal, ProposalMilestone,
+    ProposalStatus, Rating, ReleasePolicy,
     SecurityMetrics, UserStats,
 };
-use cosmwasm_std::{Timestamp, Uint128};
+use cosmwasm_schema::QueryResponses;
+use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
+use cw20::{Cw20ReceiveMsg, Denom};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct InstantiateMsg {
     pub admin: Option<String>,
     pub platform_fee_percent: Option<u64>,
     pub min_escrow_amount: Option<Uint128>,
     pub dispute_period_days: Option<u64>,
     pub max_job_duration_days: Option<u64>,
+    pub denom: Option<String>,
+    pub treasury: Option<String>,
+    pub dispute_timeout_release_to_freelancer: Option<bool>,
+    pub max_proposals_per_job: Option<u64>,
+    pub job_archive_retention_days: Option<u64>,
+    pub fee_split_bps: Option<u16>,
+    pub max_bounty_reward: Option<Uint128>,
+    pub edit_grace_period_days: Option<u64>,
+    pub allowed_denoms: Option<Vec<String>>, // Defaults to just `denom`
+    pub allowed_cw20_tokens: Option<Vec<String>>, // Defaults to none
+    pub proposal_validity_days: Option<u64>,
+    pub min_edit_interval_seconds: Option<u64>,
+    pub dispute_deposit: Option<Uint128>,
+    pub reputation_half_life_days: Option<u64>,
+    pub dispute_cooldown_seconds: Option<u64>,
+    pub max_open_jobs_per_user: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct MilestoneInput {
     pub title: String,
     pub description: String,
@@ -25,18 +46,64 @@ pub struct MilestoneInput {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct RewardTierInput {
     pub position: u64,
     pub percentage: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct WinnerSelection {
     pub submission_id: u64,
     pub position: u64,
 }
 
+/// One listing in a `PostJobsBatch`; fields mirror `ExecuteMsg::PostJob`
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PostJobItem {
+    pub title: String,
+    pub description: String,
+    pub company: Option<String>,
+    pub location: Option<String>,
+    pub category: String,
+    pub skills_required: Vec<String>,
+    pub documents: Option<Vec<String>>,
+    pub milestones: Option<Vec<MilestoneInput>>,
+    pub budget: Uint128,
+    pub duration_days: u64,
+    pub experience_level: u8,
+    pub is_remote: bool,
+    pub urgency_level: u8,
+    pub off_chain_storage_key: String,
+    pub release_policy: Option<ReleasePolicy>,
+    pub denom: Option<Denom>,
+}
+
+/// Fields replacing a template's defaults in `PostJobFromTemplate`; `None` keeps the template value
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct JobTemplateOverrides {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub company: Option<String>,
+    pub location: Option<String>,
+    pub category: Option<String>,
+    pub skills_required: Option<Vec<String>>,
+    pub documents: Option<Vec<String>>,
+    pub milestones: Option<Vec<MilestoneInput>>,
+    pub budget: Option<Uint128>,
+    pub duration_days: Option<u64>,
+    pub experience_level: Option<u8>,
+    pub is_remote: Option<bool>,
+    pub urgency_level: Option<u8>,
+    pub release_policy: Option<ReleasePolicy>,
+    pub denom: Option<Denom>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     // JOB MANAGEMENT (HYBRID ON-CHAIN/OFF-CHAIN)
     PostJob {
@@ -59,6 +126,35 @@ pub enum ExecuteMsg {
 
         // WEB2 BACKEND REFERENCE
         off_chain_storage_key: String, // Key for retrieving from web2 backend
+
+        release_policy: Option<ReleasePolicy>, // Defaults to Auto
+        denom: Option<Denom>, // Whitelisted native denom or CW20 token; defaults to the payout denom
+    },
+    PostJobsBatch {
+        // Up to 20 listings, funded together or all left unfunded
+        jobs: Vec<PostJobItem>,
+    },
+    SaveJobTemplate {
+        // Creates or replaces the sender's template with this name
+        name: String,
+        title: String,
+        description: String,
+        category: String,
+        skills_required: Vec<String>,
+        budget: Uint128,
+        duration_days: u64,
+        experience_level: u8,
+        is_remote: bool,
+        urgency_level: u8,
+    },
+    DeleteJobTemplate {
+        name: String,
+    },
+    PostJobFromTemplate {
+        // Posts like PostJob, funded by the attached funds if any
+        name: String,
+        overrides: JobTemplateOverrides,
+        off_chain_storage_key: String,
     },
     EditJob {
         job_id: u64,
@@ -72,6 +168,10 @@ pub enum ExecuteMsg {
         milestones: Option<Vec<MilestoneInput>>,
         off_chain_storage_key: String, // Key for web2 backend updates
     },
+    ObjectToJobEdits {
+        // Assigned freelancer ends the poster's edit grace period
+        job_id: u64,
+    },
     DeleteJob {
         job_id: u64,
     },
@@ -94,33 +194,54 @@ pub enum ExecuteMsg {
         agreed_to_terms: bool,
         agreed_to_escrow: bool,
         estimated_hours: Option<u16>,
+        payout_address: Option<String>, // Wallet or contract paid instead of the submitter
+        referrer: Option<String>,       // Paid a cut of the platform fee on release
+        referral_bps: Option<u16>,      // Referrer's share of the platform fee, at most MAX_REFERRAL_BPS
 
         // 🌐 WEB2 BACKEND REFERENCE
         off_chain_storage_key: String,
     },
+    SetUserVerified {
+        address: String,
+        verified: bool,
+    },
     EditProposal {
         proposal_id: u64,
-        cover_letter: Option<String>,
+        cover_letter: Option<String>, // None keeps the current cover letter; same for milestones
         delivery_time_days: Option<u64>,
         milestones: Option<Vec<ProposalMilestone>>,
     },
     WithdrawProposal {
         proposal_id: u64,
     },
+    RejectProposal {
+        proposal_id: u64,
+        reason: Option<String>,
+    },
     AcceptProposal {
         job_id: u64,
         proposal_id: u64,
     },
+    ReassignJob {
+        job_id: u64,
+        new_proposal_id: u64,
+    },
 
     // Escrow Management
+    /// Legacy: funds an unescrowed job like `CreateEscrowNative`; on a job whose
+    /// escrow already exists, the single attached coin tops it up like `TopUpEscrow`,
+    /// covering the budget increase plus the poster's fee share
     CreateEscrow {
         job_id: u64,
+        refund_overpayment: Option<bool>, // Send back any surplus instead of rejecting it; default false
     },
     CreateEscrowNative {
         job_id: u64,
         amount: Uint128,
+        refund_overpayment: Option<bool>,
     },
     CreateEscrowCw20 {
+        // Deprecated: send the tokens with ReceiveMsg::FundJobEscrow
         job_id: u64,
         token_address: String,
         amount: Uint128,
@@ -128,6 +249,10 @@ pub enum ExecuteMsg {
     FundEscrow {
         escrow_id: String,
     },
+    /// Attach the budget increase plus the poster's fee share on it, in the escrow's denom
+    TopUpEscrow {
+        job_id: u64,
+    },
     ReleaseEscrow {
         escrow_id: String,
     },
@@ -136,7 +261,14 @@ pub enum ExecuteMsg {
     },
 
     // Work Management
+    SubmitWork {
+        job_id: u64,
+    },
+    ApproveCompletion {
+        job_id: u64,
+    },
     CompleteJob {
+        // Freelancer claims the payout once the client review window lapses
         job_id: u64,
     },
     CompleteMilestone {
@@ -154,6 +286,10 @@ pub enum ExecuteMsg {
         rating: u8,
         comment: String,
     },
+    FinalizeRatings {
+        // Reveal ratings whose counterpart never arrived within the window
+        job_id: u64,
+    },
 
     // Dispute Management
     RaiseDispute {
@@ -165,18 +301,69 @@ pub enum ExecuteMsg {
         dispute_id: String,
         resolution: String,
         release_to_freelancer: bool,
+        freelancer_bps: Option<u16>, // Split the escrow instead (0-10000), overrides release_to_freelancer
+    },
+    AppealDispute {
+        dispute_id: String,
+    },
+    FinalizeDispute {
+        dispute_id: String,
+    },
+    ClaimDisputeTimeout {
+        dispute_id: String,
     },
 
     // Admin Functions
     UpdateConfig {
-        admin: Option<String>,
         platform_fee_percent: Option<u64>,
         min_escrow_amount: Option<Uint128>,
         dispute_period_days: Option<u64>,
         max_job_duration_days: Option<u64>,
+        treasury: Option<String>,
+        dispute_timeout_release_to_freelancer: Option<bool>,
+        max_proposals_per_job: Option<u64>,
+        job_archive_retention_days: Option<u64>,
+        fee_split_bps: Option<u16>,
+        max_bounty_reward: Option<Uint128>,
+        edit_grace_period_days: Option<u64>,
+        proposal_validity_days: Option<u64>,
+        min_edit_interval_seconds: Option<u64>,
+        dispute_deposit: Option<Uint128>,
+        reputation_half_life_days: Option<u64>,
+        dispute_cooldown_seconds: Option<u64>,
+        max_open_jobs_per_user: Option<u64>,
+    },
+    SetAllowedDenoms {
+        // Replaces the whitelist; must keep the payout denom
+        denoms: Vec<String>,
+    },
+    SetAllowedCw20Tokens {
+        // Replaces the CW20 whitelist; existing escrows keep paying out in their token
+        tokens: Vec<String>,
     },
     PauseContract {},
     UnpauseContract {},
+    FreezeEscrows {},
+    UnfreezeEscrows {},
+    ProposeAdmin {
+        // Takes effect once the new admin calls AcceptAdmin
+        new_admin: String,
+    },
+    AcceptAdmin {},
+    GrantRole {
+        address: String,
+        role: AdminRole,
+    },
+    RevokeRole {
+        address: String,
+    },
+    WithdrawFees {
+        denom: Option<Denom>,    // Native denom or CW20 token the fees were earned in; defaults to the payout denom
+        amount: Option<Uint128>, // Defaults to all fees accrued in that denom
+    },
+    ArchiveJobs {
+        job_ids: Vec<u64>, // Completed/cancelled jobs past the retention window
+    },
 
     // User Profile Management (HYBRID)
     UpdateUserProfile {
@@ -210,6 +397,8 @@ pub enum ExecuteMsg {
         reward_distribution: Vec<RewardTierInput>,
         documents: Option<Vec<String>>,
     },
+    /// CW20 hook; the embedded message must be a `ReceiveMsg`
+    Receive(Cw20ReceiveMsg),
     EditBounty {
         bounty_id: u64,
         title: Option<String>,
@@ -222,6 +411,10 @@ pub enum ExecuteMsg {
     CancelBounty {
         bounty_id: u64,
     },
+    ExpireBounty {
+        // Anyone may refund a bounty whose review window lapsed without winners
+        bounty_id: u64,
+    },
     SubmitToBounty {
         bounty_id: u64,
         title: String,
@@ -253,6 +446,20 @@ pub enum ExecuteMsg {
     ReleaseBountyRewards {
         bounty_id: u64,
     },
+    ContestBountyResult {
+        // Any submitter may challenge the selection before rewards release
+        bounty_id: u64,
+        reason: String,
+    },
+    ResolveBountyContest {
+        // Upholding pays the selected winners; overturning reopens winner selection
+        bounty_id: u64,
+        uphold_selection: bool,
+    },
+    SetBountyFeatured {
+        bounty_id: u64,
+        featured: bool,
+    },
 
     // Security Functions
     BlockAddress {
@@ -267,12 +474,41 @@ pub enum ExecuteMsg {
     },
 }
 
+/// Messages carried in the `msg` field of a CW20 `Send` to this contract
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    /// Create a bounty funded by the sent tokens
+    CreateBounty {
+        title: String,
+        description: String,
+        requirements: Vec<String>,
+        category: String,
+        skills_required: Vec<String>,
+        submission_deadline_days: u64, // Days from now
+        review_period_days: u64,
+        max_winners: u64,
+        reward_distribution: Vec<RewardTierInput>,
+        documents: Option<Vec<String>>,
+    },
+    /// Fund the escrow of an unfunded job posted in the sent token; the CW20
+    /// sender must be the job's poster
+    FundJobEscrow { job_id: u64 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
+#[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     // Job Queries
+    #[returns(JobResponse)]
     GetJob {
         job_id: u64,
     },
+    #[returns(JobsResponse)]
+    GetJobsByIds {
+        ids: Vec<u64>, // At most 50; missing ids are skipped
+    },
+    #[returns(JobsResponse)]
     GetJobs {
         start_after: Option<u64>,
         limit: Option<u32>,
@@ -280,67 +516,198 @@ pub enum QueryMsg {
         status: Option<JobStatus>,
         poster: Option<String>,
     },
+    #[returns(JobsResponse)]
+    GetJobsBySkills {
+        skills: Vec<String>,
+        match_all: bool, // true: jobs tagged with every skill, false: with any of them
+        limit: Option<u32>,
+    },
+    #[returns(JobsResponse)]
+    GetJobsByBudgetRange {
+        // Budget tiers: 1 is under 500, 2 is under 5000, 3 is 5000 and up
+        range: u8,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(JobResponse)]
+    GetArchivedJob {
+        job_id: u64,
+    },
+    #[returns(JobsResponse)]
+    GetArchivedJobs {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(JobsResponse)]
     GetAllJobs {
         // For frontend landing page - gets all active jobs with basic filtering
         limit: Option<u32>,
         category: Option<String>,
     },
+    #[returns(JobsResponse)]
     GetUserJobs {
         user: String,
         status: Option<JobStatus>,
     },
+    #[returns(JobTemplatesResponse)]
+    GetJobTemplates {
+        poster: String,
+    },
 
     // Proposal Queries
+    #[returns(ProposalResponse)]
     GetProposal {
         proposal_id: u64,
     },
+    #[returns(ProposalsResponse)]
     GetJobProposals {
         job_id: u64,
+        // Defaults to true; pass false to hide proposals past their expires_at
+        include_expired: Option<bool>,
+    },
+    #[returns(ProposalsResponse)]
+    GetJobProposalsRanked {
+        // Highest proposal score first; expired proposals are left out
+        job_id: u64,
+        limit: Option<u32>,
+    },
+    #[returns(JobDetailResponse)]
+    GetJobDetail {
+        // Job, escrow and proposals for a detail page in one round trip
+        job_id: u64,
+        proposals_limit: Option<u32>,
+    },
+    #[returns(ProposalsResponse)]
+    GetJobProposalsByStatus {
+        job_id: u64,
+        status: ProposalStatus,
+        start_after: Option<u64>,
+        limit: Option<u32>,
     },
+    #[returns(ProposalsResponse)]
     GetUserProposals {
         user: String,
         start_after: Option<u64>,
         limit: Option<u32>,
     },
+    #[returns(AcceptedProposalsResponse)]
+    GetAcceptedProposals {
+        // Newest first, with the won job's current status
+        freelancer: String,
+        limit: Option<u32>,
+    },
 
     // Escrow Queries
+    #[returns(EscrowResponse)]
     GetEscrow {
         escrow_id: String,
     },
+    #[returns(EscrowResponse)]
     GetJobEscrow {
         job_id: u64,
     },
+    #[returns(EscrowHistoryResponse)]
+    GetEscrowHistory {
+        // Audit trail of the escrow's transitions, oldest first
+        escrow_id: String,
+    },
+    #[returns(EscrowsResponse)]
+    GetEscrows {
+        // For ops monitoring of locked and disputed funds
+        start_after: Option<String>,
+        limit: Option<u32>,
+        released: Option<bool>,
+        dispute_status: Option<DisputeStatus>,
+    },
+    #[returns(SolvencyResponse)]
+    GetSolvency {},
+    #[returns(FeePreviewResponse)]
+    PreviewFees {
+        // Escrow terms for a budget under the current config
+        amount: Uint128,
+        category: Option<String>, // Every category currently shares `platform_fee_percent`
+    },
 
     // Rating Queries
+    #[returns(RatingsResponse)]
     GetUserRatings {
         user: String,
     },
+    #[returns(Rating)]
     GetJobRating {
+        // Not found while the rating is still held pending
         job_id: u64,
         rater: String,
     },
+    #[returns(bool)]
+    HasRated {
+        // Returns a bool; true while the rating is still held pending too
+        job_id: u64,
+        user: String,
+    },
 
     // Stats Queries
+    #[returns(UserStatsResponse)]
     GetUserStats {
         user: String,
     },
+    #[returns(UserReputationResponse)]
+    GetUserReputation {
+        // Average of the user's recent ratings, weighted towards newer ones
+        user: String,
+    },
+    #[returns(UserActivityResponse)]
+    GetUserActivity {
+        user: String,
+        limit: Option<u32>, // Recent items per collection, default 5, max 20
+    },
+    #[returns(UserProfileResponse)]
+    GetUserProfile {
+        user: String,
+    },
+    #[returns(VerifiedUsersResponse)]
+    GetVerifiedUsers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(PlatformStatsResponse)]
     GetPlatformStats {},
+    #[returns(CountsResponse)]
+    GetCounts {
+        // Counter-backed totals, cheap compared to GetPlatformStats
+    },
 
     // Dispute Queries
+    #[returns(DisputeResponse)]
     GetDispute {
         dispute_id: String,
     },
+    #[returns(DisputeDetailResponse)]
+    GetDisputeDetail {
+        // Dispute with its job and escrow for resolvers
+        dispute_id: String,
+    },
+    #[returns(DisputesResponse)]
     GetJobDisputes {
         job_id: u64,
     },
+    #[returns(DisputesResponse)]
     GetUserDisputes {
         user: String,
     },
+    #[returns(DisputesResponse)]
+    GetDisputesByStatus {
+        status: DisputeStatus,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 
     // Bounty Queries
+    #[returns(BountyResponse)]
     GetBounty {
         bounty_id: u64,
     },
+    #[returns(BountiesResponse)]
     GetBounties {
         start_after: Option<u64>,
         limit: Option<u32>,
@@ -348,41 +715,88 @@ pub enum QueryMsg {
         status: Option<BountyStatus>,
         poster: Option<String>,
     },
+    #[returns(BountiesResponse)]
     GetAllBounties {
         // For frontend landing page - gets all active bounties with basic filtering
         limit: Option<u32>,
         category: Option<String>,
     },
+    #[returns(BountiesResponse)]
     GetUserBounties {
         user: String,
         status: Option<BountyStatus>,
     },
+    #[returns(BountySubmissionResponse)]
     GetBountySubmission {
         submission_id: u64,
     },
+    #[returns(BountySubmissionsResponse)]
     GetBountySubmissions {
         bounty_id: u64,
         status: Option<BountySubmissionStatus>,
     },
+    #[returns(BountyWinnersResponse)]
+    GetBountyWinners {
+        // Leaderboard of selected winners ordered by position
+        bounty_id: u64,
+    },
+    #[returns(BountySubmissionsResponse)]
     GetUserBountySubmissions {
         user: String,
+        status: Option<BountySubmissionStatus>,
         start_after: Option<u64>,
         limit: Option<u32>,
     },
+    #[returns(BountiesResponse)]
+    GetFeaturedBounties {
+        // For the homepage carousel
+        limit: Option<u32>,
+    },
+    #[returns(BountiesResponse)]
+    GetBountiesExpiringSoon {
+        // Open bounties closing within the window, soonest deadline first
+        within_days: u64,
+        limit: Option<u32>,
+    },
 
     // Config Query
+    #[returns(ConfigResponse)]
     GetConfig {},
+    #[returns(ContractInfoResponse)]
+    GetContractInfo {},
+    #[returns(AccruedFeesResponse)]
+    GetAccruedFees {
+        denom: Option<Denom>, // Defaults to the payout denom
+    },
+    #[returns(CategoryRegistryResponse)]
+    GetCategories {},
+
+    // Content Queries
+    #[returns(ContentHashResolutionResponse)]
+    ResolveContentHash {
+        hash: String,
+    },
+    #[returns(ContentReferencesResponse)]
+    GetContentReferences {
+        // Every entity's current content hash, ordered by entity key
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 
     // Security Queries
+    #[returns(SecurityMetricsResponse)]
     GetSecurityMetrics {},
+    #[returns(AuditLogsResponse)]
     GetAuditLogs {
         start_after: Option<String>,
         limit: Option<u32>,
         action_filter: Option<String>,
     },
+    #[returns(AddressBlockedResponse)]
     IsAddressBlocked {
         address: String,
     },
+    #[returns(RateLimitStatusResponse)]
     GetRateLimitStatus {
         address: String,
     },
@@ -390,41 +804,125 @@ pub enum QueryMsg {
 
 // Response types
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct JobResponse {
-    pub job: Job,
+    pub job: Option<Job>, // None when the job does not exist
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct JobsResponse {
     pub jobs: Vec<Job>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct JobTemplatesResponse {
+    pub templates: Vec<JobTemplate>, // Sorted by name
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct ProposalResponse {
     pub proposal: Proposal,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct ProposalsResponse {
     pub proposals: Vec<Proposal>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AcceptedProposal {
+    pub proposal: Proposal,
+    pub job_status: JobStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AcceptedProposalsResponse {
+    pub proposals: Vec<AcceptedProposal>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct EscrowResponse {
-    pub escrow: EscrowState,
+    pub escrow: Option<EscrowState>, // None when the escrow does not exist
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct EscrowHistoryResponse {
+    pub escrow_id: String,
+    pub events: Vec<EscrowEvent>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct RatingsResponse {
     pub ratings: Vec<Rating>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct UserStatsResponse {
     pub stats: UserStats,
+    pub is_verified: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct UserReputationResponse {
+    pub user: Addr,
+    pub reputation: Decimal, // 0-5, zero until the user has a revealed rating
+    pub ratings_considered: u64,
+    pub half_life_days: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct JobDetailResponse {
+    pub job: Job,
+    pub escrow: Option<EscrowState>,
+    pub proposals: Vec<Proposal>, // First `proposals_limit` in submission order
+    pub proposal_count: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct UserActivityResponse {
+    pub stats: UserStats,
+    pub total_jobs: u64,
+    pub total_proposals: u64,
+    pub total_bounty_submissions: u64,
+    pub total_ratings: u64,
+    // Most recent first
+    pub recent_jobs: Vec<Job>,
+    pub recent_proposals: Vec<Proposal>,
+    pub recent_bounty_submissions: Vec<BountySubmission>,
+    pub recent_ratings: Vec<Rating>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct VerifiedUsersResponse {
+    pub users: Vec<Addr>,
+}
+
+/// Entities ever created minus deleted jobs and withdrawn proposals; archived jobs still count
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CountsResponse {
+    pub total_jobs: u64,
+    pub total_proposals: u64,
+    pub total_bounties: u64,
+    pub total_submissions: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct PlatformStatsResponse {
     pub total_jobs: u64,
     pub open_jobs: u64,
@@ -438,89 +936,206 @@ pub struct PlatformStatsResponse {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct DisputeResponse {
     pub dispute: Dispute,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DisputeDetailResponse {
+    pub dispute: Dispute,
+    pub job: Job,
+    pub escrow: Option<EscrowState>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct DisputesResponse {
     pub disputes: Vec<Dispute>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct ConfigResponse {
     pub config: Config,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ContractInfoResponse {
+    pub contract: String,
+    pub version: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct SecurityMetricsResponse {
     pub metrics: SecurityMetrics,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct AuditLogsResponse {
     pub logs: Vec<AuditLog>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct AddressBlockedResponse {
     pub is_blocked: bool,
     pub reason: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct RateLimitStatusResponse {
-    pub current_count: u64,
+    pub current_count: u64, // Job posting counter, kept for existing clients
     pub limit: u64,
     pub window_start: Timestamp,
     pub is_limited: bool,
+    pub counters: Vec<RateLimitCounter>, // Every rate-limited action in the current window
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct RateLimitCounter {
+    pub action: String,
+    pub count: u64,
+    pub limit: u64,
+    pub is_limited: bool,
 }
 
 // Bounty Response Types
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct BountyResponse {
-    pub bounty: Bounty,
+    pub bounty: Option<Bounty>, // None when the bounty does not exist
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct BountiesResponse {
     pub bounties: Vec<Bounty>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct BountySubmissionResponse {
     pub submission: BountySubmission,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct BountySubmissionsResponse {
     pub submissions: Vec<BountySubmission>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct BountyWinner {
+    pub position: u64,
+    pub submitter: Addr,
+    pub submission_id: u64,
+    pub reward_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct BountyWinnersResponse {
+    pub winners: Vec<BountyWinner>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct SearchResponse {
     pub jobs: Vec<Job>,
     pub bounties: Vec<Bounty>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct TrendingResponse {
     pub trending_jobs: Vec<Job>,
     pub trending_bounties: Vec<Bounty>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct CategoriesResponse {
     pub job_categories: Vec<(String, u64)>,
     pub bounty_categories: Vec<(String, u64)>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AccruedFeesResponse {
+    pub denom: Denom,
+    pub amount: Uint128,
+    pub treasury: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct RegistryEntry {
+    pub id: u8,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CategoryRegistryResponse {
+    pub categories: Vec<RegistryEntry>,
+    pub skills: Vec<RegistryEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct EscrowsResponse {
     pub escrows: Vec<EscrowState>,
 }
 
+/// What funding an escrow for `amount` would charge and pay out
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct FeePreviewResponse {
+    pub deposit: Uint128, // Budget plus the poster's share of the fee
+    pub platform_fee: Uint128,
+    pub net_to_freelancer: Uint128,
+    pub fee_percent_applied: u64,
+}
+
+/// Funds held by unreleased escrows, to reconcile against the bank balance
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SolvencyResponse {
+    pub total_locked: Uint128,
+    pub per_denom: Vec<(String, Uint128)>, // Sorted by denom
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ContentReference {
+    pub entity_key: String,
+    pub hash: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ContentReferencesResponse {
+    pub references: Vec<ContentReference>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ContentHashResolutionResponse {
+    pub entity_type: String,
+    pub entity_id: u64,
+    pub current_hash: String, // Entity's current content hash; differs when the queried hash is stale
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct UserProfileResponse {
     pub profile: crate::state::UserProfile,
 }
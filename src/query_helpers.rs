@@ -1,7 +1,10 @@
+use crate::category_skill_manager::skill_id;
 use crate::hash_utils::ContentHash;
 use crate::msg::*;
 use crate::state::*;
-use cosmwasm_std::{Deps, Order, StdResult, Uint128};
+use cosmwasm_std::{Deps, Order, StdError, StdResult, Uint128};
+use cw_storage_plus::{Bound, Item};
+use std::collections::BTreeSet;
 
 /// Generic pagination helper for any collection
 pub struct PaginationParams {
@@ -20,6 +23,7 @@ impl PaginationParams {
 
 /// 🎯 Enhanced JobResponse with hash reference for off-chain content
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct HashAwareJobResponse {
     pub id: u64,
     pub poster: String,
@@ -49,6 +53,7 @@ pub struct HashAwareJobResponse {
 
 /// 🎯 Enhanced ProposalResponse with hash reference
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct HashAwareProposalResponse {
     pub id: u64,
     pub freelancer: String,
@@ -72,6 +77,7 @@ pub struct HashAwareProposalResponse {
 
 /// 🎯 Enhanced UserProfileResponse with hash reference
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub struct HashAwareUserProfileResponse {
     pub address: String,
     pub created_at: cosmwasm_std::Timestamp,
@@ -89,77 +95,93 @@ pub struct HashAwareUserProfileResponse {
     pub response_time_hours: u8,
 }
 
+/// O(1) entity totals read from the id counters
+pub fn query_counts(deps: Deps) -> StdResult<CountsResponse> {
+    let live = |next_id: &Item<u64>, removed: Option<&Item<u64>>| {
+        let created = next_id.may_load(deps.storage)?.unwrap_or_default();
+        let removed = match removed {
+            Some(tally) => tally.may_load(deps.storage)?.unwrap_or_default(),
+            None => 0,
+        };
+        StdResult::Ok(created.saturating_sub(removed))
+    };
+    Ok(CountsResponse {
+        total_jobs: live(&NEXT_JOB_ID, Some(&REMOVED_JOB_COUNT))?,
+        total_proposals: live(&NEXT_PROPOSAL_ID, Some(&REMOVED_PROPOSAL_COUNT))?,
+        total_bounties: live(&NEXT_BOUNTY_ID, None)?,
+        total_submissions: live(&NEXT_BOUNTY_SUBMISSION_ID, None)?,
+    })
+}
+
 /// Platform statistics calculation with hash-aware data
 pub fn query_platform_stats(deps: Deps) -> StdResult<PlatformStatsResponse> {
-    // Count jobs by status
-    let mut total_jobs = 0u64;
+    // Archived jobs are only counted, never scanned
+    let mut total_jobs = ARCHIVED_JOB_COUNT.may_load(deps.storage)?.unwrap_or_default();
     let mut open_jobs = 0u64;
     let mut in_progress_jobs = 0u64;
-    let mut completed_jobs = 0u64;
+    let mut completed_jobs = ARCHIVED_COMPLETED_JOB_COUNT
+        .may_load(deps.storage)?
+        .unwrap_or_default();
 
-    let jobs: StdResult<Vec<_>> = JOBS
+    // Efficiently process jobs using iterator without collecting all into memory
+    for (_, job) in JOBS
         .range(deps.storage, None, None, Order::Ascending)
-        .collect();
-
-    if let Ok(job_pairs) = jobs {
-        for (_, job) in job_pairs {
-            total_jobs += 1;
-            match job.status {
-                JobStatus::Open => open_jobs += 1,
-                JobStatus::InProgress => in_progress_jobs += 1,
-                JobStatus::Completed => completed_jobs += 1,
-                _ => {}
-            }
+        .flatten()
+    {
+        total_jobs += 1;
+
+        match job.status {
+            JobStatus::Open => open_jobs += 1,
+            JobStatus::InProgress => in_progress_jobs += 1,
+            JobStatus::Completed => completed_jobs += 1,
+            _ => {}
         }
     }
 
-    // Count bounties by status
-    let mut total_bounties = 0u64;
-    let mut open_bounties = 0u64;
-    let mut completed_bounties = 0u64;
-
-    let bounties: StdResult<Vec<_>> = BOUNTIES
+    // Count bounties
+    let bounty_stats = BOUNTIES
         .range(deps.storage, None, None, Order::Ascending)
-        .collect();
-
-    if let Ok(bounty_pairs) = bounties {
-        for (_, bounty) in bounty_pairs {
-            total_bounties += 1;
-            match bounty.status {
-                BountyStatus::Open => open_bounties += 1,
-                BountyStatus::Completed => completed_bounties += 1,
-                _ => {}
-            }
-        }
-    }
-
-    // Count total users with profiles
-    let total_users = USER_PROFILES
+        .try_fold(
+            (0u64, 0u64, 0u64),
+            |acc, result| -> StdResult<(u64, u64, u64)> {
+                let (_, bounty) = result?;
+                let (total, open, completed) = acc;
+                Ok((
+                    total + 1,
+                    if bounty.status == BountyStatus::Open { open + 1 } else { open },
+                    if bounty.status == BountyStatus::Completed { completed + 1 } else { completed },
+                ))
+            },
+        )?;
+
+    // USER_STATS is the authoritative user set: a record is created once an
+    // address is hired, completes a job or is rated. Profiles are optional
+    // metadata and are not counted on their own.
+    let total_users = USER_STATS
         .range(deps.storage, None, None, Order::Ascending)
         .count() as u64;
 
-    // Calculate total value locked (from escrows)
-    let mut total_value_locked = Uint128::zero();
-    let escrows: StdResult<Vec<_>> = ESCROWS
+    // Total value locked is everything still held in unreleased escrows
+    let total_value_locked = ESCROWS
         .range(deps.storage, None, None, Order::Ascending)
-        .collect();
-
-    if let Ok(escrow_pairs) = escrows {
-        for (_, escrow) in escrow_pairs {
-            if !escrow.released {
-                total_value_locked += escrow.amount;
+        .try_fold(Uint128::zero(), |acc, result| -> StdResult<Uint128> {
+            let (_, escrow) = result?;
+            if escrow.released {
+                return Ok(acc);
             }
-        }
-    }
+            Ok(acc
+                .checked_add(escrow.amount)?
+                .checked_add(escrow.platform_fee)?)
+        })?;
 
     Ok(PlatformStatsResponse {
         total_jobs,
         open_jobs,
         in_progress_jobs,
         completed_jobs,
-        total_bounties,
-        open_bounties,
-        completed_bounties,
+        total_bounties: bounty_stats.0,
+        open_bounties: bounty_stats.1,
+        completed_bounties: bounty_stats.2,
         total_users,
         total_value_locked,
     })
@@ -496,9 +518,9 @@ pub fn job_to_hash_aware_response(job: &Job, off_chain_key: String) -> HashAware
         content_hash: job.content_hash.clone(),
         off_chain_data_key: off_chain_key,
         // ULTRA-MINIMAL: These fields moved to off-chain content
-        category_id: 0,          // Backend handles category filtering
-        skill_tags: vec![],      // Backend handles skill filtering
-        budget_range: 0,         // Backend handles budget filtering
+        category_id: job.category_id,
+        skill_tags: job.skill_tags.clone(),
+        budget_range: job.budget_range,
         experience_level: 0,     // Backend handles experience filtering
         is_remote: false,        // Backend handles remote filtering
         has_milestones: false,   // Backend handles milestone filtering
@@ -523,7 +545,7 @@ pub fn proposal_to_hash_aware_response(
         content_hash: proposal.content_hash.clone(),
         off_chain_data_key: off_chain_key,
         // ULTRA-MINIMAL: These fields moved to off-chain content
-        proposal_score: 0,       // Backend handles proposal scoring
+        proposal_score: proposal.score,
         has_milestones: false,   // Backend handles milestone info
         milestone_count: 0,      // Backend handles milestone count
         estimated_hours: 0,      // Backend handles time estimation
@@ -550,6 +572,73 @@ pub fn user_profile_to_hash_aware_response(
     }
 }
 
+/// Jobs tagged with the given skills, newest first. `match_all` intersects the
+/// per-skill job sets from `JOBS_BY_SKILL`; otherwise they are unioned.
+pub fn query_jobs_by_skills(
+    deps: Deps,
+    skills: Vec<String>,
+    match_all: bool,
+    limit: Option<u32>,
+) -> StdResult<JobsResponse> {
+    let limit = limit.unwrap_or(50).min(100) as usize;
+
+    let tags: Vec<Option<u8>> = skills.iter().map(|skill| skill_id(skill)).collect();
+    // An unregistered skill can never be matched, so requiring all of them yields nothing
+    if tags.is_empty() || (match_all && tags.iter().any(Option::is_none)) {
+        return Ok(JobsResponse { jobs: vec![] });
+    }
+
+    let mut matched: Option<BTreeSet<u64>> = None;
+    for tag in tags.into_iter().flatten() {
+        let job_ids = JOBS_BY_SKILL
+            .prefix(tag)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<BTreeSet<u64>>>()?;
+        matched = Some(match matched {
+            None => job_ids,
+            Some(acc) if match_all => acc.intersection(&job_ids).copied().collect(),
+            Some(mut acc) => {
+                acc.extend(job_ids);
+                acc
+            }
+        });
+    }
+
+    let mut jobs = Vec::new();
+    for job_id in matched.unwrap_or_default().into_iter().rev() {
+        if let Some(job) = JOBS.may_load(deps.storage, job_id)? {
+            jobs.push(job);
+            if jobs.len() >= limit {
+                break;
+            }
+        }
+    }
+
+    Ok(JobsResponse { jobs })
+}
+
+/// Jobs in one budget tier, oldest first, read from `JOBS_BY_BUDGET_RANGE`
+pub fn query_jobs_by_budget_range(
+    deps: Deps,
+    range: u8,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<JobsResponse> {
+    if !(1..=3).contains(&range) {
+        return Err(StdError::generic_err("Budget range must be 1, 2 or 3"));
+    }
+    let limit = limit.unwrap_or(50).min(100) as usize;
+
+    let jobs = JOBS_BY_BUDGET_RANGE
+        .prefix(range)
+        .keys(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .map(|job_id| JOBS.load(deps.storage, job_id?))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(JobsResponse { jobs })
+}
+
 /// 🔍 Query hash-aware jobs with efficient filtering
 pub fn query_hash_aware_jobs(
     deps: Deps,
@@ -558,24 +647,21 @@ pub fn query_hash_aware_jobs(
     let limit = limit.unwrap_or(50).min(100) as usize;
     let mut results = Vec::new();
 
-    // ULTRA-MINIMAL: Since search indexes are removed, iterate through all jobs
-    // Backend should handle advanced filtering for better performance
-    for job_result in JOBS.range(deps.storage, None, None, Order::Descending) {
-        if let Ok((job_id, job)) = job_result {
-            // Only include open jobs
-            if job.status == JobStatus::Open {
-                // Get off-chain key
-                let entity_key = format!("job_{}", job_id);
-                let off_chain_key = ENTITY_TO_HASH
-                    .load(deps.storage, &entity_key)
-                    .unwrap_or_default();
+    // Only open jobs, read through the active index; backend handles advanced filtering
+    for job_id in ACTIVE_JOBS.keys(deps.storage, None, None, Order::Descending) {
+        let job_id = job_id?;
+        let job = JOBS.load(deps.storage, job_id)?;
 
-                results.push(job_to_hash_aware_response(&job, off_chain_key));
+        // Get off-chain key
+        let entity_key = format!("job_{}", job_id);
+        let off_chain_key = ENTITY_TO_HASH
+            .load(deps.storage, &entity_key)
+            .unwrap_or_default();
 
-                if results.len() >= limit {
-                    break;
-                }
-            }
+        results.push(job_to_hash_aware_response(&job, off_chain_key));
+
+        if results.len() >= limit {
+            break;
         }
     }
 
@@ -600,3 +686,163 @@ pub fn get_content_hash_for_entity(
 pub fn resolve_hash_to_reference(deps: Deps, hash: &str) -> StdResult<Option<String>> {
     HASH_TO_ENTITY.may_load(deps.storage, hash)
 }
+
+/// 🔍 Map a content hash back to its entity and that entity's current hash
+pub fn query_resolve_content_hash(
+    deps: Deps,
+    hash: String,
+) -> StdResult<ContentHashResolutionResponse> {
+    let entity_key = resolve_hash_to_reference(deps, &hash)?
+        .ok_or_else(|| StdError::not_found("Content hash"))?;
+
+    // Entity keys look like "job_1" or "bounty_submission_3"
+    let (entity_type, entity_id) = entity_key
+        .rsplit_once('_')
+        .and_then(|(entity_type, id)| id.parse::<u64>().ok().map(|id| (entity_type, id)))
+        .ok_or_else(|| {
+            StdError::generic_err(format!("Entity {} has no numeric id", entity_key))
+        })?;
+
+    let current_hash = ENTITY_TO_HASH.load(deps.storage, &entity_key)?;
+
+    Ok(ContentHashResolutionResponse {
+        entity_type: entity_type.to_string(),
+        entity_id,
+        current_hash,
+    })
+}
+
+/// Page through `ENTITY_TO_HASH` so backups can check every referenced blob
+pub fn query_content_references(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ContentReferencesResponse> {
+    let limit = limit.unwrap_or(50).min(100) as usize;
+    let references = ENTITY_TO_HASH
+        .range(
+            deps.storage,
+            start_after.as_deref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(entity_key, hash)| ContentReference { entity_key, hash }))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ContentReferencesResponse { references })
+}
+
+/// Newest-first page of `ids`, skipping entries that no longer exist
+fn recent_items<K, T>(
+    ids: &[K],
+    limit: usize,
+    load: impl Fn(&K) -> StdResult<Option<T>>,
+) -> StdResult<Vec<T>> {
+    let mut items = Vec::new();
+    for id in ids.iter().rev() {
+        if items.len() >= limit {
+            break;
+        }
+        if let Some(item) = load(id)? {
+            items.push(item);
+        }
+    }
+    Ok(items)
+}
+
+/// Most jobs returned by `GetUserJobs`
+pub const MAX_USER_JOBS: usize = 50;
+
+/// 👤 A poster's live jobs, newest first, read from their USER_JOBS index instead of scanning JOBS
+pub fn query_user_jobs(
+    deps: Deps,
+    user: String,
+    status: Option<JobStatus>,
+) -> StdResult<JobsResponse> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let job_ids = USER_JOBS.may_load(deps.storage, &user_addr)?.unwrap_or_default();
+
+    // Archived jobs stay in the index but have left JOBS, so they are skipped
+    let jobs = recent_items(&job_ids, MAX_USER_JOBS, |id| {
+        Ok(JOBS
+            .may_load(deps.storage, *id)?
+            .filter(|job| status.as_ref().is_none_or(|s| &job.status == s)))
+    })?;
+    Ok(JobsResponse { jobs })
+}
+
+/// 🏆 A freelancer's won proposals, from their per-user proposal index
+pub fn query_accepted_proposals(
+    deps: Deps,
+    freelancer: String,
+    limit: Option<u32>,
+) -> StdResult<AcceptedProposalsResponse> {
+    let freelancer_addr = deps.api.addr_validate(&freelancer)?;
+    let limit = limit.unwrap_or(10).min(50) as usize;
+    let proposal_ids = USER_PROPOSALS
+        .may_load(deps.storage, &freelancer_addr)?
+        .unwrap_or_default();
+
+    let proposals = recent_items(&proposal_ids, limit, |id| {
+        let Some(proposal) = PROPOSALS.may_load(deps.storage, *id)? else {
+            return Ok(None);
+        };
+        if proposal.status != ProposalStatus::Accepted {
+            return Ok(None);
+        }
+        // Archived jobs keep their final status
+        let job = match JOBS.may_load(deps.storage, proposal.job_id)? {
+            Some(job) => job,
+            None => ARCHIVED_JOBS.load(deps.storage, proposal.job_id)?,
+        };
+        Ok(Some(AcceptedProposal {
+            proposal,
+            job_status: job.status,
+        }))
+    })?;
+    Ok(AcceptedProposalsResponse { proposals })
+}
+
+/// 👤 Profile page aggregate built from the per-user indexes
+pub fn query_user_activity(
+    deps: Deps,
+    user: String,
+    limit: Option<u32>,
+) -> StdResult<UserActivityResponse> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let limit = limit.unwrap_or(5).min(20) as usize;
+
+    let job_ids = USER_JOBS.may_load(deps.storage, &user_addr)?.unwrap_or_default();
+    let proposal_ids = USER_PROPOSALS
+        .may_load(deps.storage, &user_addr)?
+        .unwrap_or_default();
+    let submission_ids = USER_BOUNTY_SUBMISSIONS
+        .may_load(deps.storage, &user_addr)?
+        .unwrap_or_default();
+    let rating_keys = USER_RATINGS
+        .may_load(deps.storage, &user_addr)?
+        .unwrap_or_default();
+
+    Ok(UserActivityResponse {
+        stats: USER_STATS
+            .may_load(deps.storage, &user_addr)?
+            .unwrap_or_default(),
+        total_jobs: job_ids.len() as u64,
+        total_proposals: proposal_ids.len() as u64,
+        total_bounty_submissions: submission_ids.len() as u64,
+        total_ratings: rating_keys.len() as u64,
+        recent_jobs: recent_items(&job_ids, limit, |id| JOBS.may_load(deps.storage, *id))?,
+        recent_proposals: recent_items(&proposal_ids, limit, |id| {
+            PROPOSALS.may_load(deps.storage, *id)
+        })?,
+        recent_bounty_submissions: recent_items(&submission_ids, limit, |id| {
+            BOUNTY_SUBMISSIONS.may_load(deps.storage, *id)
+        })?,
+        // Held ratings stay blind here too, as in GetJobRating and GetUserRatings
+        recent_ratings: recent_items(&rating_keys, limit, |key| {
+            Ok(RATINGS
+                .may_load(deps.storage, key)?
+                .filter(|rating| !rating.pending))
+        })?,
+    })
+}
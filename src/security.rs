@@ -1,7 +1,8 @@
-use cosmwasm_std::{Addr, DepsMut, Env, Timestamp, Uint128};
+use cosmwasm_std::{Addr, DepsMut, Env, ReplyOn, Response, Timestamp, Uint128};
 use cw_storage_plus::Map;
 use serde::{Deserialize, Serialize};
 
+use crate::contract_helpers::validate_text_chars;
 use crate::error::ContractError;
 
 // Security constants
@@ -17,7 +18,8 @@ const MAX_DOCUMENTS_COUNT: usize = 10;
 const MAX_MILESTONES_COUNT: usize = 10;
 
 // Rate limiting
-const MAX_JOBS_PER_USER_PER_DAY: u64 = 5;
+const RATE_LIMIT_WINDOW_SECONDS: u64 = 86_400;
+pub const MAX_JOBS_PER_USER_PER_DAY: u64 = 5;
 const MAX_PROPOSALS_PER_USER_PER_DAY: u64 = 20;
 const MAX_BOUNTIES_PER_USER_PER_DAY: u64 = 3;
 const MAX_DISPUTES_PER_USER_PER_DAY: u64 = 2;
@@ -35,23 +37,97 @@ pub struct RateLimit {
     pub last_reset: Timestamp,
 }
 
+impl RateLimit {
+    pub fn new(now: Timestamp) -> Self {
+        RateLimit {
+            daily_jobs: 0,
+            daily_proposals: 0,
+            daily_bounties: 0,
+            daily_disputes: 0,
+            daily_escrows: 0,
+            daily_admin_actions: 0,
+            last_reset: now,
+        }
+    }
+
+    /// Every counter as (name, count, daily limit), in the order check_rate_limit enforces them
+    pub fn counters(&self) -> [(&'static str, u64, u64); 6] {
+        [
+            ("jobs", self.daily_jobs, MAX_JOBS_PER_USER_PER_DAY),
+            ("proposals", self.daily_proposals, MAX_PROPOSALS_PER_USER_PER_DAY),
+            ("bounties", self.daily_bounties, MAX_BOUNTIES_PER_USER_PER_DAY),
+            ("disputes", self.daily_disputes, MAX_DISPUTES_PER_USER_PER_DAY),
+            ("escrows", self.daily_escrows, MAX_ESCROWS_PER_USER_PER_DAY),
+            ("admin_actions", self.daily_admin_actions, MAX_ADMIN_ACTIONS_PER_DAY),
+        ]
+    }
+
+    /// Zero every counter once a full window has passed since `last_reset`
+    pub fn reset_if_expired(&mut self, now: Timestamp) {
+        if now.seconds() >= self.last_reset.seconds().saturating_add(RATE_LIMIT_WINDOW_SECONDS) {
+            *self = RateLimit::new(now);
+        }
+    }
+}
+
 pub const USER_RATE_LIMITS: Map<&Addr, RateLimit> = Map::new("user_rate_limits");
 pub const REENTRANCY_GUARDS: Map<&Addr, bool> = Map::new("reentrancy_guards");
 
+/// Storage key for the contract-wide reentrancy lock
+fn reentrancy_guard_key() -> Addr {
+    Addr::unchecked("global")
+}
+
 /// Reentrancy guard to prevent reentrancy attacks
-/// Note: Basic implementation - can be enhanced for production use
-pub fn reentrancy_guard(_deps: DepsMut) -> Result<(), ContractError> {
-    // Basic reentrancy protection - currently allows normal flow
-    // In production, implement per-transaction guards with proper cleanup
+/// Rejects entry while an earlier `execute`'s messages still hold the lock
+pub fn reentrancy_guard(deps: DepsMut) -> Result<(), ContractError> {
+    let locked = REENTRANCY_GUARDS
+        .may_load(deps.storage, &reentrancy_guard_key())?
+        .unwrap_or(false);
+    if locked {
+        return Err(ContractError::ReentrancyDetected {});
+    }
+    Ok(())
+}
+
+/// Acquire reentrancy guard until `reply` releases it
+pub fn acquire_reentrancy_guard(mut deps: DepsMut) -> Result<(), ContractError> {
+    reentrancy_guard(deps.branch())?;
+    REENTRANCY_GUARDS.save(deps.storage, &reentrancy_guard_key(), &true)?;
     Ok(())
 }
 
 /// Release reentrancy guard
 pub fn release_reentrancy_guard(deps: DepsMut) -> Result<(), ContractError> {
-    REENTRANCY_GUARDS.save(deps.storage, &deps.api.addr_validate("global")?, &false)?;
+    REENTRANCY_GUARDS.save(deps.storage, &reentrancy_guard_key(), &false)?;
     Ok(())
 }
 
+/// Reply id carried by the last outgoing message of a guarded `execute`
+pub const REENTRANCY_RELEASE_REPLY_ID: u64 = 1;
+
+/// Run an `execute` handler and hold the reentrancy lock while its messages run
+///
+/// The contract can only be re-entered through the messages a handler returns, and
+/// those run after `execute` itself has returned. So when the response carries
+/// messages the lock is set on the way out, the last message asks for a reply, and
+/// `reply` clears the lock once it succeeds. Any `execute` those messages trigger in
+/// the meantime fails with `ReentrancyDetected`. If a message fails, the whole
+/// transaction reverts, lock included.
+pub fn with_reentrancy_guard(
+    mut deps: DepsMut,
+    body: impl FnOnce(DepsMut) -> Result<Response, ContractError>,
+) -> Result<Response, ContractError> {
+    reentrancy_guard(deps.branch())?;
+    let mut response = body(deps.branch())?;
+    if let Some(last) = response.messages.last_mut() {
+        last.id = REENTRANCY_RELEASE_REPLY_ID;
+        last.reply_on = ReplyOn::Success;
+        acquire_reentrancy_guard(deps)?;
+    }
+    Ok(response)
+}
+
 /// Validate platform fee percentage
 pub fn validate_platform_fee(fee_percent: u64) -> Result<(), ContractError> {
     if fee_percent > MAX_PLATFORM_FEE_PERCENT {
@@ -107,6 +183,9 @@ pub fn validate_text_inputs(
         });
     }
 
+    validate_text_chars(title, "Title", false)?;
+    validate_text_chars(description, "Description", true)?;
+
     if let Some(letter) = cover_letter {
         if letter.is_empty() || letter.len() > MAX_COVER_LETTER_LENGTH {
             return Err(ContractError::InvalidInput {
@@ -116,9 +195,11 @@ pub fn validate_text_inputs(
                 ),
             });
         }
+        validate_text_chars(letter, "Cover letter", true)?;
     }
 
     if let Some(c) = comment {
+        validate_text_chars(c, "Comment", true)?;
         if c.len() > MAX_COMMENT_LENGTH {
             return Err(ContractError::InvalidInput {
                 error: format!(
@@ -169,26 +250,10 @@ pub fn check_rate_limit(
     let current_time = env.block.time;
     let mut rate_limit = USER_RATE_LIMITS
         .may_load(deps.storage, user)?
-        .unwrap_or(RateLimit {
-            daily_jobs: 0,
-            daily_proposals: 0,
-            daily_bounties: 0,
-            daily_disputes: 0,
-            daily_escrows: 0,
-            daily_admin_actions: 0,
-            last_reset: current_time,
-        });
+        .unwrap_or_else(|| RateLimit::new(current_time));
 
     // Reset counters if it's a new day
-    if current_time.seconds() >= rate_limit.last_reset.seconds() + 86_400 {
-        rate_limit.daily_jobs = 0;
-        rate_limit.daily_proposals = 0;
-        rate_limit.daily_bounties = 0;
-        rate_limit.daily_disputes = 0;
-        rate_limit.daily_escrows = 0;
-        rate_limit.daily_admin_actions = 0;
-        rate_limit.last_reset = current_time;
-    }
+    rate_limit.reset_if_expired(current_time);
 
     // Check limits
     match action {
@@ -259,6 +324,7 @@ pub fn check_rate_limit(
         | RateLimitAction::ApproveMilestone
         | RateLimitAction::EditBounty 
         | RateLimitAction::CancelBounty 
+        | RateLimitAction::ExpireBounty
         | RateLimitAction::SubmitToBounty 
         | RateLimitAction::EditBountySubmission
         | RateLimitAction::WithdrawBountySubmission
@@ -297,6 +363,7 @@ pub enum RateLimitAction {
     CreateBounty,
     EditBounty,
     CancelBounty,
+    ExpireBounty,
     SubmitToBounty,
     EditBountySubmission,
     WithdrawBountySubmission,
@@ -1,23 +1,62 @@
 use crate::hash_utils::ContentHash;
-use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
+use cosmwasm_std::{Addr, Coin, Decimal, Timestamp, Uint128};
+use cw20::Denom;
 use cw_storage_plus::{Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+/// How a job's escrow is paid out once the job completes
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub enum ReleasePolicy {
+    #[default]
+    Auto,   // Completion releases the escrow to the freelancer
+    Manual, // Completion only closes the job; the poster calls ReleaseEscrow
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub enum JobStatus {
     Open,
     InProgress,
+    WorkSubmitted, // Freelancer delivered, awaiting client approval
     Completed,
     Cancelled,
     Disputed,
 }
 
+impl JobStatus {
+    pub const ALL: [JobStatus; 6] = [
+        JobStatus::Open,
+        JobStatus::InProgress,
+        JobStatus::WorkSubmitted,
+        JobStatus::Completed,
+        JobStatus::Cancelled,
+        JobStatus::Disputed,
+    ];
+
+    /// Stable snake_case code for event attributes, independent of serde casing
+    pub fn as_code(&self) -> &'static str {
+        match self {
+            JobStatus::Open => "open",
+            JobStatus::InProgress => "in_progress",
+            JobStatus::WorkSubmitted => "work_submitted",
+            JobStatus::Completed => "completed",
+            JobStatus::Cancelled => "cancelled",
+            JobStatus::Disputed => "disputed",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|status| status.as_code() == code)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub enum DisputeStatus {
     None,
     Raised,
     UnderReview,
+    ResolutionProposed, // Admin decision pending the appeal window
+    Appealed,           // A party contested the proposed decision
     Resolved,
 }
 
@@ -36,11 +75,39 @@ pub struct Job {
     pub deadline: Timestamp,               // Contract needs for deadline enforcement
     pub escrow_id: Option<String>,         // Contract needs for escrow management
     pub total_proposals: u64,              // Contract needs for proposal counting
+    pub category_id: u8,                   // Registry id for on-chain filtering
+    pub skill_tags: Vec<u8>,               // Registry skill ids for on-chain filtering
+    pub previous_freelancers: Vec<Addr>,   // Freelancers replaced through ReassignJob
+    pub work_submitted_at: Option<Timestamp>, // Starts the client review window
+    pub assigned_at: Option<Timestamp>,    // Starts the poster's edit grace period
+    pub edits_objected: bool,              // Assigned freelancer closed the edit grace period early
+    pub payout_address: Option<Addr>,      // Accepted proposal's payout override, else the freelancer is paid
+    pub referrer: Option<Addr>,            // Accepted proposal's referrer, paid out of the platform fee
+    pub referral_bps: u16,                 // Referrer's share of the platform fee
+    pub budget_range: u8,                  // JOBS_BY_BUDGET_RANGE tier, kept in step with budget
+    pub denom: Denom,                      // Native denom or CW20 token the budget is paid in; escrow funding must match it
+    pub release_policy: ReleasePolicy,     // Chosen at post time, see ReleasePolicy
 
     // 🌐 ALL CONTENT OFF-CHAIN (via content_hash)
     pub content_hash: ContentHash, // title, description, company, location, category, skills, documents, requirements, etc.
 }
 
+/// Listing defaults a poster reuses through `PostJobFromTemplate`; holds no funds
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct JobTemplate {
+    pub name: String,
+    pub title: String,
+    pub description: String,
+    pub category: String,
+    pub skills_required: Vec<String>,
+    pub budget: Uint128,
+    pub duration_days: u64,
+    pub experience_level: u8,
+    pub is_remote: bool,
+    pub urgency_level: u8,
+    pub updated_at: Timestamp,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Milestone {
     pub id: u64,
@@ -64,6 +131,12 @@ pub struct Proposal {
     pub agreed_to_terms: bool,   // Contract needs for agreement validation
     pub agreed_to_escrow: bool,  // Contract needs for escrow validation
     pub submitted_at: Timestamp, // Contract needs for time-based logic
+    pub status: ProposalStatus,  // Contract needs for acceptance/rejection tracking
+    pub score: u8,               // 0-100 ranking heuristic, see job_management::proposal_score
+    pub payout_address: Option<Addr>, // Receives the freelancer's payouts instead of the freelancer
+    pub referrer: Option<Addr>,  // Receives referral_bps of the platform fee on release
+    pub referral_bps: u16,       // Bounded by job_management::MAX_REFERRAL_BPS
+    pub expires_at: Timestamp,   // Contract needs to refuse accepting stale proposals
 
     // 🌐 ALL CONTENT OFF-CHAIN (via content_hash)
     pub content_hash: ContentHash, // cover_letter, milestones, portfolio, proposal_score, estimated_hours, etc.
@@ -99,6 +172,26 @@ pub struct EscrowState {
     pub dispute_status: DisputeStatus,
     pub dispute_raised_at: Option<Timestamp>,
     pub dispute_deadline: Option<Timestamp>,
+    pub denom: Denom, // Allowed native denom or CW20 token the escrow was funded in, and pays out in
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum EscrowEventKind {
+    Funded,
+    ToppedUp,
+    Disputed,
+    Resolved,
+    Released,
+    Refunded,
+}
+
+/// One entry in an escrow's audit trail
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EscrowEvent {
+    pub kind: EscrowEventKind,
+    pub actor: Addr,
+    pub amount: Uint128, // Funds moved by this transition, zero for status-only changes
+    pub timestamp: Timestamp,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -108,7 +201,24 @@ pub struct Config {
     pub min_escrow_amount: Uint128,
     pub dispute_period_days: u64,   // Default 7 days
     pub max_job_duration_days: u64, // Default 365 days
+    pub denom: String,              // Default "uxion"
+    pub allowed_denoms: Vec<String>, // Native denoms accepted for escrows and bounties, always includes `denom`
+    pub allowed_cw20_tokens: Vec<Addr>, // CW20 tokens accepted for job escrows and bounty rewards, default none
+    pub treasury: Addr,             // Receives withdrawn platform fees, defaults to admin
+    pub dispute_timeout_release_to_freelancer: bool, // Timed-out disputes pay the freelancer instead of refunding the client
+    pub max_proposals_per_job: u64, // Default 100, capped at MAX_PROPOSALS_PER_JOB
+    pub job_archive_retention_days: u64, // Default 90, closed jobs become archivable after this
+    pub fee_split_bps: u16, // Share of the platform fee the poster pays on top of the budget, default 0
+    pub max_bounty_reward: Uint128, // Bounty rewards must lie in [min_escrow_amount, max_bounty_reward]
+    pub edit_grace_period_days: u64, // Default 2, posters may edit the content of an accepted job this long; 0 disables
+    pub proposal_validity_days: u64, // Default 30, minimum time a proposal stays acceptable
+    pub min_edit_interval_seconds: u64, // Default 60, cooldown between edits of the same job or bounty; 0 disables
+    pub dispute_deposit: Uint128, // Default 0, refundable deposit sent with RaiseDispute in the escrow denom
+    pub reputation_half_life_days: u64, // Default 180, age at which a rating counts half in GetUserReputation; 0 disables decay
+    pub dispute_cooldown_seconds: u64, // Default 0, minimum gap between disputes raised by the same user; 0 disables
+    pub max_open_jobs_per_user: u64, // Default 20, jobs a poster may have Open at once; 0 disables
     pub paused: bool,
+    pub escrows_frozen: bool, // Emergency stop for escrow payouts; posting and queries stay open
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -120,17 +230,19 @@ pub struct Rating {
     pub rating: u8, // 1-5 stars
     pub comment: String,
     pub created_at: Timestamp,
+    pub pending: bool, // Held out of UserStats until both parties rate or the reveal window lapses
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
 pub struct UserStats {
     pub total_jobs_posted: u64,
+    pub total_jobs_assigned: u64, // Jobs this user was hired for, the completion rate denominator
     pub total_jobs_completed: u64,
     pub total_earned: Uint128,
     pub total_spent: Uint128,
     pub average_rating: Decimal,
     pub total_ratings: u64,
-    pub completion_rate: Decimal,
+    pub completion_rate: Decimal, // total_jobs_completed / total_jobs_assigned
     // New field for UI display
     pub display_name: Option<String>, // Optional display name for freelancers
 }
@@ -146,6 +258,18 @@ pub struct Dispute {
     pub created_at: Timestamp,
     pub resolved_at: Option<Timestamp>,
     pub resolution: Option<String>,
+    pub proposed_release_to_freelancer: Option<bool>, // Set when the admin proposes a resolution
+    pub proposed_freelancer_bps: Option<u16>,         // Freelancer's share of a split resolution
+    pub appeal_deadline: Option<Timestamp>,           // Parties may appeal until this time
+    pub appealed_by: Option<Addr>,
+    pub deposit: Option<Coin>, // Refunded if the raiser wins, otherwise forfeited to the platform fees
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum AdminRole {
+    SuperAdmin,      // Everything, including config changes and role grants
+    Moderator,       // Blocking addresses, rate limits and listings
+    DisputeResolver, // Dispute resolution and refunds
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -173,6 +297,8 @@ pub enum BountySubmissionStatus {
     Rejected,
     Winner,
     Withdrawn,
+    BountyCancelled, // Bounty was withdrawn by the poster before a win
+    BountyExpired,   // Review window lapsed without any winners being selected
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
@@ -252,6 +378,31 @@ pub enum BountyStatus {
     Expired,
 }
 
+impl BountyStatus {
+    pub const ALL: [BountyStatus; 5] = [
+        BountyStatus::Open,
+        BountyStatus::InReview,
+        BountyStatus::Completed,
+        BountyStatus::Cancelled,
+        BountyStatus::Expired,
+    ];
+
+    /// Stable snake_case code for event attributes, independent of serde casing
+    pub fn as_code(&self) -> &'static str {
+        match self {
+            BountyStatus::Open => "open",
+            BountyStatus::InReview => "in_review",
+            BountyStatus::Completed => "completed",
+            BountyStatus::Cancelled => "cancelled",
+            BountyStatus::Expired => "expired",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|status| status.as_code() == code)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Bounty {
     pub id: u64,
@@ -259,6 +410,7 @@ pub struct Bounty {
 
     // 🔥 ESSENTIAL BUSINESS LOGIC DATA ONLY (KEPT ON-CHAIN)
     pub total_reward: Uint128, // Contract needs for escrow/payments
+    pub reward_denom: Denom,   // Contract needs for payouts (native denom or CW20 token)
     pub submission_deadline: Timestamp, // Contract needs for deadline enforcement
     pub review_period_days: u64, // Contract needs for review period enforcement
     pub max_winners: u64,      // Contract needs for winner selection logic
@@ -269,11 +421,21 @@ pub struct Bounty {
     pub total_submissions: u64, // Contract needs for submission counting
     pub selected_winners: Vec<u64>, // Contract needs for winner tracking
     pub escrow_id: Option<String>, // Contract needs for escrow management
+    pub is_featured: bool,     // Contract needs for featured listing index
+    pub winners_selected_at: Option<Timestamp>, // Rewards release review_period_days after this
+    pub contest: Option<BountyContest>, // Set while a submitter's challenge awaits admin review
 
     // 🌐 ALL CONTENT OFF-CHAIN (via content_hash)
     pub content_hash: ContentHash, // title, description, requirements, documents, skills, category, etc.
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BountyContest {
+    pub submitter: Addr,
+    pub reason: String,
+    pub contested_at: Timestamp,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct RewardTier {
     pub position: u64,   // 1st place, 2nd place, etc.
@@ -299,6 +461,15 @@ pub struct BountySubmission {
 
 // Enhanced storage keys with security features
 pub const JOBS: Map<u64, Job> = Map::new("jobs");
+pub const USER_JOBS: Map<&Addr, Vec<u64>> = Map::new("user_jobs"); // poster -> job_ids
+pub const ACTIVE_JOBS: Map<u64, bool> = Map::new("active_jobs"); // job_id -> marker while Open
+pub const OPEN_JOB_COUNT: Map<&Addr, u64> = Map::new("open_job_count"); // poster -> jobs with an ACTIVE_JOBS marker
+pub const ARCHIVED_JOBS: Map<u64, Job> = Map::new("archived_jobs"); // closed jobs moved out of JOBS
+pub const ARCHIVED_JOB_COUNT: Item<u64> = Item::new("archived_job_count");
+pub const ARCHIVED_COMPLETED_JOB_COUNT: Item<u64> = Item::new("archived_completed_job_count");
+pub const JOBS_BY_SKILL: Map<(u8, u64), bool> = Map::new("jobs_by_skill"); // (skill_id, job_id) -> marker
+pub const JOBS_BY_BUDGET_RANGE: Map<(u8, u64), bool> = Map::new("jobs_by_budget_range"); // (budget_range, job_id) -> marker
+pub const JOB_TEMPLATES: Map<(&Addr, &str), JobTemplate> = Map::new("job_templates"); // (poster, name) -> template
 pub const PROPOSALS: Map<u64, Proposal> = Map::new("proposals");
 pub const JOB_PROPOSALS: Map<u64, Vec<u64>> = Map::new("job_proposals"); // job_id -> proposal_ids
 pub const USER_PROPOSALS: Map<&Addr, Vec<u64>> = Map::new("user_proposals"); // user -> proposal_ids
@@ -306,25 +477,35 @@ pub const USER_JOB_PROPOSALS: Map<(&Addr, u64), u64> = Map::new("user_job_propos
 pub const JOB_COUNTER: Item<u64> = Item::new("job_counter");
 pub const PROPOSAL_COUNTER: Item<u64> = Item::new("proposal_counter");
 pub const ESCROWS: Map<&str, EscrowState> = Map::new("escrows");
+pub const ESCROW_EVENTS: Map<(&str, u64), EscrowEvent> = Map::new("escrow_events"); // (escrow_id, seq) -> event, append-only
+pub const ACCRUED_FEES: Map<&str, Uint128> = Map::new("accrued_fees_by_denom"); // native denom or CW20 address -> fees earned but not yet withdrawn
 
 // 🎯 HASH & OFF-CHAIN DATA MANAGEMENT
 pub const CONTENT_HASHES: Map<&str, ContentHash> = Map::new("content_hashes"); // hash -> metadata
 pub const HASH_TO_ENTITY: Map<&str, String> = Map::new("hash_to_entity"); // hash -> entity_id
 pub const ENTITY_TO_HASH: Map<&str, String> = Map::new("entity_to_hash"); // entity_id -> current_hash
+pub const LAST_EDIT: Map<&str, Timestamp> = Map::new("last_edit"); // entity_id -> time of its last edit
 
 // Bounty storage
 pub const BOUNTIES: Map<u64, Bounty> = Map::new("bounties");
+pub const ACTIVE_BOUNTIES: Map<u64, bool> = Map::new("active_bounties"); // bounty_id -> marker while Open
 pub const BOUNTY_SUBMISSIONS: Map<u64, BountySubmission> = Map::new("bounty_submissions");
 pub const BOUNTY_SUBMISSIONS_BY_BOUNTY: Map<u64, Vec<u64>> =
     Map::new("bounty_submissions_by_bounty");
 pub const USER_BOUNTY_SUBMISSIONS: Map<&Addr, Vec<u64>> = Map::new("user_bounty_submissions");
 pub const BOUNTY_COUNTER: Item<u64> = Item::new("bounty_counter");
 pub const BOUNTY_SUBMISSION_COUNTER: Item<u64> = Item::new("bounty_submission_counter");
+pub const FEATURED_BOUNTIES: Map<u64, bool> = Map::new("featured_bounties"); // bounty_id -> featured marker
 
 pub const CONFIG: Item<Config> = Item::new("config");
+pub const ADMINS: Map<&Addr, AdminRole> = Map::new("admins"); // granted roles; config.admin is always super admin
+pub const PENDING_ADMIN: Item<Addr> = Item::new("pending_admin"); // proposed config.admin awaiting AcceptAdmin
 pub const RATINGS: Map<&str, Rating> = Map::new("ratings"); // job_id_rater_address
+pub const USER_RATINGS: Map<&Addr, Vec<String>> = Map::new("user_ratings"); // rated user -> rating keys
 pub const USER_STATS: Map<&Addr, UserStats> = Map::new("user_stats");
 pub const DISPUTES: Map<&str, Dispute> = Map::new("disputes");
+pub const LAST_DISPUTE: Map<&Addr, Timestamp> = Map::new("last_dispute"); // user -> when they last raised a dispute
+pub const DISPUTES_BY_STATUS: Map<(u8, &str), bool> = Map::new("disputes_by_status"); // (status key, dispute_id) -> marker
 
 // Missing ID counters
 pub const NEXT_JOB_ID: Item<u64> = Item::new("next_job_id");
@@ -332,9 +513,12 @@ pub const NEXT_PROPOSAL_ID: Item<u64> = Item::new("next_proposal_id");
 pub const NEXT_ESCROW_ID: Item<u64> = Item::new("next_escrow_id");
 pub const NEXT_BOUNTY_ID: Item<u64> = Item::new("next_bounty_id");
 pub const NEXT_BOUNTY_SUBMISSION_ID: Item<u64> = Item::new("next_bounty_submission_id");
+pub const REMOVED_JOB_COUNT: Item<u64> = Item::new("removed_job_count"); // deleted jobs, subtracted from NEXT_JOB_ID
+pub const REMOVED_PROPOSAL_COUNT: Item<u64> = Item::new("removed_proposal_count"); // withdrawn proposals, subtracted from NEXT_PROPOSAL_ID
 
 // User profiles storage
 pub const USER_PROFILES: Map<&Addr, UserProfile> = Map::new("user_profiles");
+pub const VERIFIED_USERS: Map<&Addr, Timestamp> = Map::new("verified_users"); // user -> verified_at
 
 // Security-related storage
 pub const SECURITY_METRICS: Item<SecurityMetrics> = Item::new("security_metrics");
@@ -342,3 +526,26 @@ pub const RATE_LIMITS: Map<(&Addr, &str), RateLimitState> = Map::new("rate_limit
 pub const AUDIT_LOGS: Map<&str, AuditLog> = Map::new("audit_logs");
 pub const REENTRANCY_GUARDS: Map<&Addr, bool> = Map::new("reentrancy_guards");
 pub const BLOCKED_ADDRESSES: Map<&Addr, Timestamp> = Map::new("blocked_addresses");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn job_status_codes_round_trip() {
+        for status in JobStatus::ALL {
+            assert_eq!(JobStatus::from_code(status.as_code()), Some(status));
+        }
+        assert_eq!(JobStatus::InProgress.as_code(), "in_progress");
+        assert_eq!(JobStatus::from_code("InProgress"), None);
+    }
+
+    #[test]
+    fn bounty_status_codes_round_trip() {
+        for status in BountyStatus::ALL {
+            assert_eq!(BountyStatus::from_code(status.as_code()), Some(status));
+        }
+        assert_eq!(BountyStatus::InReview.as_code(), "in_review");
+        assert_eq!(BountyStatus::from_code("unknown"), None);
+    }
+}
@@ -1,17 +1,32 @@
 use crate::contract_helpers::*;
 use crate::error::ContractError;
 use crate::hash_utils::{create_content_hash, create_user_profile_bundle};
-use crate::helpers::ensure_not_paused;
-use crate::msg::{RatingsResponse, UserProfileResponse, UserStatsResponse};
+use crate::helpers::{ensure_not_paused, increment_counter, record_job_completion};
+use crate::msg::{RatingsResponse, UserProfileResponse, UserStatsResponse, VerifiedUsersResponse};
 use crate::security::{check_rate_limit, reentrancy_guard, RateLimitAction};
 use crate::state::{
-    Rating, UserProfile, UserStats, CONTENT_HASHES, ENTITY_TO_HASH, HASH_TO_ENTITY, JOBS, RATINGS,
-    USER_PROFILES, USER_STATS,
+    AdminRole, Rating, UserProfile, UserStats, CONTENT_HASHES, ENTITY_TO_HASH, HASH_TO_ENTITY,
+    JOBS, RATINGS, USER_PROFILES, USER_STATS, VERIFIED_USERS,
 };
-use crate::{apply_security_checks, build_success_response, validate_content_inputs};
+use crate::{apply_security_checks, build_success_response, ensure_role, validate_content_inputs};
 use cosmwasm_std::{
     Addr, Decimal, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Uint128,
 };
+use cw_storage_plus::Bound;
+
+/// Empty profile for a user seen for the first time
+fn new_user_profile(env: &Env) -> UserProfile {
+    UserProfile {
+        created_at: env.block.time,
+        updated_at: env.block.time,
+        content_hash: create_content_hash("", "user_profile", env.block.time.seconds()).unwrap(),
+        total_jobs_completed: 0,
+        average_rating: Decimal::zero(),
+        total_earned: Uint128::zero(),
+        is_verified: false,
+        response_time_hours: 24,
+    }
+}
 
 /// 🎯 Update user profile with hybrid on-chain/off-chain storage
 #[allow(clippy::too_many_arguments)]
@@ -35,17 +50,7 @@ pub fn execute_update_user_profile(
     // 📋 Load or create user profile
     let mut profile = USER_PROFILES
         .may_load(deps.storage, &info.sender)?
-        .unwrap_or_else(|| UserProfile {
-            created_at: env.block.time,
-            updated_at: env.block.time,
-            content_hash: create_content_hash("", "user_profile", env.block.time.seconds())
-                .unwrap(),
-            total_jobs_completed: 0,
-            average_rating: Decimal::zero(),
-            total_earned: Uint128::zero(),
-            is_verified: false,
-            response_time_hours: 24,
-        });
+        .unwrap_or_else(|| new_user_profile(&env));
 
     // 🔍 Validate inputs if provided
     if let Some(ref name) = display_name {
@@ -58,7 +63,7 @@ pub fn execute_update_user_profile(
     }
 
     if let Some(ref bio_text) = bio {
-        validate_content_inputs!(bio_text, bio_text);
+        validate_string_field(bio_text, "Bio", 1, 200)?;
         if bio_text.len() > 1000 {
             return Err(ContractError::InvalidInput {
                 error: "Bio too long".to_string(),
@@ -116,6 +121,43 @@ pub fn execute_update_user_profile(
         "off_chain_key" => off_chain_storage_key
     ))
 }
+
+/// Mark a user as verified or revoke verification (moderators only)
+pub fn execute_set_user_verified(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+    verified: bool,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.as_ref())?;
+    ensure_role!(deps, info, AdminRole::Moderator);
+
+    let user = deps.api.addr_validate(&address)?;
+
+    let mut profile = USER_PROFILES
+        .may_load(deps.storage, &user)?
+        .unwrap_or_else(|| new_user_profile(&env));
+    profile.is_verified = verified;
+    profile.updated_at = env.block.time;
+    USER_PROFILES.save(deps.storage, &user, &profile)?;
+
+    // Keep the verified index in sync with the profile flag
+    if verified {
+        VERIFIED_USERS.save(deps.storage, &user, &env.block.time)?;
+    } else {
+        VERIFIED_USERS.remove(deps.storage, &user);
+    }
+
+    Ok(build_success_response!(
+        "set_user_verified",
+        0u64,
+        &info.sender,
+        "address" => user,
+        "verified" => verified
+    ))
+}
+
 /// Submit a rating for a user
 pub fn execute_submit_rating(
     mut deps: DepsMut,
@@ -188,6 +230,7 @@ pub fn execute_submit_rating(
         })?,
         comment: comment.unwrap_or_default(),
         created_at: env.block.time,
+        pending: false,
     };
 
     RATINGS.save(deps.storage, &rating_key, &new_rating)?;
@@ -197,6 +240,7 @@ pub fn execute_submit_rating(
         .may_load(deps.storage, &rated_user_addr)?
         .unwrap_or_else(|| UserStats {
             total_jobs_posted: 0,
+            total_jobs_assigned: 0,
             total_jobs_completed: 0,
             total_earned: Uint128::zero(),
             total_spent: Uint128::zero(),
@@ -243,9 +287,30 @@ pub fn query_user_stats(deps: Deps, user: String) -> StdResult<UserStatsResponse
     let stats = USER_STATS.may_load(deps.storage, &user_addr)?;
     Ok(UserStatsResponse {
         stats: stats.unwrap_or_default(),
+        is_verified: VERIFIED_USERS.has(deps.storage, &user_addr),
     })
 }
 
+/// Query verified users
+pub fn query_verified_users(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<VerifiedUsersResponse> {
+    let limit = limit.unwrap_or(50).min(100) as usize;
+    let start_addr = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let start = start_addr.as_ref().map(Bound::exclusive);
+
+    let users: StdResult<Vec<Addr>> = VERIFIED_USERS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect();
+
+    Ok(VerifiedUsersResponse { users: users? })
+}
+
 /// Query user ratings
 pub fn query_user_ratings(deps: Deps, user: String) -> StdResult<RatingsResponse> {
     let user_addr = deps.api.addr_validate(&user)?;
@@ -288,6 +353,7 @@ pub fn update_user_job_stats(
         .may_load(deps.storage, user)?
         .unwrap_or_else(|| UserStats {
             total_jobs_posted: 0,
+            total_jobs_assigned: 0,
             total_jobs_completed: 0,
             total_earned: Uint128::zero(),
             total_spent: Uint128::zero(),
@@ -298,15 +364,15 @@ pub fn update_user_job_stats(
         });
 
     if job_completed {
-        stats.total_jobs_completed += 1;
+        record_job_completion(&mut stats)?;
     }
 
     if let Some(earned) = amount_earned {
-        stats.total_earned += earned;
+        stats.total_earned = stats.total_earned.checked_add(earned)?;
     }
 
     if let Some(spent) = amount_spent {
-        stats.total_spent += spent;
+        stats.total_spent = stats.total_spent.checked_add(spent)?;
     }
 
     USER_STATS.save(deps.storage, user, &stats)?;
@@ -326,6 +392,7 @@ pub fn update_user_bounty_stats(
         .may_load(deps.storage, user)?
         .unwrap_or_else(|| UserStats {
             total_jobs_posted: 0,
+            total_jobs_assigned: 0,
             total_jobs_completed: 0,
             total_earned: Uint128::zero(),
             total_spent: Uint128::zero(),
@@ -339,20 +406,20 @@ pub fn update_user_bounty_stats(
     // These would need to be tracked separately if needed
     if bounty_created {
         // Could increment total_jobs_posted as a generic "items posted" counter
-        stats.total_jobs_posted += 1;
+        stats.total_jobs_posted = increment_counter(stats.total_jobs_posted)?;
     }
 
     if bounty_won {
         // Could increment total_jobs_completed as a generic "items completed" counter
-        stats.total_jobs_completed += 1;
+        stats.total_jobs_completed = increment_counter(stats.total_jobs_completed)?;
     }
 
     if let Some(earned) = amount_earned {
-        stats.total_earned += earned;
+        stats.total_earned = stats.total_earned.checked_add(earned)?;
     }
 
     if let Some(spent) = amount_spent {
-        stats.total_spent += spent;
+        stats.total_spent = stats.total_spent.checked_add(spent)?;
     }
 
     USER_STATS.save(deps.storage, user, &stats)?;
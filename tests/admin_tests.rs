@@ -0,0 +1,266 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{coins, Env};
+use xworks_freelance_contract::msg::{ConfigResponse, ExecuteMsg, JobResponse, QueryMsg};
+use xworks_freelance_contract::state::AdminRole;
+use xworks_freelance_contract::ContractError;
+
+const MODERATOR: &str = "moderator";
+const RESOLVER: &str = "resolver";
+
+fn grant(deps: &mut TestDeps, env: &Env, sender: &str, address: &str, role: AdminRole) {
+    exec(
+        deps,
+        env,
+        sender,
+        &[],
+        ExecuteMsg::GrantRole {
+            address: address.to_string(),
+            role,
+        },
+    )
+    .unwrap();
+}
+
+fn block_msg(address: &str) -> ExecuteMsg {
+    ExecuteMsg::BlockAddress {
+        address: address.to_string(),
+        reason: "Spam".to_string(),
+    }
+}
+
+fn update_fee_msg() -> ExecuteMsg {
+    ExecuteMsg::UpdateConfig {
+        platform_fee_percent: Some(7),
+        min_escrow_amount: None,
+        dispute_period_days: None,
+        max_job_duration_days: None,
+        treasury: None,
+        dispute_timeout_release_to_freelancer: None,
+        max_proposals_per_job: None,
+        job_archive_retention_days: None,
+        fee_split_bps: None,
+        max_bounty_reward: None,
+        edit_grace_period_days: None,
+        proposal_validity_days: None,
+        min_edit_interval_seconds: None,
+        dispute_deposit: None,
+        reputation_half_life_days: None,
+        dispute_cooldown_seconds: None,
+        max_open_jobs_per_user: None,
+    }
+}
+
+#[test]
+fn moderator_can_block_but_not_update_config() {
+    let (mut deps, env) = setup_contract();
+    grant(&mut deps, &env, ADMIN, MODERATOR, AdminRole::Moderator);
+
+    exec(&mut deps, &env, MODERATOR, &[], block_msg("spammer")).unwrap();
+    exec(
+        &mut deps,
+        &env,
+        MODERATOR,
+        &[],
+        ExecuteMsg::UnblockAddress {
+            address: "spammer".to_string(),
+        },
+    )
+    .unwrap();
+
+    let err = exec(&mut deps, &env, MODERATOR, &[], update_fee_msg()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = exec(
+        &mut deps,
+        &env,
+        MODERATOR,
+        &[],
+        ExecuteMsg::PauseContract {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn dispute_resolver_cannot_moderate() {
+    let (mut deps, env) = setup_contract();
+    grant(&mut deps, &env, ADMIN, RESOLVER, AdminRole::DisputeResolver);
+
+    let err = exec(&mut deps, &env, RESOLVER, &[], block_msg("spammer")).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn granted_super_admin_has_every_role() {
+    let (mut deps, env) = setup_contract();
+    grant(&mut deps, &env, ADMIN, "superadmin", AdminRole::SuperAdmin);
+
+    exec(&mut deps, &env, "superadmin", &[], block_msg("spammer")).unwrap();
+    exec(&mut deps, &env, "superadmin", &[], update_fee_msg()).unwrap();
+    grant(
+        &mut deps,
+        &env,
+        "superadmin",
+        MODERATOR,
+        AdminRole::Moderator,
+    );
+}
+
+#[test]
+fn only_super_admins_manage_roles() {
+    let (mut deps, env) = setup_contract();
+    grant(&mut deps, &env, ADMIN, MODERATOR, AdminRole::Moderator);
+
+    let err = exec(
+        &mut deps,
+        &env,
+        MODERATOR,
+        &[],
+        ExecuteMsg::GrantRole {
+            address: "friend".to_string(),
+            role: AdminRole::SuperAdmin,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    exec(
+        &mut deps,
+        &env,
+        ADMIN,
+        &[],
+        ExecuteMsg::RevokeRole {
+            address: MODERATOR.to_string(),
+        },
+    )
+    .unwrap();
+
+    let err = exec(&mut deps, &env, MODERATOR, &[], block_msg("spammer")).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+fn config_admin(deps: &TestDeps, env: &Env) -> String {
+    query_as::<ConfigResponse>(deps, env, QueryMsg::GetConfig {})
+        .config
+        .admin
+        .to_string()
+}
+
+#[test]
+fn admin_transfer_takes_effect_once_accepted() {
+    let (mut deps, env) = setup_contract();
+    let propose = |new_admin: &str| ExecuteMsg::ProposeAdmin {
+        new_admin: new_admin.to_string(),
+    };
+
+    // Granted super admins can't hand over the contract admin
+    grant(&mut deps, &env, ADMIN, "deputy", AdminRole::SuperAdmin);
+    let err = exec(&mut deps, &env, "deputy", &[], propose("deputy")).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    exec(&mut deps, &env, ADMIN, &[], propose("newadmin")).unwrap();
+    assert_eq!(config_admin(&deps, &env), ADMIN);
+
+    // The old admin keeps control until the transfer is accepted
+    exec(&mut deps, &env, ADMIN, &[], update_fee_msg()).unwrap();
+
+    let res = exec(&mut deps, &env, "newadmin", &[], ExecuteMsg::AcceptAdmin {}).unwrap();
+    assert_eq!(attr_value(&res, "previous_admin").as_deref(), Some(ADMIN));
+    assert_eq!(config_admin(&deps, &env), "newadmin");
+
+    let err = exec(&mut deps, &env, ADMIN, &[], update_fee_msg()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    exec(&mut deps, &env, "newadmin", &[], update_fee_msg()).unwrap();
+
+    // The proposal is consumed by the transfer
+    let err = exec(&mut deps, &env, "newadmin", &[], ExecuteMsg::AcceptAdmin {}).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn only_the_proposed_admin_can_accept() {
+    let (mut deps, env) = setup_contract();
+
+    // Nothing to accept before a proposal
+    let err = exec(&mut deps, &env, ADMIN, &[], ExecuteMsg::AcceptAdmin {}).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    exec(
+        &mut deps,
+        &env,
+        ADMIN,
+        &[],
+        ExecuteMsg::ProposeAdmin {
+            new_admin: "newadmin".to_string(),
+        },
+    )
+    .unwrap();
+    for sender in [ADMIN, CLIENT, "newadmim"] {
+        let err = exec(&mut deps, &env, sender, &[], ExecuteMsg::AcceptAdmin {}).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+    assert_eq!(config_admin(&deps, &env), ADMIN);
+}
+
+#[test]
+fn frozen_escrows_block_payouts_but_not_posting() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    exec(
+        &mut deps,
+        &env,
+        FREELANCER,
+        &[],
+        ExecuteMsg::SubmitWork { job_id },
+    )
+    .unwrap();
+
+    let err = exec(&mut deps, &env, CLIENT, &[], ExecuteMsg::FreezeEscrows {}).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    exec(&mut deps, &env, ADMIN, &[], ExecuteMsg::FreezeEscrows {}).unwrap();
+
+    let approve = ExecuteMsg::ApproveCompletion { job_id };
+    let err = exec(&mut deps, &env, CLIENT, &[], approve.clone()).unwrap_err();
+    assert_eq!(err, ContractError::EscrowsFrozen {});
+
+    let bounty_id = create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::CancelBounty { bounty_id },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::EscrowsFrozen {});
+
+    // Posting and browsing carry on while payouts are frozen
+    let new_job = post_job(&mut deps, &env, CLIENT, 1000);
+    let job: JobResponse = query_as(&deps, &env, QueryMsg::GetJob { job_id: new_job });
+    assert!(job.job.is_some());
+
+    exec(&mut deps, &env, ADMIN, &[], ExecuteMsg::UnfreezeEscrows {}).unwrap();
+    let res = exec(&mut deps, &env, CLIENT, &[], approve).unwrap();
+    assert_eq!(res.messages.len(), 1);
+}
+
+#[test]
+fn paused_contract_rejects_job_posts_with_contract_paused() {
+    let (mut deps, env) = setup_contract();
+    exec(&mut deps, &env, ADMIN, &[], ExecuteMsg::PauseContract {}).unwrap();
+
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, DENOM),
+        post_job_msg(1000),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ContractPaused {});
+
+    exec(&mut deps, &env, ADMIN, &[], ExecuteMsg::UnpauseContract {}).unwrap();
+    post_job(&mut deps, &env, CLIENT, 1000);
+}
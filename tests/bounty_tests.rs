@@ -0,0 +1,1311 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{coins, to_json_binary, Addr, BankMsg, CosmosMsg, Uint128, WasmMsg};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, Denom};
+use xworks_freelance_contract::bounty_management::MAX_BOUNTY_PAYOUTS;
+use xworks_freelance_contract::msg::{
+    AccruedFeesResponse, BountiesResponse, BountyResponse, BountySubmissionResponse,
+    BountySubmissionsResponse, BountyWinnersResponse, EscrowResponse, ExecuteMsg, QueryMsg,
+    ReceiveMsg, RewardTierInput, WinnerSelection,
+};
+use xworks_freelance_contract::state::{BountyStatus, BountySubmissionStatus};
+use xworks_freelance_contract::ContractError;
+
+fn set_featured(
+    deps: &mut TestDeps,
+    env: &cosmwasm_std::Env,
+    sender: &str,
+    bounty_id: u64,
+    featured: bool,
+) -> Result<cosmwasm_std::Response, ContractError> {
+    exec(
+        deps,
+        env,
+        sender,
+        &[],
+        ExecuteMsg::SetBountyFeatured {
+            bounty_id,
+            featured,
+        },
+    )
+}
+
+fn featured_ids(deps: &TestDeps, env: &cosmwasm_std::Env) -> Vec<u64> {
+    let res: BountiesResponse = query_as(deps, env, QueryMsg::GetFeaturedBounties { limit: None });
+    res.bounties.iter().map(|b| b.id).collect()
+}
+
+#[test]
+fn admin_can_feature_and_unfeature_bounties() {
+    let (mut deps, env) = setup_contract();
+    let first = create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+    let second = create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+
+    assert!(featured_ids(&deps, &env).is_empty());
+
+    set_featured(&mut deps, &env, ADMIN, first, true).unwrap();
+    set_featured(&mut deps, &env, ADMIN, second, true).unwrap();
+    assert_eq!(featured_ids(&deps, &env), vec![second, first]);
+
+    let bounty: BountyResponse = query_as(&deps, &env, QueryMsg::GetBounty { bounty_id: first });
+    assert!(bounty.bounty.unwrap().is_featured);
+
+    set_featured(&mut deps, &env, ADMIN, first, false).unwrap();
+    assert_eq!(featured_ids(&deps, &env), vec![second]);
+
+    let bounty: BountyResponse = query_as(&deps, &env, QueryMsg::GetBounty { bounty_id: first });
+    assert!(!bounty.bounty.unwrap().is_featured);
+
+    let limited: BountiesResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetFeaturedBounties { limit: Some(0) },
+    );
+    assert!(limited.bounties.is_empty());
+}
+
+#[test]
+fn only_admin_can_feature_bounties() {
+    let (mut deps, env) = setup_contract();
+    let bounty_id = create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+
+    let err = set_featured(&mut deps, &env, CLIENT, bounty_id, true).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    assert!(featured_ids(&deps, &env).is_empty());
+}
+
+#[test]
+fn cancelling_bounty_flags_submissions_and_refunds_in_config_denom() {
+    let (mut deps, env) = setup_contract();
+    let bounty_id = create_bounty(&mut deps, &env, CLIENT, 1000, &[60, 40]);
+    let active = submit_to_bounty(&mut deps, &env, FREELANCER, bounty_id);
+    let withdrawn = submit_to_bounty(&mut deps, &env, "designer", bounty_id);
+
+    exec(
+        &mut deps,
+        &env,
+        "designer",
+        &[],
+        ExecuteMsg::WithdrawBountySubmission {
+            submission_id: withdrawn,
+        },
+    )
+    .unwrap();
+
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::CancelBounty { bounty_id },
+    )
+    .unwrap();
+    assert_eq!(
+        attr_value(&res, "cancelled_submissions"),
+        Some("1".to_string())
+    );
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: CLIENT.to_string(),
+            amount: coins(1000, DENOM),
+        })
+    );
+
+    let status = |deps: &TestDeps, submission_id| {
+        let res: BountySubmissionResponse =
+            query_as(deps, &env, QueryMsg::GetBountySubmission { submission_id });
+        res.submission.status
+    };
+    assert_eq!(
+        status(&deps, active),
+        BountySubmissionStatus::BountyCancelled
+    );
+    assert_eq!(status(&deps, withdrawn), BountySubmissionStatus::Withdrawn);
+
+    let bounty: BountyResponse = query_as(&deps, &env, QueryMsg::GetBounty { bounty_id });
+    assert_eq!(bounty.bounty.unwrap().status, BountyStatus::Cancelled);
+}
+
+fn edit_deadline(
+    deps: &mut TestDeps,
+    env: &cosmwasm_std::Env,
+    bounty_id: u64,
+    days: u64,
+) -> Result<cosmwasm_std::Response, ContractError> {
+    exec(
+        deps,
+        env,
+        CLIENT,
+        &[],
+        ExecuteMsg::EditBounty {
+            bounty_id,
+            title: None,
+            description: None,
+            requirements: None,
+            submission_deadline_days: Some(days),
+            review_period_days: None,
+            documents: None,
+        },
+    )
+}
+
+#[test]
+fn deadline_can_be_shortened_before_any_submissions() {
+    let (mut deps, env) = setup_contract();
+    let bounty_id = create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+
+    edit_deadline(&mut deps, &env, bounty_id, 2).unwrap();
+
+    let bounty: BountyResponse = query_as(&deps, &env, QueryMsg::GetBounty { bounty_id });
+    assert_eq!(
+        bounty.bounty.unwrap().submission_deadline,
+        env.block.time.plus_seconds(2 * 24 * 60 * 60)
+    );
+
+    // Zero days would expire the bounty immediately
+    let err = edit_deadline(&mut deps, &env, bounty_id, 0).unwrap_err();
+    assert_eq!(err, ContractError::InvalidDeadline {});
+}
+
+#[test]
+fn deadline_cannot_be_shortened_once_submissions_exist() {
+    let (mut deps, env) = setup_contract();
+    let bounty_id = create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+    submit_to_bounty(&mut deps, &env, FREELANCER, bounty_id);
+
+    let err = edit_deadline(&mut deps, &env, bounty_id, 2).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+
+    // Extending the window is still allowed
+    edit_deadline(&mut deps, &env, bounty_id, 14).unwrap();
+    let bounty: BountyResponse = query_as(&deps, &env, QueryMsg::GetBounty { bounty_id });
+    assert_eq!(
+        bounty.bounty.unwrap().submission_deadline,
+        env.block.time.plus_seconds(14 * 24 * 60 * 60)
+    );
+}
+
+fn select_winners(deps: &mut TestDeps, env: &cosmwasm_std::Env, bounty_id: u64, winners: &[u64]) {
+    exec(
+        deps,
+        env,
+        CLIENT,
+        &[],
+        ExecuteMsg::SelectBountyWinners {
+            bounty_id,
+            winner_submissions: winners
+                .iter()
+                .enumerate()
+                .map(|(i, submission_id)| WinnerSelection {
+                    submission_id: *submission_id,
+                    position: (i + 1) as u64,
+                })
+                .collect(),
+        },
+    )
+    .unwrap();
+}
+
+/// Rewards release once the 3 day review period after selection has passed
+fn after_review(env: &cosmwasm_std::Env) -> cosmwasm_std::Env {
+    let mut later = env.clone();
+    later.block.time = later.block.time.plus_seconds(3 * 24 * 60 * 60);
+    later
+}
+
+fn payout(to: &str, amount: u128) -> CosmosMsg {
+    CosmosMsg::Bank(BankMsg::Send {
+        to_address: to.to_string(),
+        amount: coins(amount, DENOM),
+    })
+}
+
+#[test]
+fn release_refunds_unclaimed_tiers_to_poster() {
+    let (mut deps, env) = setup_contract();
+    let bounty_id = create_bounty(&mut deps, &env, CLIENT, 1000, &[60, 40]);
+    let entry = submit_to_bounty(&mut deps, &env, FREELANCER, bounty_id);
+    select_winners(&mut deps, &env, bounty_id, &[entry]);
+
+    let res = exec(
+        &mut deps,
+        &after_review(&env),
+        CLIENT,
+        &[],
+        ExecuteMsg::ReleaseBountyRewards { bounty_id },
+    )
+    .unwrap();
+
+    // The awarded tier pays its 5% share of the fee; the unclaimed one is refunded whole
+    assert_eq!(attr_value(&res, "refunded"), Some("400".to_string()));
+    assert_eq!(attr_value(&res, "platform_fee"), Some("30".to_string()));
+    let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+    assert_eq!(msgs, vec![payout(FREELANCER, 570), payout(CLIENT, 400)]);
+}
+
+#[test]
+fn release_with_all_tiers_filled_has_no_refund() {
+    let (mut deps, env) = setup_contract();
+    let bounty_id = create_bounty(&mut deps, &env, CLIENT, 1000, &[60, 40]);
+    let first = submit_to_bounty(&mut deps, &env, FREELANCER, bounty_id);
+    let second = submit_to_bounty(&mut deps, &env, "designer", bounty_id);
+    select_winners(&mut deps, &env, bounty_id, &[first, second]);
+
+    let res = exec(
+        &mut deps,
+        &after_review(&env),
+        CLIENT,
+        &[],
+        ExecuteMsg::ReleaseBountyRewards { bounty_id },
+    )
+    .unwrap();
+
+    assert_eq!(attr_value(&res, "refunded"), Some("0".to_string()));
+    let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+    assert_eq!(msgs, vec![payout(FREELANCER, 570), payout("designer", 380)]);
+
+    // The escrow's whole fee is now waiting for the treasury
+    let fees: AccruedFeesResponse = query_as(&deps, &env, QueryMsg::GetAccruedFees { denom: None });
+    assert_eq!(fees.amount, Uint128::new(50));
+}
+
+fn contest(
+    deps: &mut TestDeps,
+    env: &cosmwasm_std::Env,
+    sender: &str,
+    bounty_id: u64,
+) -> Result<cosmwasm_std::Response, ContractError> {
+    exec(
+        deps,
+        env,
+        sender,
+        &[],
+        ExecuteMsg::ContestBountyResult {
+            bounty_id,
+            reason: "The winning entry copied mine".to_string(),
+        },
+    )
+}
+
+fn resolve_contest(
+    deps: &mut TestDeps,
+    env: &cosmwasm_std::Env,
+    sender: &str,
+    bounty_id: u64,
+    uphold_selection: bool,
+) -> Result<cosmwasm_std::Response, ContractError> {
+    exec(
+        deps,
+        env,
+        sender,
+        &[],
+        ExecuteMsg::ResolveBountyContest {
+            bounty_id,
+            uphold_selection,
+        },
+    )
+}
+
+fn release(
+    deps: &mut TestDeps,
+    env: &cosmwasm_std::Env,
+    bounty_id: u64,
+) -> Result<cosmwasm_std::Response, ContractError> {
+    exec(
+        deps,
+        env,
+        CLIENT,
+        &[],
+        ExecuteMsg::ReleaseBountyRewards { bounty_id },
+    )
+}
+
+#[test]
+fn winner_selection_holds_rewards_for_the_review_period() {
+    let (mut deps, env) = setup_contract();
+    let bounty_id = create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+    let entry = submit_to_bounty(&mut deps, &env, FREELANCER, bounty_id);
+
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::SelectBountyWinners {
+            bounty_id,
+            winner_submissions: vec![WinnerSelection {
+                submission_id: entry,
+                position: 1,
+            }],
+        },
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+
+    let mut almost = after_review(&env);
+    almost.block.time = almost.block.time.minus_seconds(1);
+    let err = release(&mut deps, &almost, bounty_id).unwrap_err();
+    assert_eq!(err, ContractError::DisputePeriodActive {});
+
+    let res = release(&mut deps, &after_review(&env), bounty_id).unwrap();
+    let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+    assert_eq!(msgs, vec![payout(FREELANCER, 950)]);
+
+    // Rewards are paid exactly once
+    let err = release(&mut deps, &after_review(&env), bounty_id).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+}
+
+#[test]
+fn winner_can_release_once_the_review_period_ends() {
+    let (mut deps, env) = setup_contract();
+    let bounty_id = create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+    let entry = submit_to_bounty(&mut deps, &env, FREELANCER, bounty_id);
+    select_winners(&mut deps, &env, bounty_id, &[entry]);
+
+    // The poster never releases; the winner still can't jump the review period
+    let release_as_winner = ExecuteMsg::ReleaseBountyRewards { bounty_id };
+    let err = exec(&mut deps, &env, FREELANCER, &[], release_as_winner.clone()).unwrap_err();
+    assert_eq!(err, ContractError::DisputePeriodActive {});
+
+    let res = exec(
+        &mut deps,
+        &after_review(&env),
+        FREELANCER,
+        &[],
+        release_as_winner,
+    )
+    .unwrap();
+    let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+    assert_eq!(msgs, vec![payout(FREELANCER, 950)]);
+}
+
+#[test]
+fn contested_result_waits_for_admin_override() {
+    let (mut deps, env) = setup_contract();
+    let bounty_id = create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+    let entry = submit_to_bounty(&mut deps, &env, FREELANCER, bounty_id);
+    submit_to_bounty(&mut deps, &env, "designer", bounty_id);
+    select_winners(&mut deps, &env, bounty_id, &[entry]);
+
+    // Only competitors can contest, and only once
+    let err = contest(&mut deps, &env, "outsider", bounty_id).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contest(&mut deps, &env, "designer", bounty_id).unwrap();
+    let err = contest(&mut deps, &env, FREELANCER, bounty_id).unwrap_err();
+    assert_eq!(err, ContractError::BountyContested {});
+
+    let bounty = query_as::<BountyResponse>(&deps, &env, QueryMsg::GetBounty { bounty_id })
+        .bounty
+        .unwrap();
+    assert_eq!(bounty.contest.unwrap().submitter.as_str(), "designer");
+
+    // The contest outlives the review period until an admin rules on it
+    let err = release(&mut deps, &after_review(&env), bounty_id).unwrap_err();
+    assert_eq!(err, ContractError::BountyContested {});
+    let err = resolve_contest(&mut deps, &env, CLIENT, bounty_id, true).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = resolve_contest(&mut deps, &env, ADMIN, bounty_id, true).unwrap();
+    assert_eq!(
+        attr_value(&res, "contested_by").as_deref(),
+        Some("designer")
+    );
+    let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+    assert_eq!(msgs, vec![payout(FREELANCER, 950)]);
+
+    let err = release(&mut deps, &after_review(&env), bounty_id).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+}
+
+#[test]
+fn overturned_result_reopens_winner_selection() {
+    let (mut deps, env) = setup_contract();
+    let bounty_id = create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+    let first = submit_to_bounty(&mut deps, &env, FREELANCER, bounty_id);
+    let second = submit_to_bounty(&mut deps, &env, "designer", bounty_id);
+    select_winners(&mut deps, &env, bounty_id, &[first]);
+
+    // Contests close with the review period
+    let err = contest(&mut deps, &after_review(&env), "designer", bounty_id).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+    contest(&mut deps, &env, "designer", bounty_id).unwrap();
+
+    let res = resolve_contest(&mut deps, &env, ADMIN, bounty_id, false).unwrap();
+    assert!(res.messages.is_empty());
+
+    let bounty = query_as::<BountyResponse>(&deps, &env, QueryMsg::GetBounty { bounty_id })
+        .bounty
+        .unwrap();
+    assert_eq!(bounty.status, BountyStatus::Open);
+    assert!(bounty.selected_winners.is_empty());
+    assert_eq!(bounty.contest, None);
+    let submission = query_as::<BountySubmissionResponse>(
+        &deps,
+        &env,
+        QueryMsg::GetBountySubmission {
+            submission_id: first,
+        },
+    )
+    .submission;
+    assert_eq!(submission.status, BountySubmissionStatus::UnderReview);
+    assert_eq!(submission.winner_position, None);
+
+    // The poster picks again and the new selection gets its own review period
+    let later = after_review(&env);
+    select_winners(&mut deps, &later, bounty_id, &[second]);
+    let res = release(&mut deps, &after_review(&later), bounty_id).unwrap();
+    let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+    assert_eq!(msgs, vec![payout("designer", 950)]);
+}
+
+const TOKEN: &str = "token";
+
+fn allow_cw20_tokens(deps: &mut TestDeps, env: &cosmwasm_std::Env, tokens: &[&str]) {
+    exec(
+        deps,
+        env,
+        ADMIN,
+        &[],
+        ExecuteMsg::SetAllowedCw20Tokens {
+            tokens: tokens.iter().map(|t| t.to_string()).collect(),
+        },
+    )
+    .unwrap();
+}
+
+fn create_cw20_bounty(deps: &mut TestDeps, env: &cosmwasm_std::Env, amount: u128) -> u64 {
+    allow_cw20_tokens(deps, env, &[TOKEN]);
+    let res = exec(deps, env, TOKEN, &[], cw20_bounty_msg(amount)).unwrap();
+    attr_value(&res, "id").unwrap().parse().unwrap()
+}
+
+fn cw20_bounty_msg(amount: u128) -> ExecuteMsg {
+    let hook = ReceiveMsg::CreateBounty {
+        title: "Design a logo".to_string(),
+        description: "Logo for the XWorks brand".to_string(),
+        requirements: vec!["SVG output".to_string()],
+        category: "Design".to_string(),
+        skills_required: vec!["design".to_string()],
+        submission_deadline_days: 7,
+        review_period_days: 3,
+        max_winners: 2,
+        reward_distribution: vec![
+            RewardTierInput {
+                position: 1,
+                percentage: 60,
+            },
+            RewardTierInput {
+                position: 2,
+                percentage: 40,
+            },
+        ],
+        documents: None,
+    };
+    ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: CLIENT.to_string(),
+        amount: Uint128::new(amount),
+        msg: to_json_binary(&hook).unwrap(),
+    })
+}
+
+fn cw20_payout(to: &str, amount: u128) -> CosmosMsg {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: TOKEN.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: to.to_string(),
+            amount: Uint128::new(amount),
+        })
+        .unwrap(),
+        funds: vec![],
+    })
+}
+
+#[test]
+fn cw20_bounty_pays_multiple_winners_in_token() {
+    let (mut deps, env) = setup_contract();
+    let bounty_id = create_cw20_bounty(&mut deps, &env, 1000);
+
+    let res: BountyResponse = query_as(&deps, &env, QueryMsg::GetBounty { bounty_id });
+    let bounty = res.bounty.unwrap();
+    assert_eq!(bounty.poster.as_str(), CLIENT);
+    assert_eq!(bounty.reward_denom, Denom::Cw20(Addr::unchecked(TOKEN)));
+
+    let first = submit_to_bounty(&mut deps, &env, FREELANCER, bounty_id);
+    let second = submit_to_bounty(&mut deps, &env, "designer", bounty_id);
+    select_winners(&mut deps, &env, bounty_id, &[first, second]);
+
+    let res = exec(
+        &mut deps,
+        &after_review(&env),
+        CLIENT,
+        &[],
+        ExecuteMsg::ReleaseBountyRewards { bounty_id },
+    )
+    .unwrap();
+
+    let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+    assert_eq!(
+        msgs,
+        vec![cw20_payout(FREELANCER, 570), cw20_payout("designer", 380)]
+    );
+}
+
+#[test]
+fn cw20_bounties_only_accept_whitelisted_tokens() {
+    let (mut deps, env) = setup_contract();
+
+    // No CW20 token is accepted until an admin whitelists it
+    let err = exec(&mut deps, &env, TOKEN, &[], cw20_bounty_msg(1000)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DenomNotAllowed {
+            denom: TOKEN.to_string()
+        }
+    );
+
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::SetAllowedCw20Tokens {
+            tokens: vec![TOKEN.to_string()],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    allow_cw20_tokens(&mut deps, &env, &["othertoken"]);
+    let err = exec(&mut deps, &env, TOKEN, &[], cw20_bounty_msg(1000)).unwrap_err();
+    assert!(matches!(err, ContractError::DenomNotAllowed { .. }));
+
+    allow_cw20_tokens(&mut deps, &env, &["othertoken", TOKEN]);
+    exec(&mut deps, &env, TOKEN, &[], cw20_bounty_msg(1000)).unwrap();
+}
+
+#[test]
+fn cancelling_cw20_bounty_refunds_in_token() {
+    let (mut deps, env) = setup_contract();
+    let bounty_id = create_cw20_bounty(&mut deps, &env, 1000);
+
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::CancelBounty { bounty_id },
+    )
+    .unwrap();
+    assert_eq!(res.messages[0].msg, cw20_payout(CLIENT, 1000));
+}
+
+#[test]
+fn paused_contract_rejects_bounty_actions() {
+    let (mut deps, env) = setup_contract();
+    let bounty_id = create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+    let entry = submit_to_bounty(&mut deps, &env, FREELANCER, bounty_id);
+
+    exec(&mut deps, &env, ADMIN, &[], ExecuteMsg::PauseContract {}).unwrap();
+
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, DENOM),
+        create_bounty_msg(1000, &[100]),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ContractPaused {});
+
+    // Pausing takes precedence over a funding mismatch
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        create_bounty_msg(1000, &[100]),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ContractPaused {});
+
+    let err = exec(
+        &mut deps,
+        &env,
+        "designer",
+        &[],
+        ExecuteMsg::SubmitToBounty {
+            bounty_id,
+            title: "My entry".to_string(),
+            description: "See attached".to_string(),
+            deliverables: vec!["https://github.com/example/entry".to_string()],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ContractPaused {});
+
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::SelectBountyWinners {
+            bounty_id,
+            winner_submissions: vec![WinnerSelection {
+                submission_id: entry,
+                position: 1,
+            }],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ContractPaused {});
+}
+
+fn set_allowed_denoms(
+    deps: &mut TestDeps,
+    env: &cosmwasm_std::Env,
+    sender: &str,
+    denoms: &[&str],
+) -> Result<cosmwasm_std::Response, ContractError> {
+    exec(
+        deps,
+        env,
+        sender,
+        &[],
+        ExecuteMsg::SetAllowedDenoms {
+            denoms: denoms.iter().map(|d| d.to_string()).collect(),
+        },
+    )
+}
+
+#[test]
+fn bounties_only_accept_whitelisted_denoms() {
+    let (mut deps, env) = setup_contract();
+
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, "uatom"),
+        create_bounty_msg(1000, &[100]),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DenomNotAllowed {
+            denom: "uatom".to_string()
+        }
+    );
+
+    let err = set_allowed_denoms(&mut deps, &env, CLIENT, &[DENOM, "uatom"]).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    // The payout denom can't be dropped and entries must be unique
+    for denoms in [vec![], vec!["uatom"], vec![DENOM, DENOM]] {
+        let err = set_allowed_denoms(&mut deps, &env, ADMIN, &denoms).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidInput { .. }));
+    }
+    set_allowed_denoms(&mut deps, &env, ADMIN, &[DENOM, "uatom"]).unwrap();
+
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, "uatom"),
+        create_bounty_msg(1000, &[100]),
+    )
+    .unwrap();
+    let bounty_id = attr_value(&res, "id").unwrap();
+
+    // The escrow records the denom it was funded in
+    let escrow = query_as::<EscrowResponse>(
+        &deps,
+        &env,
+        QueryMsg::GetEscrow {
+            escrow_id: format!("bounty_{bounty_id}"),
+        },
+    )
+    .escrow
+    .unwrap();
+    assert_eq!(escrow.denom, Denom::Native("uatom".to_string()));
+}
+
+#[test]
+fn missing_bounty_query_returns_none() {
+    let (deps, env) = setup_contract();
+    let res: BountyResponse = query_as(&deps, &env, QueryMsg::GetBounty { bounty_id: 42 });
+    assert_eq!(res.bounty, None);
+}
+
+#[test]
+fn bounty_content_edits_emit_new_content_hash() {
+    let (mut deps, env) = setup_contract();
+    let bounty_id = create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::EditBounty {
+            bounty_id,
+            title: Some("Sharper title".to_string()),
+            description: None,
+            requirements: None,
+            submission_deadline_days: None,
+            review_period_days: None,
+            documents: None,
+        },
+    )
+    .unwrap();
+    let bounty = query_as::<BountyResponse>(&deps, &env, QueryMsg::GetBounty { bounty_id })
+        .bounty
+        .unwrap();
+    assert_eq!(
+        attr_value(&res, "content_hash"),
+        Some(bounty.content_hash.hash)
+    );
+
+    // Deadline-only edits don't touch the content
+    let mut later = env.clone();
+    later.block.time = later.block.time.plus_seconds(60);
+    let res = edit_deadline(&mut deps, &later, bounty_id, 20).unwrap();
+    assert_eq!(attr_value(&res, "content_hash"), None);
+
+    let submission_id = submit_to_bounty(&mut deps, &env, FREELANCER, bounty_id);
+    let res = exec(
+        &mut deps,
+        &env,
+        FREELANCER,
+        &[],
+        ExecuteMsg::EditBountySubmission {
+            submission_id,
+            title: Some("Revised entry".to_string()),
+            description: None,
+            deliverables: None,
+        },
+    )
+    .unwrap();
+    let submission: BountySubmissionResponse =
+        query_as(&deps, &env, QueryMsg::GetBountySubmission { submission_id });
+    assert_eq!(
+        attr_value(&res, "content_hash"),
+        Some(submission.submission.content_hash.hash)
+    );
+}
+
+#[test]
+fn reward_tiers_sum_exactly_to_total_reward() {
+    let (mut deps, env) = setup_contract();
+
+    for (i, (total_reward, percentages)) in [
+        (1001u128, vec![33, 33, 34]),
+        (1003, vec![50, 30, 20]),
+        (7777, vec![1, 99]),
+        (9999, vec![10, 20, 30, 40]),
+        (1000, vec![100]),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        // Separate posters keep clear of the daily bounty rate limit
+        let poster = format!("poster{}", i);
+        let bounty_id = create_bounty(&mut deps, &env, &poster, total_reward, &percentages);
+        let bounty = query_as::<BountyResponse>(&deps, &env, QueryMsg::GetBounty { bounty_id })
+            .bounty
+            .unwrap();
+
+        let sum: Uint128 = bounty.reward_distribution.iter().map(|t| t.amount).sum();
+        assert_eq!(sum, Uint128::new(total_reward));
+
+        // Only first place absorbs the rounding remainder
+        for tier in &bounty.reward_distribution[1..] {
+            assert_eq!(
+                tier.amount,
+                Uint128::new(total_reward).multiply_ratio(tier.percentage, 100u64)
+            );
+        }
+    }
+
+    // Percentages that don't cover the whole reward are rejected
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, DENOM),
+        create_bounty_msg(1000, &[60, 30]),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+}
+
+fn withdraw_submission(
+    deps: &mut TestDeps,
+    env: &cosmwasm_std::Env,
+    submission_id: u64,
+) -> Result<cosmwasm_std::Response, ContractError> {
+    exec(
+        deps,
+        env,
+        FREELANCER,
+        &[],
+        ExecuteMsg::WithdrawBountySubmission { submission_id },
+    )
+}
+
+#[test]
+fn submissions_are_indexed_by_bounty() {
+    let (mut deps, env) = setup_contract();
+    let bounty_id = create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+    let first = submit_to_bounty(&mut deps, &env, FREELANCER, bounty_id);
+    let second = submit_to_bounty(&mut deps, &env, "designer", bounty_id);
+
+    let res: BountySubmissionsResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetBountySubmissions {
+            bounty_id,
+            status: None,
+        },
+    );
+    let ids: Vec<u64> = res.submissions.iter().map(|s| s.id).collect();
+    assert_eq!(ids, vec![first, second]);
+
+    // Withdrawing drops the submission from the bounty's list
+    withdraw_submission(&mut deps, &env, first).unwrap();
+    let res: BountySubmissionsResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetBountySubmissions {
+            bounty_id,
+            status: None,
+        },
+    );
+    let ids: Vec<u64> = res.submissions.iter().map(|s| s.id).collect();
+    assert_eq!(ids, vec![second]);
+}
+
+fn user_submission_ids(
+    deps: &TestDeps,
+    env: &cosmwasm_std::Env,
+    status: Option<BountySubmissionStatus>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> Vec<u64> {
+    let res: BountySubmissionsResponse = query_as(
+        deps,
+        env,
+        QueryMsg::GetUserBountySubmissions {
+            user: FREELANCER.to_string(),
+            status,
+            start_after,
+            limit,
+        },
+    );
+    res.submissions.iter().map(|s| s.id).collect()
+}
+
+#[test]
+fn user_submissions_filter_by_status_and_paginate() {
+    let (mut deps, env) = setup_contract();
+    let mut ids = Vec::new();
+    for _ in 0..3 {
+        let bounty_id = create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+        ids.push(submit_to_bounty(&mut deps, &env, FREELANCER, bounty_id));
+    }
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::ReviewBountySubmission {
+            submission_id: ids[1],
+            status: BountySubmissionStatus::UnderReview,
+            review_notes: None,
+            score: Some(80),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(user_submission_ids(&deps, &env, None, None, None), ids);
+
+    // Pages walk only the matching submissions
+    let submitted = Some(BountySubmissionStatus::Submitted);
+    let page = user_submission_ids(&deps, &env, submitted.clone(), None, Some(1));
+    assert_eq!(page, vec![ids[0]]);
+    let page = user_submission_ids(&deps, &env, submitted.clone(), Some(ids[0]), Some(1));
+    assert_eq!(page, vec![ids[2]]);
+    assert!(user_submission_ids(&deps, &env, submitted, Some(ids[2]), None).is_empty());
+
+    let reviewed = Some(BountySubmissionStatus::UnderReview);
+    assert_eq!(
+        user_submission_ids(&deps, &env, reviewed, None, None),
+        vec![ids[1]]
+    );
+}
+
+#[test]
+fn withdrawal_closes_at_review_or_deadline() {
+    let (mut deps, env) = setup_contract();
+    let bounty_id = create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+
+    // Reviewed submissions stay put
+    let reviewed = submit_to_bounty(&mut deps, &env, FREELANCER, bounty_id);
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::ReviewBountySubmission {
+            submission_id: reviewed,
+            status: BountySubmissionStatus::UnderReview,
+            review_notes: None,
+            score: Some(80),
+        },
+    )
+    .unwrap();
+    let err = withdraw_submission(&mut deps, &env, reviewed).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+
+    // Pending submissions can't be pulled once the window closes
+    let other_bounty = create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+    let pending = submit_to_bounty(&mut deps, &env, FREELANCER, other_bounty);
+    let mut after_deadline = env.clone();
+    after_deadline.block.time = after_deadline.block.time.plus_seconds(7 * 24 * 60 * 60);
+    let err = withdraw_submission(&mut deps, &after_deadline, pending).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+
+    withdraw_submission(&mut deps, &env, pending).unwrap();
+    let submission: BountySubmissionResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetBountySubmission {
+            submission_id: pending,
+        },
+    );
+    assert_eq!(
+        submission.submission.status,
+        BountySubmissionStatus::Withdrawn
+    );
+}
+
+#[test]
+fn bounty_documents_are_bounded() {
+    let (mut deps, env) = setup_contract();
+
+    let mut msg = create_bounty_msg(1000, &[100]);
+    if let ExecuteMsg::CreateBounty { documents, .. } = &mut msg {
+        *documents = Some(vec!["ipfs://brief".to_string(); 11]);
+    }
+    let err = exec(&mut deps, &env, CLIENT, &coins(1000, DENOM), msg).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+
+    let mut msg = create_bounty_msg(1000, &[100]);
+    if let ExecuteMsg::CreateBounty { documents, .. } = &mut msg {
+        *documents = Some(vec![String::new()]);
+    }
+    let err = exec(&mut deps, &env, CLIENT, &coins(1000, DENOM), msg).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+}
+
+#[test]
+fn bounty_skills_and_requirements_are_bounded() {
+    // Fresh contract per case so failed attempts don't trip the rate limiter
+    let create_with = |skills: Vec<String>, reqs: Vec<String>| {
+        let (mut deps, env) = setup_contract();
+        let mut msg = create_bounty_msg(1000, &[100]);
+        if let ExecuteMsg::CreateBounty {
+            skills_required,
+            requirements,
+            ..
+        } = &mut msg
+        {
+            *skills_required = skills;
+            *requirements = reqs;
+        }
+        exec(&mut deps, &env, CLIENT, &coins(1000, DENOM), msg)
+    };
+    let one = |s: &str| vec![s.to_string()];
+
+    let err = create_with(vec![" ".to_string()], one("Working demo")).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+    let err = create_with(vec!["design".to_string(); 21], one("Working demo")).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+    let err = create_with(one("design"), vec![String::new()]).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+    let err = create_with(one("design"), vec!["Working demo".to_string(); 11]).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+    create_with(one("design"), one("Working demo")).unwrap();
+}
+
+#[test]
+fn bounty_winners_join_positions_and_rewards() {
+    let (mut deps, env) = setup_contract();
+    let bounty_id = create_bounty(&mut deps, &env, CLIENT, 1000, &[50, 30, 20]);
+    let entries: Vec<u64> = ["alice", "bob", "carol"]
+        .iter()
+        .map(|submitter| submit_to_bounty(&mut deps, &env, submitter, bounty_id))
+        .collect();
+
+    // carol takes first place, alice second, bob third
+    select_winners(
+        &mut deps,
+        &env,
+        bounty_id,
+        &[entries[2], entries[0], entries[1]],
+    );
+
+    let res: BountyWinnersResponse =
+        query_as(&deps, &env, QueryMsg::GetBountyWinners { bounty_id });
+    let winners: Vec<(u64, &str, u64, u128)> = res
+        .winners
+        .iter()
+        .map(|w| {
+            (
+                w.position,
+                w.submitter.as_str(),
+                w.submission_id,
+                w.reward_amount.u128(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        winners,
+        vec![
+            (1, "carol", entries[2], 500),
+            (2, "alice", entries[0], 300),
+            (3, "bob", entries[1], 200),
+        ]
+    );
+}
+
+fn bounty_with_deadline(
+    deps: &mut TestDeps,
+    env: &cosmwasm_std::Env,
+    poster: &str,
+    days: u64,
+) -> u64 {
+    let mut msg = create_bounty_msg(1000, &[100]);
+    if let ExecuteMsg::CreateBounty {
+        submission_deadline_days,
+        ..
+    } = &mut msg
+    {
+        *submission_deadline_days = days;
+    }
+    let res = exec(deps, env, poster, &coins(1000, DENOM), msg).unwrap();
+    attr_value(&res, "id").unwrap().parse().unwrap()
+}
+
+fn expiring_ids(
+    deps: &TestDeps,
+    env: &cosmwasm_std::Env,
+    within_days: u64,
+    limit: Option<u32>,
+) -> Vec<u64> {
+    let res: BountiesResponse = query_as(
+        deps,
+        env,
+        QueryMsg::GetBountiesExpiringSoon { within_days, limit },
+    );
+    res.bounties.iter().map(|b| b.id).collect()
+}
+
+#[test]
+fn expiring_soon_lists_open_bounties_by_deadline() {
+    let (mut deps, mut env) = setup_contract();
+    let in_seven = bounty_with_deadline(&mut deps, &env, CLIENT, 7);
+    let in_two = bounty_with_deadline(&mut deps, &env, CLIENT, 2);
+    let in_thirty = bounty_with_deadline(&mut deps, &env, CLIENT, 30);
+    let in_four = bounty_with_deadline(&mut deps, &env, ADMIN, 4);
+    let cancelled = bounty_with_deadline(&mut deps, &env, ADMIN, 3);
+    exec(
+        &mut deps,
+        &env,
+        ADMIN,
+        &[],
+        ExecuteMsg::CancelBounty {
+            bounty_id: cancelled,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        expiring_ids(&deps, &env, 7, None),
+        vec![in_two, in_four, in_seven]
+    );
+    assert_eq!(expiring_ids(&deps, &env, 7, Some(2)), vec![in_two, in_four]);
+    assert_eq!(expiring_ids(&deps, &env, 1, None), Vec::<u64>::new());
+    assert_eq!(
+        expiring_ids(&deps, &env, 30, None),
+        vec![in_two, in_four, in_seven, in_thirty]
+    );
+
+    // Bounties whose deadline already passed are no longer expiring soon
+    env.block.time = env.block.time.plus_seconds(3 * 24 * 60 * 60);
+    assert_eq!(expiring_ids(&deps, &env, 7, None), vec![in_four, in_seven]);
+}
+
+#[test]
+fn bounty_reward_must_lie_within_configured_bounds() {
+    let (mut deps, env) = setup_contract();
+    let max = 1_000_000_000_000u128;
+
+    for (reward, expected) in [
+        (
+            999u128,
+            ContractError::BountyRewardBelowMinimum {
+                min: Uint128::new(1000),
+            },
+        ),
+        (
+            max + 1,
+            ContractError::BountyRewardAboveMaximum {
+                max: Uint128::new(max),
+            },
+        ),
+    ] {
+        let err = exec(
+            &mut deps,
+            &env,
+            CLIENT,
+            &coins(reward, DENOM),
+            create_bounty_msg(reward, &[100]),
+        )
+        .unwrap_err();
+        assert_eq!(err, expected);
+    }
+
+    // The rejected attempts left no trace, not even in the daily rate limit
+    let first = create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+    let second = create_bounty(&mut deps, &env, CLIENT, max, &[100]);
+    assert_eq!(second, first + 1);
+    create_bounty(&mut deps, &env, CLIENT, 5000, &[100]);
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, DENOM),
+        create_bounty_msg(1000, &[100]),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::RateLimitExceeded { .. }));
+}
+
+#[test]
+fn unawarded_bounty_expires_and_refunds_after_review_window() {
+    let (mut deps, mut env) = setup_contract();
+    let bounty_id = create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+    let submission_id = submit_to_bounty(&mut deps, &env, FREELANCER, bounty_id);
+    let expire = ExecuteMsg::ExpireBounty { bounty_id };
+
+    // 7 day deadline plus a 3 day review period
+    env.block.time = env.block.time.plus_seconds(10 * 24 * 60 * 60);
+    let err = exec(&mut deps, &env, "keeper", &[], expire.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+
+    env.block.time = env.block.time.plus_seconds(1);
+    let res = exec(&mut deps, &env, "keeper", &[], expire.clone()).unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].msg, payout(CLIENT, 1000));
+
+    let bounty: BountyResponse = query_as(&deps, &env, QueryMsg::GetBounty { bounty_id });
+    assert_eq!(bounty.bounty.unwrap().status, BountyStatus::Expired);
+    let submission: BountySubmissionResponse =
+        query_as(&deps, &env, QueryMsg::GetBountySubmission { submission_id });
+    assert_eq!(
+        submission.submission.status,
+        BountySubmissionStatus::BountyExpired
+    );
+
+    // The refund happens only once
+    let err = exec(&mut deps, &env, "keeper", &[], expire).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+}
+
+const IBC_DENOM: &str = "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+
+#[test]
+fn ibc_vouchers_fund_and_release_verbatim() {
+    let (mut deps, env) = setup_contract();
+
+    // Trace hashes must be well formed; a lowercased voucher is a different denom
+    for bad in [IBC_DENOM.to_lowercase(), "ibc/27394F".to_string()] {
+        let err = set_allowed_denoms(&mut deps, &env, ADMIN, &[DENOM, &bad]).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidInput { .. }));
+    }
+    set_allowed_denoms(&mut deps, &env, ADMIN, &[DENOM, IBC_DENOM]).unwrap();
+
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, IBC_DENOM),
+        create_bounty_msg(1000, &[100]),
+    )
+    .unwrap();
+    let bounty_id: u64 = attr_value(&res, "id").unwrap().parse().unwrap();
+    let escrow = query_as::<EscrowResponse>(
+        &deps,
+        &env,
+        QueryMsg::GetEscrow {
+            escrow_id: format!("bounty_{bounty_id}"),
+        },
+    )
+    .escrow
+    .unwrap();
+    assert_eq!(escrow.denom, Denom::Native(IBC_DENOM.to_string()));
+
+    let entry = submit_to_bounty(&mut deps, &env, FREELANCER, bounty_id);
+    select_winners(&mut deps, &env, bounty_id, &[entry]);
+    let res = exec(
+        &mut deps,
+        &after_review(&env),
+        CLIENT,
+        &[],
+        ExecuteMsg::ReleaseBountyRewards { bounty_id },
+    )
+    .unwrap();
+
+    let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+    assert_eq!(
+        msgs,
+        vec![CosmosMsg::Bank(BankMsg::Send {
+            to_address: FREELANCER.to_string(),
+            amount: coins(950, IBC_DENOM),
+        })]
+    );
+}
+
+#[test]
+fn release_payouts_stay_within_the_message_budget() {
+    let (mut deps, env) = setup_contract();
+
+    // One tier too many to fit alongside the poster's refund
+    let mut too_many = vec![51];
+    too_many.extend([1; 49]);
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, DENOM),
+        create_bounty_msg(1000, &too_many),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+
+    let mut tiers = vec![52];
+    tiers.extend([1; 48]);
+    let bounty_id = create_bounty(&mut deps, &env, CLIENT, 1000, &tiers);
+    let entries: Vec<u64> = (0..tiers.len())
+        .map(|i| submit_to_bounty(&mut deps, &env, &format!("entrant{}", i), bounty_id))
+        .collect();
+    select_winners(&mut deps, &env, bounty_id, &entries);
+
+    let res = release(&mut deps, &after_review(&env), bounty_id).unwrap();
+    assert_eq!(res.messages.len(), tiers.len());
+    assert!(res.messages.len() <= MAX_BOUNTY_PAYOUTS);
+    assert_eq!(res.messages[0].msg, payout("entrant0", 494));
+    assert_eq!(res.messages[48].msg, payout("entrant48", 10));
+}
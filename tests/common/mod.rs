@@ -0,0 +1,279 @@
+// Shared fixtures for the integration test suites
+#![allow(dead_code)]
+
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    coins, from_json, Coin, Env, MessageInfo, OwnedDeps, Reply, ReplyOn, Response, SubMsgResponse,
+    SubMsgResult, Uint128,
+};
+use cw20::Denom;
+use serde::de::DeserializeOwned;
+
+use xworks_freelance_contract::contract::{execute, instantiate, query, reply};
+use xworks_freelance_contract::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, RewardTierInput};
+use xworks_freelance_contract::state::ContactPreference;
+use xworks_freelance_contract::ContractError;
+
+pub const ADMIN: &str = "admin";
+pub const CLIENT: &str = "client";
+pub const FREELANCER: &str = "freelancer";
+pub const DENOM: &str = "uxion";
+
+pub type TestDeps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+pub fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        admin: Some(ADMIN.to_string()),
+        platform_fee_percent: Some(5),
+        min_escrow_amount: Some(Uint128::new(1000)),
+        dispute_period_days: Some(3),
+        max_job_duration_days: Some(365),
+        denom: None,
+        treasury: None,
+        dispute_timeout_release_to_freelancer: None,
+        max_proposals_per_job: None,
+        job_archive_retention_days: None,
+        fee_split_bps: None,
+        max_bounty_reward: None,
+        edit_grace_period_days: None,
+        allowed_denoms: None,
+        allowed_cw20_tokens: None,
+        proposal_validity_days: None,
+        min_edit_interval_seconds: None,
+        dispute_deposit: None,
+        reputation_half_life_days: None,
+        dispute_cooldown_seconds: None,
+        max_open_jobs_per_user: None,
+    }
+}
+
+pub fn setup_contract() -> (TestDeps, Env) {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN, &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    (deps, env)
+}
+
+pub fn exec(
+    deps: &mut TestDeps,
+    env: &Env,
+    sender: &str,
+    funds: &[Coin],
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    run(deps, env, mock_info(sender, funds), msg)
+}
+
+/// Execute as the chain would: the outgoing messages succeed, then their replies arrive
+pub fn run(
+    deps: &mut TestDeps,
+    env: &Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    let res = execute(deps.as_mut(), env.clone(), info, msg)?;
+    for sub in res
+        .messages
+        .iter()
+        .filter(|sub| sub.reply_on != ReplyOn::Never)
+    {
+        let result = SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        });
+        reply(deps.as_mut(), env.clone(), Reply { id: sub.id, result })?;
+    }
+    Ok(res)
+}
+
+pub fn query_as<T: DeserializeOwned>(deps: &TestDeps, env: &Env, msg: QueryMsg) -> T {
+    from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap()
+}
+
+pub fn attr_value(res: &Response, key: &str) -> Option<String> {
+    res.attributes
+        .iter()
+        .find(|a| a.key == key)
+        .map(|a| a.value.clone())
+}
+
+pub fn post_job_msg(budget: u128) -> ExecuteMsg {
+    ExecuteMsg::PostJob {
+        title: "Build a dApp".to_string(),
+        description: "Full stack CosmWasm work".to_string(),
+        company: None,
+        location: None,
+        category: "Web Development".to_string(),
+        skills_required: vec!["rust".to_string()],
+        documents: None,
+        milestones: None,
+        budget: Uint128::new(budget),
+        duration_days: 10,
+        experience_level: 2,
+        is_remote: true,
+        urgency_level: 1,
+        off_chain_storage_key: "job_key".to_string(),
+        release_policy: None,
+        denom: None,
+    }
+}
+
+pub fn post_job_in(denom: Denom) -> ExecuteMsg {
+    let mut msg = post_job_msg(1000);
+    if let ExecuteMsg::PostJob {
+        denom: job_denom, ..
+    } = &mut msg
+    {
+        *job_denom = Some(denom);
+    }
+    msg
+}
+
+pub fn post_job(deps: &mut TestDeps, env: &Env, poster: &str, budget: u128) -> u64 {
+    let res = exec(
+        deps,
+        env,
+        poster,
+        &coins(budget, DENOM),
+        post_job_msg(budget),
+    )
+    .unwrap();
+    attr_value(&res, "id").unwrap().parse().unwrap()
+}
+
+pub fn submit_proposal_msg(job_id: u64) -> ExecuteMsg {
+    ExecuteMsg::SubmitProposal {
+        job_id,
+        cover_letter: "I can deliver this".to_string(),
+        milestones: None,
+        portfolio_samples: None,
+        delivery_time_days: 7,
+        contact_preference: ContactPreference::Platform,
+        agreed_to_terms: true,
+        agreed_to_escrow: true,
+        estimated_hours: Some(40),
+        payout_address: None,
+        referrer: None,
+        referral_bps: None,
+        off_chain_storage_key: "proposal_key".to_string(),
+    }
+}
+
+pub fn submit_proposal(deps: &mut TestDeps, env: &Env, freelancer: &str, job_id: u64) -> u64 {
+    let res = exec(deps, env, freelancer, &[], submit_proposal_msg(job_id)).unwrap();
+    attr_value(&res, "id").unwrap().parse().unwrap()
+}
+
+pub fn accept_proposal(
+    deps: &mut TestDeps,
+    env: &Env,
+    poster: &str,
+    job_id: u64,
+    proposal_id: u64,
+) {
+    exec(
+        deps,
+        env,
+        poster,
+        &[],
+        ExecuteMsg::AcceptProposal {
+            job_id,
+            proposal_id,
+        },
+    )
+    .unwrap();
+}
+
+/// Posts a job as CLIENT and assigns it to FREELANCER, returning the job id
+pub fn assigned_job(deps: &mut TestDeps, env: &Env, budget: u128) -> u64 {
+    let job_id = post_job(deps, env, CLIENT, budget);
+    let proposal_id = submit_proposal(deps, env, FREELANCER, job_id);
+    accept_proposal(deps, env, CLIENT, job_id, proposal_id);
+    job_id
+}
+
+/// FREELANCER submits work on an assigned job and CLIENT approves it
+pub fn complete_job(deps: &mut TestDeps, env: &Env, job_id: u64) -> Response {
+    exec(
+        deps,
+        env,
+        FREELANCER,
+        &[],
+        ExecuteMsg::SubmitWork { job_id },
+    )
+    .unwrap();
+    exec(
+        deps,
+        env,
+        CLIENT,
+        &[],
+        ExecuteMsg::ApproveCompletion { job_id },
+    )
+    .unwrap()
+}
+
+pub fn create_bounty_msg(total_reward: u128, percentages: &[u64]) -> ExecuteMsg {
+    ExecuteMsg::CreateBounty {
+        title: "Design a logo".to_string(),
+        description: "Logo for the XWorks brand".to_string(),
+        requirements: vec!["SVG output".to_string()],
+        total_reward: Uint128::new(total_reward),
+        category: "Design".to_string(),
+        skills_required: vec!["design".to_string()],
+        submission_deadline_days: 7,
+        review_period_days: 3,
+        max_winners: percentages.len() as u64,
+        reward_distribution: percentages
+            .iter()
+            .enumerate()
+            .map(|(i, p)| RewardTierInput {
+                position: (i + 1) as u64,
+                percentage: *p,
+            })
+            .collect(),
+        documents: None,
+    }
+}
+
+pub fn create_bounty(
+    deps: &mut TestDeps,
+    env: &Env,
+    poster: &str,
+    total_reward: u128,
+    percentages: &[u64],
+) -> u64 {
+    let res = exec(
+        deps,
+        env,
+        poster,
+        &coins(total_reward, DENOM),
+        create_bounty_msg(total_reward, percentages),
+    )
+    .unwrap();
+    attr_value(&res, "id").unwrap().parse().unwrap()
+}
+
+pub fn submit_to_bounty(deps: &mut TestDeps, env: &Env, submitter: &str, bounty_id: u64) -> u64 {
+    let res = exec(
+        deps,
+        env,
+        submitter,
+        &[],
+        ExecuteMsg::SubmitToBounty {
+            bounty_id,
+            title: "My entry".to_string(),
+            description: "See attached".to_string(),
+            deliverables: vec!["https://github.com/example/entry".to_string()],
+        },
+    )
+    .unwrap();
+    attr_value(&res, "id").unwrap().parse().unwrap()
+}
@@ -1,6 +1,9 @@
+mod common;
+
+use common::run;
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
 use cosmwasm_std::{coins, from_json, Addr, Uint128};
-use xworks_freelance_contract::contract::{execute, instantiate, query};
+use xworks_freelance_contract::contract::{instantiate, query};
 use xworks_freelance_contract::msg::{
     BountyResponse, ConfigResponse, DisputesResponse, EscrowResponse, ExecuteMsg, InstantiateMsg,
     JobResponse, MilestoneInput, ProposalResponse, QueryMsg, RewardTierInput,
@@ -21,6 +24,22 @@ fn full_contract_flow() {
         min_escrow_amount: Some(Uint128::new(100)),
         dispute_period_days: Some(3),
         max_job_duration_days: Some(30),
+        denom: None,
+        treasury: None,
+        dispute_timeout_release_to_freelancer: None,
+        max_proposals_per_job: None,
+        job_archive_retention_days: None,
+        fee_split_bps: None,
+        max_bounty_reward: None,
+        edit_grace_period_days: None,
+        allowed_denoms: Some(vec!["uxion".to_string(), "token".to_string()]),
+        allowed_cw20_tokens: None,
+        proposal_validity_days: None,
+        min_edit_interval_seconds: None,
+        dispute_deposit: None,
+        reputation_half_life_days: None,
+        dispute_cooldown_seconds: None,
+        max_open_jobs_per_user: None,
     };
     instantiate(deps.as_mut(), env.clone(), info.clone(), init).unwrap();
     // Query and verify config
@@ -51,13 +70,15 @@ fn full_contract_flow() {
         is_remote: true,
         urgency_level: 1,
         off_chain_storage_key: "key1".to_string(),
+        release_policy: None,
+        denom: None,
     };
-    execute(deps.as_mut(), env.clone(), info.clone(), post).unwrap();
+    run(&mut deps, &env, info.clone(), post).unwrap();
     // Verify job stored
     let j_resp: JobResponse =
         from_json(&query(deps.as_ref(), env.clone(), QueryMsg::GetJob { job_id: 0 }).unwrap())
             .unwrap();
-    let j = j_resp.job;
+    let j = j_resp.job.unwrap();
     assert_eq!(j.status, JobStatus::Open);
     assert_eq!(j.budget.u128(), 1000);
 
@@ -77,9 +98,12 @@ fn full_contract_flow() {
         agreed_to_terms: true,
         agreed_to_escrow: true,
         estimated_hours: Some(40),
+        payout_address: None,
+        referrer: None,
+        referral_bps: None,
         off_chain_storage_key: "key2".to_string(),
     };
-    execute(deps.as_mut(), env.clone(), info.clone(), prop).unwrap();
+    run(&mut deps, &env, info.clone(), prop).unwrap();
     let p_resp: ProposalResponse = from_json(
         &query(
             deps.as_ref(),
@@ -97,11 +121,11 @@ fn full_contract_flow() {
         job_id: 0,
         proposal_id: 0,
     };
-    execute(deps.as_mut(), env.clone(), info.clone(), acc).unwrap();
+    run(&mut deps, &env, info.clone(), acc).unwrap();
     let j2_resp: JobResponse =
         from_json(&query(deps.as_ref(), env.clone(), QueryMsg::GetJob { job_id: 0 }).unwrap())
             .unwrap();
-    let j2 = j2_resp.job;
+    let j2 = j2_resp.job.unwrap();
     assert_eq!(j2.status, JobStatus::InProgress);
 
     // Query existing escrow created during posting
@@ -114,19 +138,22 @@ fn full_contract_flow() {
         .unwrap(),
     )
     .unwrap();
-    let es = es_resp.escrow;
-    // Escrow auto-funded with full budget
-    assert_eq!(es.amount.u128(), 1000);
+    let es = es_resp.escrow.unwrap();
+    // Escrow auto-funded with the budget; the freelancer bears the 5% fee
+    assert_eq!(es.amount.u128(), 950);
+    assert_eq!(es.platform_fee.u128(), 50);
     let _escrow_id = es.id.clone();
 
-    // Complete the job which triggers escrow release on-chain
-    let cj = ExecuteMsg::CompleteJob { job_id: 0 };
-    execute(deps.as_mut(), env.clone(), info.clone(), cj).unwrap();
+    // Submit the work, then approve it which triggers escrow release on-chain
+    let sw = ExecuteMsg::SubmitWork { job_id: 0 };
+    run(&mut deps, &env, info.clone(), sw).unwrap();
+    let ac = ExecuteMsg::ApproveCompletion { job_id: 0 };
+    run(&mut deps, &env, info.clone(), ac).unwrap();
     // Verify job status updated to Completed
     let j3_resp: JobResponse =
         from_json(&query(deps.as_ref(), env.clone(), QueryMsg::GetJob { job_id: 0 }).unwrap())
             .unwrap();
-    assert_eq!(j3_resp.job.status, JobStatus::Completed);
+    assert_eq!(j3_resp.job.unwrap().status, JobStatus::Completed);
     // Verify escrow released flag
     let es2_resp: EscrowResponse = from_json(
         &query(
@@ -137,7 +164,7 @@ fn full_contract_flow() {
         .unwrap(),
     )
     .unwrap();
-    assert!(es2_resp.escrow.released);
+    assert!(es2_resp.escrow.unwrap().released);
 
     // Submit rating
     let rt = ExecuteMsg::SubmitRating {
@@ -145,12 +172,17 @@ fn full_contract_flow() {
         rating: 5,
         comment: "good".to_string(),
     };
-    execute(deps.as_mut(), env.clone(), info.clone(), rt).unwrap();
+    run(&mut deps, &env, info.clone(), rt).unwrap();
+    // Held until the reveal window lapses, since the counterpart never rates
+    let mut revealed = env.clone();
+    revealed.block.time = revealed.block.time.plus_seconds(7 * 24 * 60 * 60);
+    let fr = ExecuteMsg::FinalizeRatings { job_id: 0 };
+    run(&mut deps, &revealed, info.clone(), fr).unwrap();
     // Retrieve single rating
     let r: Rating = from_json(
         &query(
             deps.as_ref(),
-            env.clone(),
+            revealed,
             QueryMsg::GetJobRating {
                 job_id: 0,
                 rater: "admin".to_string(),
@@ -161,14 +193,13 @@ fn full_contract_flow() {
     .unwrap();
     assert_eq!(r.rating, 5);
 
-    // Raise dispute
+    // The escrow is already paid out, so there is nothing left to dispute
     let rd = ExecuteMsg::RaiseDispute {
         job_id: 0,
         reason: "issue".to_string(),
         evidence: vec![],
     };
-    execute(deps.as_mut(), env.clone(), info.clone(), rd).unwrap();
-    // Fetch disputes for job
+    run(&mut deps, &env, info.clone(), rd).unwrap_err();
     let dr: DisputesResponse = from_json(
         &query(
             deps.as_ref(),
@@ -178,32 +209,17 @@ fn full_contract_flow() {
         .unwrap(),
     )
     .unwrap();
-    assert_eq!(dr.disputes.len(), 1);
-    let dispute = &dr.disputes[0];
-    assert_eq!(dispute.job_id, 0);
-    // Resolve dispute
-    let resd = ExecuteMsg::ResolveDispute {
-        dispute_id: dispute.id.clone(),
-        resolution: "ok".to_string(),
-        release_to_freelancer: true,
-    };
-    execute(deps.as_mut(), env.clone(), info.clone(), resd).unwrap();
+    assert!(dr.disputes.is_empty());
 
     // Admin pause and unpause
-    execute(
-        deps.as_mut(),
-        env.clone(),
-        info.clone(),
-        ExecuteMsg::PauseContract {},
-    )
-    .unwrap();
+    run(&mut deps, &env, info.clone(), ExecuteMsg::PauseContract {}).unwrap();
     let pause_resp: ConfigResponse =
         from_json(&query(deps.as_ref(), env.clone(), QueryMsg::GetConfig {}).unwrap()).unwrap();
     let pause_q = pause_resp.config;
     assert!(pause_q.paused);
-    execute(
-        deps.as_mut(),
-        env.clone(),
+    run(
+        &mut deps,
+        &env,
         info.clone(),
         ExecuteMsg::UnpauseContract {},
     )
@@ -225,7 +241,7 @@ fn full_contract_flow() {
         availability: None,
         off_chain_storage_key: "key3".to_string(),
     };
-    execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), up).unwrap();
+    run(&mut deps, &env, mock_info("alice", &[]), up).unwrap();
     // User profile querying not available; skip direct profile check
 
     // Create a bounty
@@ -246,13 +262,7 @@ fn full_contract_flow() {
         }],
         documents: None,
     };
-    execute(
-        deps.as_mut(),
-        env.clone(),
-        mock_info("admin", &bounty_funds),
-        cb,
-    )
-    .unwrap();
+    run(&mut deps, &env, mock_info("admin", &bounty_funds), cb).unwrap();
     let b_resp: BountyResponse = from_json(
         &query(
             deps.as_ref(),
@@ -262,6 +272,6 @@ fn full_contract_flow() {
         .unwrap(),
     )
     .unwrap();
-    let b = b_resp.bounty;
+    let b = b_resp.bounty.unwrap();
     assert_eq!(b.status, BountyStatus::Open);
 }
@@ -0,0 +1,927 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{
+    coins, to_json_binary, Addr, BankMsg, ContractResult, CosmosMsg, Env, SystemResult, Uint128,
+    WasmMsg,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20ReceiveMsg, Denom};
+use xworks_freelance_contract::msg::{
+    AccruedFeesResponse, DisputeDetailResponse, DisputeResponse, DisputesResponse,
+    EscrowHistoryResponse, ExecuteMsg, JobResponse, QueryMsg, ReceiveMsg,
+};
+use xworks_freelance_contract::state::{DisputeStatus, EscrowEventKind, JobStatus};
+use xworks_freelance_contract::ContractError;
+
+fn raise_dispute(deps: &mut TestDeps, env: &Env, job_id: u64) -> String {
+    exec(
+        deps,
+        env,
+        FREELANCER,
+        &[],
+        ExecuteMsg::RaiseDispute {
+            job_id,
+            reason: "Client stopped responding".to_string(),
+            evidence: vec![],
+        },
+    )
+    .unwrap();
+
+    let disputes: DisputesResponse = query_as(deps, env, QueryMsg::GetJobDisputes { job_id });
+    disputes.disputes[0].id.clone()
+}
+
+fn resolve(
+    deps: &mut TestDeps,
+    env: &Env,
+    dispute_id: &str,
+    release_to_freelancer: bool,
+) -> Result<cosmwasm_std::Response, ContractError> {
+    resolve_split(deps, env, dispute_id, release_to_freelancer, None)
+}
+
+fn resolve_split(
+    deps: &mut TestDeps,
+    env: &Env,
+    dispute_id: &str,
+    release_to_freelancer: bool,
+    freelancer_bps: Option<u16>,
+) -> Result<cosmwasm_std::Response, ContractError> {
+    exec(
+        deps,
+        env,
+        ADMIN,
+        &[],
+        ExecuteMsg::ResolveDispute {
+            dispute_id: dispute_id.to_string(),
+            resolution: "Reviewed the evidence".to_string(),
+            release_to_freelancer,
+            freelancer_bps,
+        },
+    )
+}
+
+fn dispute_status(deps: &TestDeps, env: &Env, dispute_id: &str) -> DisputeStatus {
+    let res: DisputeResponse = query_as(
+        deps,
+        env,
+        QueryMsg::GetDispute {
+            dispute_id: dispute_id.to_string(),
+        },
+    );
+    res.dispute.status
+}
+
+fn first_recipient(res: &cosmwasm_std::Response) -> String {
+    match &res.messages[0].msg {
+        CosmosMsg::Bank(BankMsg::Send { to_address, .. }) => to_address.clone(),
+        other => panic!("unexpected message {:?}", other),
+    }
+}
+
+#[test]
+fn resolution_is_proposed_before_payout() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    let dispute_id = raise_dispute(&mut deps, &env, job_id);
+
+    let res = resolve(&mut deps, &env, &dispute_id, true).unwrap();
+    assert!(res.messages.is_empty());
+
+    let dispute: DisputeResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetDispute {
+            dispute_id: dispute_id.clone(),
+        },
+    );
+    assert_eq!(dispute.dispute.status, DisputeStatus::ResolutionProposed);
+    assert_eq!(dispute.dispute.proposed_release_to_freelancer, Some(true));
+    assert_eq!(
+        dispute.dispute.appeal_deadline,
+        Some(env.block.time.plus_seconds(3 * 24 * 60 * 60))
+    );
+
+    let job: JobResponse = query_as(&deps, &env, QueryMsg::GetJob { job_id });
+    assert_eq!(job.job.unwrap().status, JobStatus::Disputed);
+}
+
+#[test]
+fn appeal_then_admin_override() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    let dispute_id = raise_dispute(&mut deps, &env, job_id);
+
+    // Admin proposes refunding the client
+    resolve(&mut deps, &env, &dispute_id, false).unwrap();
+
+    // Freelancer appeals within the window
+    exec(
+        &mut deps,
+        &env,
+        FREELANCER,
+        &[],
+        ExecuteMsg::AppealDispute {
+            dispute_id: dispute_id.clone(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        dispute_status(&deps, &env, &dispute_id),
+        DisputeStatus::Appealed
+    );
+
+    // An appealed resolution cannot be finalized by the timeout path
+    let mut later = env.clone();
+    later.block.time = later.block.time.plus_seconds(4 * 24 * 60 * 60);
+    let err = exec(
+        &mut deps,
+        &later,
+        CLIENT,
+        &[],
+        ExecuteMsg::FinalizeDispute {
+            dispute_id: dispute_id.clone(),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+
+    // Second admin confirmation overrides in the freelancer's favour
+    let res = resolve(&mut deps, &env, &dispute_id, true).unwrap();
+    assert_eq!(first_recipient(&res), FREELANCER);
+    assert_eq!(
+        dispute_status(&deps, &env, &dispute_id),
+        DisputeStatus::Resolved
+    );
+
+    let job: JobResponse = query_as(&deps, &env, QueryMsg::GetJob { job_id });
+    assert_eq!(job.job.unwrap().status, JobStatus::Completed);
+}
+
+#[test]
+fn unappealed_resolution_finalizes_after_deadline() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    let dispute_id = raise_dispute(&mut deps, &env, job_id);
+    resolve(&mut deps, &env, &dispute_id, false).unwrap();
+
+    let finalize = ExecuteMsg::FinalizeDispute {
+        dispute_id: dispute_id.clone(),
+    };
+
+    // Too early
+    let err = exec(&mut deps, &env, CLIENT, &[], finalize.clone()).unwrap_err();
+    assert_eq!(err, ContractError::DisputePeriodActive {});
+
+    let mut later = env.clone();
+    later.block.time = later.block.time.plus_seconds(3 * 24 * 60 * 60 + 1);
+
+    // Appeals are closed once the window passes
+    let err = exec(
+        &mut deps,
+        &later,
+        FREELANCER,
+        &[],
+        ExecuteMsg::AppealDispute {
+            dispute_id: dispute_id.clone(),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+
+    let res = exec(&mut deps, &later, CLIENT, &[], finalize).unwrap();
+    assert_eq!(first_recipient(&res), CLIENT);
+    assert_eq!(
+        dispute_status(&deps, &later, &dispute_id),
+        DisputeStatus::Resolved
+    );
+
+    let job: JobResponse = query_as(&deps, &later, QueryMsg::GetJob { job_id });
+    assert_eq!(job.job.unwrap().status, JobStatus::Cancelled);
+}
+
+#[test]
+fn only_parties_can_appeal() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    let dispute_id = raise_dispute(&mut deps, &env, job_id);
+    resolve(&mut deps, &env, &dispute_id, true).unwrap();
+
+    let err = exec(
+        &mut deps,
+        &env,
+        "stranger",
+        &[],
+        ExecuteMsg::AppealDispute { dispute_id },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn split_resolution_divides_escrow_by_basis_points() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    let dispute_id = raise_dispute(&mut deps, &env, job_id);
+
+    let err = resolve_split(&mut deps, &env, &dispute_id, false, Some(10_001)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+
+    resolve_split(&mut deps, &env, &dispute_id, false, Some(6000)).unwrap();
+    let res = resolve_split(&mut deps, &env, &dispute_id, false, Some(6000)).unwrap();
+
+    // 1000 escrowed: the 50 platform fee comes off the top and the net 950 is split 60/40
+    let sends: Vec<(String, u128)> = res
+        .messages
+        .iter()
+        .map(|m| match &m.msg {
+            CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+                assert_eq!(amount[0].denom, DENOM);
+                (to_address.clone(), amount[0].amount.u128())
+            }
+            other => panic!("unexpected message {:?}", other),
+        })
+        .collect();
+    assert_eq!(
+        sends,
+        vec![(FREELANCER.to_string(), 570), (CLIENT.to_string(), 380)]
+    );
+    let fees: AccruedFeesResponse = query_as(&deps, &env, QueryMsg::GetAccruedFees { denom: None });
+    assert_eq!(fees.amount, Uint128::new(50));
+
+    // Never pay out more than was deposited, and leave nothing stranded
+    let total: u128 = sends.iter().map(|(_, amount)| amount).sum();
+    assert!(fees.amount + Uint128::new(total) <= Uint128::new(1000));
+    assert_eq!(fees.amount + Uint128::new(total), Uint128::new(1000));
+
+    let job: JobResponse = query_as(&deps, &env, QueryMsg::GetJob { job_id });
+    assert_eq!(job.job.unwrap().status, JobStatus::Completed);
+}
+
+#[test]
+fn split_payouts_and_fee_add_up_to_the_funded_amount() {
+    for (budget, bps) in [(1337u128, 3333u16), (1000, 0), (2501, 10_000)] {
+        let (mut deps, env) = setup_contract();
+        let job_id = assigned_job(&mut deps, &env, budget);
+        let dispute_id = raise_dispute(&mut deps, &env, job_id);
+
+        resolve_split(&mut deps, &env, &dispute_id, false, Some(bps)).unwrap();
+        let res = resolve_split(&mut deps, &env, &dispute_id, false, Some(bps)).unwrap();
+
+        let paid: u128 = res
+            .messages
+            .iter()
+            .map(|m| match &m.msg {
+                CosmosMsg::Bank(BankMsg::Send { amount, .. }) => amount[0].amount.u128(),
+                other => panic!("unexpected message {:?}", other),
+            })
+            .sum();
+        let fees: AccruedFeesResponse =
+            query_as(&deps, &env, QueryMsg::GetAccruedFees { denom: None });
+        // The whole platform fee accrues whatever the split
+        assert_eq!(fees.amount, Uint128::new(budget * 5 / 100));
+        assert!(paid + fees.amount.u128() <= budget);
+        assert_eq!(paid + fees.amount.u128(), budget);
+    }
+}
+
+#[test]
+fn escrow_history_records_each_transition() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    let job: JobResponse = query_as(&deps, &env, QueryMsg::GetJob { job_id });
+    let escrow_id = job.job.unwrap().escrow_id.unwrap();
+
+    let dispute_id = raise_dispute(&mut deps, &env, job_id);
+    resolve(&mut deps, &env, &dispute_id, true).unwrap();
+    resolve(&mut deps, &env, &dispute_id, true).unwrap();
+
+    let history: EscrowHistoryResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetEscrowHistory {
+            escrow_id: escrow_id.clone(),
+        },
+    );
+    assert_eq!(history.escrow_id, escrow_id);
+    let trail: Vec<(EscrowEventKind, String, u128)> = history
+        .events
+        .iter()
+        .map(|event| {
+            (
+                event.kind.clone(),
+                event.actor.to_string(),
+                event.amount.u128(),
+            )
+        })
+        .collect();
+    // Proposing the resolution only moves the dispute, so it leaves no escrow entry
+    assert_eq!(
+        trail,
+        vec![
+            (EscrowEventKind::Funded, CLIENT.to_string(), 1000),
+            (EscrowEventKind::Disputed, FREELANCER.to_string(), 0),
+            (EscrowEventKind::Resolved, ADMIN.to_string(), 1000),
+        ]
+    );
+
+    let unknown: EscrowHistoryResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetEscrowHistory {
+            escrow_id: "job_999".to_string(),
+        },
+    );
+    assert!(unknown.events.is_empty());
+}
+
+fn claim_timeout(
+    deps: &mut TestDeps,
+    env: &Env,
+    dispute_id: &str,
+) -> Result<cosmwasm_std::Response, ContractError> {
+    exec(
+        deps,
+        env,
+        CLIENT,
+        &[],
+        ExecuteMsg::ClaimDisputeTimeout {
+            dispute_id: dispute_id.to_string(),
+        },
+    )
+}
+
+fn after_dispute_period(env: &Env) -> Env {
+    let mut later = env.clone();
+    later.block.time = later.block.time.plus_seconds(3 * 24 * 60 * 60 + 1);
+    later
+}
+
+#[test]
+fn unresolved_dispute_times_out_to_client() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    let dispute_id = raise_dispute(&mut deps, &env, job_id);
+
+    // The deadline follows the configured dispute period
+    let err = claim_timeout(&mut deps, &env, &dispute_id).unwrap_err();
+    assert_eq!(err, ContractError::DisputePeriodActive {});
+
+    let later = after_dispute_period(&env);
+    let res = claim_timeout(&mut deps, &later, &dispute_id).unwrap();
+    assert_eq!(first_recipient(&res), CLIENT);
+    assert_eq!(
+        attr_value(&res, "method").as_deref(),
+        Some("claim_dispute_timeout")
+    );
+    assert_eq!(
+        dispute_status(&deps, &later, &dispute_id),
+        DisputeStatus::Resolved
+    );
+
+    let job: JobResponse = query_as(&deps, &later, QueryMsg::GetJob { job_id });
+    assert_eq!(job.job.unwrap().status, JobStatus::Cancelled);
+
+    // Funds are only paid out once
+    let err = claim_timeout(&mut deps, &later, &dispute_id).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+}
+
+#[test]
+fn dispute_timeout_pays_configured_party() {
+    let (mut deps, env) = setup_contract();
+    exec(
+        &mut deps,
+        &env,
+        ADMIN,
+        &[],
+        ExecuteMsg::UpdateConfig {
+            platform_fee_percent: None,
+            min_escrow_amount: None,
+            dispute_period_days: None,
+            max_job_duration_days: None,
+            treasury: None,
+            dispute_timeout_release_to_freelancer: Some(true),
+            max_proposals_per_job: None,
+            job_archive_retention_days: None,
+            fee_split_bps: None,
+            max_bounty_reward: None,
+            edit_grace_period_days: None,
+            proposal_validity_days: None,
+            min_edit_interval_seconds: None,
+            dispute_deposit: None,
+            reputation_half_life_days: None,
+            dispute_cooldown_seconds: None,
+            max_open_jobs_per_user: None,
+        },
+    )
+    .unwrap();
+
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    let dispute_id = raise_dispute(&mut deps, &env, job_id);
+
+    let later = after_dispute_period(&env);
+    let res = claim_timeout(&mut deps, &later, &dispute_id).unwrap();
+    assert_eq!(first_recipient(&res), FREELANCER);
+
+    let job: JobResponse = query_as(&deps, &later, QueryMsg::GetJob { job_id });
+    assert_eq!(job.job.unwrap().status, JobStatus::Completed);
+}
+
+#[test]
+fn timely_resolution_blocks_timeout_claim() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    let dispute_id = raise_dispute(&mut deps, &env, job_id);
+    resolve(&mut deps, &env, &dispute_id, true).unwrap();
+
+    // A proposed resolution goes through FinalizeDispute, not the timeout
+    let later = after_dispute_period(&env);
+    let err = claim_timeout(&mut deps, &later, &dispute_id).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+
+    let res = exec(
+        &mut deps,
+        &later,
+        CLIENT,
+        &[],
+        ExecuteMsg::FinalizeDispute {
+            dispute_id: dispute_id.clone(),
+        },
+    )
+    .unwrap();
+    assert_eq!(first_recipient(&res), FREELANCER);
+
+    let err = claim_timeout(&mut deps, &later, &dispute_id).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+}
+
+#[test]
+fn late_appeal_restarts_the_timeout() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    let dispute_id = raise_dispute(&mut deps, &env, job_id);
+
+    let mut resolved_at = env.clone();
+    resolved_at.block.time = env.block.time.plus_seconds(2 * 24 * 60 * 60);
+    resolve(&mut deps, &resolved_at, &dispute_id, true).unwrap();
+
+    // Appeal after the original dispute deadline but inside the appeal window
+    let appealed_at = after_dispute_period(&env);
+    exec(
+        &mut deps,
+        &appealed_at,
+        CLIENT,
+        &[],
+        ExecuteMsg::AppealDispute {
+            dispute_id: dispute_id.clone(),
+        },
+    )
+    .unwrap();
+
+    let mut just_after = appealed_at.clone();
+    just_after.block.time = appealed_at.block.time.plus_seconds(1);
+    let err = claim_timeout(&mut deps, &just_after, &dispute_id).unwrap_err();
+    assert_eq!(err, ContractError::DisputePeriodActive {});
+
+    // Without a re-decision the appeal times out a full period later
+    let res = claim_timeout(&mut deps, &after_dispute_period(&appealed_at), &dispute_id).unwrap();
+    assert_eq!(first_recipient(&res), CLIENT);
+}
+
+fn dispute_ids_by_status(
+    deps: &TestDeps,
+    env: &Env,
+    status: DisputeStatus,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Vec<String> {
+    let res: DisputesResponse = query_as(
+        deps,
+        env,
+        QueryMsg::GetDisputesByStatus {
+            status,
+            start_after,
+            limit,
+        },
+    );
+    res.disputes.into_iter().map(|d| d.id).collect()
+}
+
+#[test]
+fn disputes_are_queued_by_status() {
+    let (mut deps, env) = setup_contract();
+    let mut raised: Vec<String> = (0..2)
+        .map(|_| {
+            let job_id = assigned_job(&mut deps, &env, 1000);
+            raise_dispute(&mut deps, &env, job_id)
+        })
+        .collect();
+    raised.sort();
+
+    let proposed_job = assigned_job(&mut deps, &env, 1000);
+    let proposed = raise_dispute(&mut deps, &env, proposed_job);
+    resolve(&mut deps, &env, &proposed, false).unwrap();
+
+    let settled_job = assigned_job(&mut deps, &env, 1000);
+    let settled = raise_dispute(&mut deps, &env, settled_job);
+    resolve(&mut deps, &env, &settled, true).unwrap();
+    resolve(&mut deps, &env, &settled, true).unwrap();
+
+    assert_eq!(
+        dispute_ids_by_status(&deps, &env, DisputeStatus::Raised, None, None),
+        raised
+    );
+    assert_eq!(
+        dispute_ids_by_status(&deps, &env, DisputeStatus::ResolutionProposed, None, None),
+        vec![proposed]
+    );
+    assert_eq!(
+        dispute_ids_by_status(&deps, &env, DisputeStatus::Resolved, None, None),
+        vec![settled]
+    );
+    assert!(dispute_ids_by_status(&deps, &env, DisputeStatus::Appealed, None, None).is_empty());
+
+    // The work queue pages through the raised disputes
+    let first_page = dispute_ids_by_status(&deps, &env, DisputeStatus::Raised, None, Some(1));
+    assert_eq!(first_page, raised[..1]);
+    let second_page = dispute_ids_by_status(
+        &deps,
+        &env,
+        DisputeStatus::Raised,
+        Some(first_page[0].clone()),
+        Some(1),
+    );
+    assert_eq!(second_page, raised[1..]);
+}
+
+#[test]
+fn dispute_evidence_is_bounded() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    let raise = |evidence: Vec<String>| ExecuteMsg::RaiseDispute {
+        job_id,
+        reason: "Client stopped responding".to_string(),
+        evidence,
+    };
+
+    let too_many = vec!["ipfs://evidence".to_string(); 11];
+    let err = exec(&mut deps, &env, FREELANCER, &[], raise(too_many)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+
+    let too_long = vec![format!("https://{}", "a".repeat(512))];
+    let err = exec(&mut deps, &env, FREELANCER, &[], raise(too_long)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+
+    let blank = vec!["not a url".to_string()];
+    let err = exec(&mut deps, &env, FREELANCER, &[], raise(blank)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+
+    let ok = vec!["ipfs://bafyevidence".to_string(); 10];
+    exec(&mut deps, &env, FREELANCER, &[], raise(ok)).unwrap();
+}
+
+#[test]
+fn dispute_payout_reports_fee_and_payout_recipients() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    let dispute_id = raise_dispute(&mut deps, &env, job_id);
+
+    resolve(&mut deps, &env, &dispute_id, false).unwrap();
+    let res = resolve(&mut deps, &env, &dispute_id, false).unwrap();
+
+    assert_eq!(attr_value(&res, "fee_recipient").as_deref(), Some(ADMIN));
+    assert_eq!(attr_value(&res, "fee_amount").as_deref(), Some("50"));
+    assert_eq!(
+        attr_value(&res, "payout_recipient").as_deref(),
+        Some(CLIENT)
+    );
+    assert_eq!(attr_value(&res, "net_payout").as_deref(), Some("950"));
+}
+
+fn require_dispute_deposit(deps: &mut TestDeps, env: &Env, amount: u128) {
+    exec(
+        deps,
+        env,
+        ADMIN,
+        &[],
+        ExecuteMsg::UpdateConfig {
+            platform_fee_percent: None,
+            min_escrow_amount: None,
+            dispute_period_days: None,
+            max_job_duration_days: None,
+            treasury: None,
+            dispute_timeout_release_to_freelancer: None,
+            max_proposals_per_job: None,
+            job_archive_retention_days: None,
+            fee_split_bps: None,
+            max_bounty_reward: None,
+            edit_grace_period_days: None,
+            proposal_validity_days: None,
+            min_edit_interval_seconds: None,
+            dispute_deposit: Some(Uint128::new(amount)),
+            reputation_half_life_days: None,
+            dispute_cooldown_seconds: None,
+            max_open_jobs_per_user: None,
+        },
+    )
+    .unwrap();
+}
+
+fn raise_dispute_with_deposit(deps: &mut TestDeps, env: &Env, job_id: u64) -> String {
+    let raise = ExecuteMsg::RaiseDispute {
+        job_id,
+        reason: "Client stopped responding".to_string(),
+        evidence: vec![],
+    };
+    let err = exec(deps, env, FREELANCER, &[], raise.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidFunds { .. }));
+    let res = exec(deps, env, FREELANCER, &coins(100, DENOM), raise).unwrap();
+    assert_eq!(attr_value(&res, "deposit").as_deref(), Some("100uxion"));
+
+    let disputes: DisputesResponse = query_as(deps, env, QueryMsg::GetJobDisputes { job_id });
+    disputes.disputes[0].id.clone()
+}
+
+fn accrued_fees(deps: &TestDeps, env: &Env) -> Uint128 {
+    query_as::<AccruedFeesResponse>(deps, env, QueryMsg::GetAccruedFees { denom: None }).amount
+}
+
+#[test]
+fn dispute_deposit_is_refunded_when_the_raiser_prevails() {
+    let (mut deps, env) = setup_contract();
+    require_dispute_deposit(&mut deps, &env, 100);
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    let dispute_id = raise_dispute_with_deposit(&mut deps, &env, job_id);
+
+    resolve(&mut deps, &env, &dispute_id, true).unwrap();
+    let res = resolve(&mut deps, &env, &dispute_id, true).unwrap();
+    assert_eq!(
+        attr_value(&res, "deposit_refunded").as_deref(),
+        Some("true")
+    );
+    assert!(res.messages.iter().any(|msg| msg.msg
+        == CosmosMsg::Bank(BankMsg::Send {
+            to_address: FREELANCER.to_string(),
+            amount: coins(100, DENOM),
+        })));
+    assert_eq!(accrued_fees(&deps, &env), Uint128::new(50));
+}
+
+#[test]
+fn dispute_deposit_is_forfeited_when_the_raiser_loses() {
+    let (mut deps, env) = setup_contract();
+    require_dispute_deposit(&mut deps, &env, 100);
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    let dispute_id = raise_dispute_with_deposit(&mut deps, &env, job_id);
+
+    resolve(&mut deps, &env, &dispute_id, false).unwrap();
+    let res = resolve(&mut deps, &env, &dispute_id, false).unwrap();
+    assert_eq!(
+        attr_value(&res, "deposit_refunded").as_deref(),
+        Some("false")
+    );
+    // Only the escrow refund to the client is paid out
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(first_recipient(&res), CLIENT);
+    assert_eq!(accrued_fees(&deps, &env), Uint128::new(150));
+}
+
+#[test]
+fn dispute_fees_accrue_in_the_escrow_denom() {
+    let (mut deps, env) = setup_contract();
+    exec(
+        &mut deps,
+        &env,
+        ADMIN,
+        &[],
+        ExecuteMsg::SetAllowedDenoms {
+            denoms: vec![DENOM.to_string(), "uatom".to_string()],
+        },
+    )
+    .unwrap();
+    require_dispute_deposit(&mut deps, &env, 100);
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, "uatom"),
+        post_job_in(Denom::Native("uatom".to_string())),
+    )
+    .unwrap();
+    let job_id: u64 = attr_value(&res, "id").unwrap().parse().unwrap();
+    let proposal_id = submit_proposal(&mut deps, &env, FREELANCER, job_id);
+    accept_proposal(&mut deps, &env, CLIENT, job_id, proposal_id);
+
+    // The deposit is taken in the escrow's denom and forfeited alongside its fee
+    exec(
+        &mut deps,
+        &env,
+        FREELANCER,
+        &coins(100, "uatom"),
+        ExecuteMsg::RaiseDispute {
+            job_id,
+            reason: "Client stopped responding".to_string(),
+            evidence: vec![],
+        },
+    )
+    .unwrap();
+    let disputes: DisputesResponse = query_as(&deps, &env, QueryMsg::GetJobDisputes { job_id });
+    let dispute_id = disputes.disputes[0].id.clone();
+    resolve(&mut deps, &env, &dispute_id, false).unwrap();
+    resolve(&mut deps, &env, &dispute_id, false).unwrap();
+
+    let uatom: AccruedFeesResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetAccruedFees {
+            denom: Some(Denom::Native("uatom".to_string())),
+        },
+    );
+    assert_eq!(uatom.amount, Uint128::new(150));
+    assert!(accrued_fees(&deps, &env).is_zero());
+}
+
+#[test]
+fn cw20_escrow_fees_accrue_and_withdraw_in_the_token() {
+    let (mut deps, env) = setup_contract();
+    let token = Denom::Cw20(Addr::unchecked("token"));
+    exec(
+        &mut deps,
+        &env,
+        ADMIN,
+        &[],
+        ExecuteMsg::SetAllowedCw20Tokens {
+            tokens: vec!["token".to_string()],
+        },
+    )
+    .unwrap();
+    require_dispute_deposit(&mut deps, &env, 100);
+    exec(&mut deps, &env, CLIENT, &[], post_job_in(token.clone())).unwrap();
+    let job_id = 0;
+    exec(
+        &mut deps,
+        &env,
+        "token",
+        &[],
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: CLIENT.to_string(),
+            amount: Uint128::new(1000),
+            msg: to_json_binary(&ReceiveMsg::FundJobEscrow { job_id }).unwrap(),
+        }),
+    )
+    .unwrap();
+    let proposal_id = submit_proposal(&mut deps, &env, FREELANCER, job_id);
+    accept_proposal(&mut deps, &env, CLIENT, job_id, proposal_id);
+
+    // The deposit on a token escrow is native, so the two land in separate buckets
+    let dispute_id = raise_dispute_with_deposit(&mut deps, &env, job_id);
+    resolve(&mut deps, &env, &dispute_id, false).unwrap();
+    resolve(&mut deps, &env, &dispute_id, false).unwrap();
+    let in_token: AccruedFeesResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetAccruedFees {
+            denom: Some(token.clone()),
+        },
+    );
+    assert_eq!(in_token.amount, Uint128::new(50));
+    assert_eq!(accrued_fees(&deps, &env), Uint128::new(100));
+
+    // Withdrawal checks the token balance and pays the treasury in the token
+    deps.querier.update_wasm(|_| {
+        SystemResult::Ok(ContractResult::Ok(
+            to_json_binary(&BalanceResponse {
+                balance: Uint128::new(50),
+            })
+            .unwrap(),
+        ))
+    });
+    let res = exec(
+        &mut deps,
+        &env,
+        ADMIN,
+        &[],
+        ExecuteMsg::WithdrawFees {
+            denom: Some(token.clone()),
+            amount: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "token".to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::new(50),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(accrued_fees(&deps, &env), Uint128::new(100));
+}
+
+#[test]
+fn dispute_detail_joins_job_and_escrow() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    let dispute_id = raise_dispute(&mut deps, &env, job_id);
+
+    let detail: DisputeDetailResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetDisputeDetail {
+            dispute_id: dispute_id.clone(),
+        },
+    );
+    assert_eq!(detail.dispute.id, dispute_id);
+    assert_eq!(detail.job.id, job_id);
+    let escrow = detail.escrow.unwrap();
+    assert_eq!(escrow.amount, Uint128::new(950));
+    assert_eq!(escrow.denom, Denom::Native(DENOM.to_string()));
+    assert_eq!(escrow.freelancer.as_str(), FREELANCER);
+}
+
+#[test]
+fn disputes_from_one_user_respect_the_cooldown() {
+    let (mut deps, env) = setup_contract();
+    exec(
+        &mut deps,
+        &env,
+        ADMIN,
+        &[],
+        ExecuteMsg::UpdateConfig {
+            platform_fee_percent: None,
+            min_escrow_amount: None,
+            dispute_period_days: None,
+            max_job_duration_days: None,
+            treasury: None,
+            dispute_timeout_release_to_freelancer: None,
+            max_proposals_per_job: None,
+            job_archive_retention_days: None,
+            fee_split_bps: None,
+            max_bounty_reward: None,
+            edit_grace_period_days: None,
+            proposal_validity_days: None,
+            min_edit_interval_seconds: None,
+            dispute_deposit: None,
+            reputation_half_life_days: None,
+            dispute_cooldown_seconds: Some(3600),
+            max_open_jobs_per_user: None,
+        },
+    )
+    .unwrap();
+
+    let first = assigned_job(&mut deps, &env, 1000);
+    let second = assigned_job(&mut deps, &env, 1000);
+    raise_dispute(&mut deps, &env, first);
+
+    // A different job doesn't reset the clock
+    let raise = ExecuteMsg::RaiseDispute {
+        job_id: second,
+        reason: "Client stopped responding".to_string(),
+        evidence: vec![],
+    };
+    let err = exec(&mut deps, &env, FREELANCER, &[], raise.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DisputeCooldown {
+            retry_after: env.block.time.plus_seconds(3600),
+        }
+    );
+
+    let mut later = env.clone();
+    later.block.time = later.block.time.plus_seconds(3600);
+    exec(&mut deps, &later, FREELANCER, &[], raise).unwrap();
+}
+
+#[test]
+fn released_escrow_cannot_be_disputed() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    complete_job(&mut deps, &env, job_id);
+
+    // A dispute here could later pay the escrow out a second time
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::RaiseDispute {
+            job_id,
+            reason: "Changed my mind".to_string(),
+            evidence: vec![],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidInput {
+            error: "Escrow already released".to_string(),
+        }
+    );
+    let disputes: DisputesResponse = query_as(&deps, &env, QueryMsg::GetJobDisputes { job_id });
+    assert!(disputes.disputes.is_empty());
+}
@@ -0,0 +1,1161 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{coin, coins, to_json_binary, Addr, BankMsg, CosmosMsg, Env, Uint128, WasmMsg};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, Denom};
+use xworks_freelance_contract::msg::{
+    EscrowResponse, ExecuteMsg, JobResponse, QueryMsg, ReceiveMsg, SolvencyResponse,
+    UserStatsResponse,
+};
+use xworks_freelance_contract::state::{JobStatus, ReleasePolicy};
+use xworks_freelance_contract::ContractError;
+
+#[test]
+fn escrow_at_configured_minimum_is_accepted() {
+    let (mut deps, env) = setup_contract();
+    let job_id = post_job(&mut deps, &env, CLIENT, 1000);
+
+    let escrow = query_as::<EscrowResponse>(&deps, &env, QueryMsg::GetJobEscrow { job_id })
+        .escrow
+        .unwrap();
+    assert_eq!(escrow.amount + escrow.platform_fee, Uint128::new(1000));
+}
+
+#[test]
+fn escrow_below_configured_minimum_is_rejected() {
+    let (mut deps, env) = setup_contract();
+
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(999, DENOM),
+        post_job_msg(999),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::EscrowBelowMinimum {
+            min: Uint128::new(1000)
+        }
+    );
+}
+
+#[test]
+fn escrow_above_configured_minimum_is_accepted() {
+    let (mut deps, env) = setup_contract();
+    let job_id = post_job(&mut deps, &env, CLIENT, 1001);
+
+    let escrow = query_as::<EscrowResponse>(&deps, &env, QueryMsg::GetJobEscrow { job_id })
+        .escrow
+        .unwrap();
+    assert_eq!(escrow.amount + escrow.platform_fee, Uint128::new(1001));
+}
+
+#[test]
+fn mismatched_job_funding_names_expected_and_received_funds() {
+    let (mut deps, env) = setup_contract();
+
+    let cases = [
+        (vec![coin(1000, "uatom")], "1000uatom"),
+        (
+            vec![coin(1000, DENOM), coin(5, "uatom")],
+            "1000uxion, 5uatom",
+        ),
+        (coins(1200, DENOM), "1200uxion"),
+    ];
+    for (funds, received) in cases {
+        let err = exec(&mut deps, &env, CLIENT, &funds, post_job_msg(1000)).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidFunds {
+                expected: "1000uxion".to_string(),
+                received: received.to_string(),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            format!("Invalid funds: expected 1000uxion, received {received}")
+        );
+    }
+}
+
+#[test]
+fn jobs_are_posted_only_in_whitelisted_denoms() {
+    let (mut deps, env) = setup_contract();
+
+    for (denom, rejected) in [
+        (Denom::Native("uatom".to_string()), "uatom"),
+        (Denom::Cw20(Addr::unchecked("token")), "token"),
+    ] {
+        let err = exec(&mut deps, &env, CLIENT, &[], post_job_in(denom)).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::DenomNotAllowed {
+                denom: rejected.to_string()
+            }
+        );
+    }
+
+    exec(
+        &mut deps,
+        &env,
+        ADMIN,
+        &[],
+        ExecuteMsg::SetAllowedDenoms {
+            denoms: vec![DENOM.to_string(), "uatom".to_string()],
+        },
+    )
+    .unwrap();
+    let uatom = Denom::Native("uatom".to_string());
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, "uatom"),
+        post_job_in(uatom.clone()),
+    )
+    .unwrap();
+
+    let job = query_as::<JobResponse>(&deps, &env, QueryMsg::GetJob { job_id: 0 })
+        .job
+        .unwrap();
+    assert_eq!(job.denom, uatom);
+    let escrow = query_as::<EscrowResponse>(&deps, &env, QueryMsg::GetJobEscrow { job_id: 0 })
+        .escrow
+        .unwrap();
+    assert_eq!(escrow.denom, uatom);
+}
+
+#[test]
+fn escrow_funding_must_match_the_job_denom() {
+    let (mut deps, env) = setup_contract();
+    exec(
+        &mut deps,
+        &env,
+        ADMIN,
+        &[],
+        ExecuteMsg::SetAllowedDenoms {
+            denoms: vec![DENOM.to_string(), "uatom".to_string()],
+        },
+    )
+    .unwrap();
+
+    exec(&mut deps, &env, CLIENT, &[], post_job_msg(1000)).unwrap();
+    let job_id = 0;
+    let job = query_as::<JobResponse>(&deps, &env, QueryMsg::GetJob { job_id })
+        .job
+        .unwrap();
+    assert_eq!(job.denom, Denom::Native(DENOM.to_string()));
+
+    let fund = ExecuteMsg::CreateEscrowNative {
+        job_id,
+        amount: Uint128::new(1000),
+        refund_overpayment: None,
+    };
+    // Whitelisted, but not what the job was posted for
+    let err = exec(&mut deps, &env, CLIENT, &coins(1000, "uatom"), fund.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DenomMismatch {
+            expected: DENOM.to_string(),
+            got: "uatom".to_string(),
+        }
+    );
+
+    exec(&mut deps, &env, CLIENT, &coins(1000, DENOM), fund).unwrap();
+    let escrow = query_as::<EscrowResponse>(&deps, &env, QueryMsg::GetJobEscrow { job_id })
+        .escrow
+        .unwrap();
+    assert_eq!(escrow.denom, Denom::Native(DENOM.to_string()));
+}
+
+const TOKEN: &str = "token";
+
+fn fund_with_token(
+    deps: &mut TestDeps,
+    env: &Env,
+    token: &str,
+    job_id: u64,
+    amount: u128,
+) -> Result<cosmwasm_std::Response, ContractError> {
+    exec(
+        deps,
+        env,
+        token,
+        &[],
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: CLIENT.to_string(),
+            amount: Uint128::new(amount),
+            msg: to_json_binary(&ReceiveMsg::FundJobEscrow { job_id }).unwrap(),
+        }),
+    )
+}
+
+#[test]
+fn cw20_job_is_funded_and_paid_in_its_token() {
+    let (mut deps, env) = setup_contract();
+    exec(
+        &mut deps,
+        &env,
+        ADMIN,
+        &[],
+        ExecuteMsg::SetAllowedCw20Tokens {
+            tokens: vec![TOKEN.to_string(), "othertoken".to_string()],
+        },
+    )
+    .unwrap();
+    let token = Denom::Cw20(Addr::unchecked(TOKEN));
+
+    // Token jobs are listed unfunded and funded through the token's Send hook
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, DENOM),
+        post_job_in(token.clone()),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidFunds { .. }));
+    exec(&mut deps, &env, CLIENT, &[], post_job_in(token.clone())).unwrap();
+    let job_id = 0;
+
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, DENOM),
+        ExecuteMsg::CreateEscrowNative {
+            job_id,
+            amount: Uint128::new(1000),
+            refund_overpayment: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DenomMismatch {
+            expected: TOKEN.to_string(),
+            got: DENOM.to_string(),
+        }
+    );
+    let err = fund_with_token(&mut deps, &env, "othertoken", job_id, 1000).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DenomMismatch {
+            expected: TOKEN.to_string(),
+            got: "othertoken".to_string(),
+        }
+    );
+
+    // The tokens were sent to the contract already, so funding moves nothing
+    let res = fund_with_token(&mut deps, &env, TOKEN, job_id, 1000).unwrap();
+    assert!(res.messages.is_empty());
+    let escrow = query_as::<EscrowResponse>(&deps, &env, QueryMsg::GetJobEscrow { job_id })
+        .escrow
+        .unwrap();
+    assert_eq!(escrow.denom, token);
+    assert_eq!(escrow.client.as_str(), CLIENT);
+
+    let proposal_id = submit_proposal(&mut deps, &env, FREELANCER, job_id);
+    accept_proposal(&mut deps, &env, CLIENT, job_id, proposal_id);
+    let res = complete_job(&mut deps, &env, job_id);
+    let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+    assert_eq!(
+        msgs,
+        vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: TOKEN.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: FREELANCER.to_string(),
+                amount: Uint128::new(950),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+}
+
+#[test]
+fn deleting_a_cw20_job_refunds_in_its_token() {
+    let (mut deps, env) = setup_contract();
+    exec(
+        &mut deps,
+        &env,
+        ADMIN,
+        &[],
+        ExecuteMsg::SetAllowedCw20Tokens {
+            tokens: vec![TOKEN.to_string()],
+        },
+    )
+    .unwrap();
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        post_job_in(Denom::Cw20(Addr::unchecked(TOKEN))),
+    )
+    .unwrap();
+    let job_id = 0;
+    fund_with_token(&mut deps, &env, TOKEN, job_id, 1000).unwrap();
+
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::DeleteJob { job_id },
+    )
+    .unwrap();
+    let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+    assert_eq!(
+        msgs,
+        vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: TOKEN.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: CLIENT.to_string(),
+                amount: Uint128::new(1000),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+}
+
+#[test]
+fn duplicate_escrow_creation_is_rejected() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+
+    // Posting a funded job already opened its escrow, so every later
+    // creation attempt for the same job must fail
+    for _ in 0..2 {
+        let err = exec(
+            &mut deps,
+            &env,
+            CLIENT,
+            &coins(1000, DENOM),
+            ExecuteMsg::CreateEscrowNative {
+                job_id,
+                amount: Uint128::new(1000),
+                refund_overpayment: None,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::EscrowAlreadyExists { job_id });
+    }
+}
+
+#[test]
+fn unfunded_job_must_be_funded_before_accepting() {
+    let (mut deps, env) = setup_contract();
+
+    // Listing a paid job without funds leaves it unescrowed
+    let res = exec(&mut deps, &env, CLIENT, &[], post_job_msg(1000)).unwrap();
+    assert_eq!(attr_value(&res, "escrow_id"), None);
+    let job_id = 0;
+    let escrow: EscrowResponse = query_as(&deps, &env, QueryMsg::GetJobEscrow { job_id });
+    assert!(escrow.escrow.is_none());
+
+    let proposal_id = submit_proposal(&mut deps, &env, FREELANCER, job_id);
+    let accept = ExecuteMsg::AcceptProposal {
+        job_id,
+        proposal_id,
+    };
+    let err = exec(&mut deps, &env, CLIENT, &[], accept.clone()).unwrap_err();
+    assert_eq!(err, ContractError::JobNotFunded { job_id });
+
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, DENOM),
+        ExecuteMsg::CreateEscrowNative {
+            job_id,
+            amount: Uint128::new(1000),
+            refund_overpayment: None,
+        },
+    )
+    .unwrap();
+    // Nobody is assigned yet, so the escrow has no freelancer until the accept
+    let escrow: EscrowResponse = query_as(&deps, &env, QueryMsg::GetJobEscrow { job_id });
+    assert_eq!(escrow.escrow.unwrap().freelancer.as_str(), "");
+
+    exec(&mut deps, &env, CLIENT, &[], accept).unwrap();
+    let escrow: EscrowResponse = query_as(&deps, &env, QueryMsg::GetJobEscrow { job_id });
+    assert_eq!(escrow.escrow.unwrap().freelancer.as_str(), FREELANCER);
+}
+
+#[test]
+fn proposal_without_escrow_agreement_is_accepted_on_unfunded_job() {
+    let (mut deps, env) = setup_contract();
+    exec(&mut deps, &env, CLIENT, &[], post_job_msg(1000)).unwrap();
+    let job_id = 0;
+
+    let mut msg = submit_proposal_msg(job_id);
+    if let ExecuteMsg::SubmitProposal {
+        agreed_to_escrow, ..
+    } = &mut msg
+    {
+        *agreed_to_escrow = false;
+    }
+    let res = exec(&mut deps, &env, FREELANCER, &[], msg).unwrap();
+    let proposal_id: u64 = attr_value(&res, "id").unwrap().parse().unwrap();
+
+    accept_proposal(&mut deps, &env, CLIENT, job_id, proposal_id);
+    assert_eq!(job_status(&deps, &env, job_id), JobStatus::InProgress);
+    let escrow: EscrowResponse = query_as(&deps, &env, QueryMsg::GetJobEscrow { job_id });
+    assert!(escrow.escrow.is_none());
+
+    // The poster can still fund the job once work has started
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, DENOM),
+        ExecuteMsg::CreateEscrowNative {
+            job_id,
+            amount: Uint128::new(1000),
+            refund_overpayment: None,
+        },
+    )
+    .unwrap();
+    let escrow = query_as::<EscrowResponse>(&deps, &env, QueryMsg::GetJobEscrow { job_id })
+        .escrow
+        .unwrap();
+    assert_eq!(escrow.freelancer.as_str(), FREELANCER);
+}
+
+#[test]
+fn poster_can_top_up_an_active_escrow() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    let top_up = ExecuteMsg::TopUpEscrow { job_id };
+
+    let err = exec(
+        &mut deps,
+        &env,
+        FREELANCER,
+        &coins(500, DENOM),
+        top_up.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(500, "uatom"),
+        top_up.clone(),
+    )
+    .unwrap_err();
+
+    let res = exec(&mut deps, &env, CLIENT, &coins(500, DENOM), top_up).unwrap();
+    assert_eq!(attr_value(&res, "budget").as_deref(), Some("1500"));
+
+    // The fee follows the new total and still comes out of the payout
+    let escrow = query_as::<EscrowResponse>(
+        &deps,
+        &env,
+        QueryMsg::GetEscrow {
+            escrow_id: format!("job_{}", job_id),
+        },
+    )
+    .escrow
+    .unwrap();
+    assert_eq!(escrow.amount, Uint128::new(1425));
+    assert_eq!(escrow.platform_fee, Uint128::new(75));
+
+    let job: JobResponse = query_as(&deps, &env, QueryMsg::GetJob { job_id });
+    assert_eq!(job.job.unwrap().budget, Uint128::new(1500));
+}
+
+#[test]
+fn released_escrow_cannot_be_topped_up() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    complete_job(&mut deps, &env, job_id);
+
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(500, DENOM),
+        ExecuteMsg::TopUpEscrow { job_id },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+}
+
+fn set_fee_terms(deps: &mut TestDeps, env: &Env, fee_percent: Option<u64>, split_bps: Option<u16>) {
+    exec(
+        deps,
+        env,
+        ADMIN,
+        &[],
+        ExecuteMsg::UpdateConfig {
+            platform_fee_percent: fee_percent,
+            min_escrow_amount: None,
+            dispute_period_days: None,
+            max_job_duration_days: None,
+            treasury: None,
+            dispute_timeout_release_to_freelancer: None,
+            max_proposals_per_job: None,
+            job_archive_retention_days: None,
+            fee_split_bps: split_bps,
+            max_bounty_reward: None,
+            edit_grace_period_days: None,
+            proposal_validity_days: None,
+            min_edit_interval_seconds: None,
+            dispute_deposit: None,
+            reputation_half_life_days: None,
+            dispute_cooldown_seconds: None,
+            max_open_jobs_per_user: None,
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn top_up_keeps_the_terms_already_escrowed() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 10_000);
+    let escrow = |deps: &TestDeps| {
+        let escrow = query_as::<EscrowResponse>(deps, &env, QueryMsg::GetJobEscrow { job_id })
+            .escrow
+            .unwrap();
+        (escrow.amount.u128(), escrow.platform_fee.u128())
+    };
+    assert_eq!(escrow(&deps), (9500, 500));
+
+    // A later fee change only prices the added budget
+    set_fee_terms(&mut deps, &env, Some(10), None);
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1, DENOM),
+        ExecuteMsg::TopUpEscrow { job_id },
+    )
+    .unwrap();
+    assert_eq!(escrow(&deps), (9501, 500));
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(100, DENOM),
+        ExecuteMsg::TopUpEscrow { job_id },
+    )
+    .unwrap();
+    assert_eq!(escrow(&deps), (9591, 510));
+
+    // With the poster paying half the fee, the coin covers the increase and that share
+    set_fee_terms(&mut deps, &env, None, Some(5_000));
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(105, DENOM),
+        ExecuteMsg::TopUpEscrow { job_id },
+    )
+    .unwrap();
+    assert_eq!(attr_value(&res, "budget").as_deref(), Some("10201"));
+    assert_eq!(escrow(&deps), (9686, 520));
+
+    // Rounding leaves some amounts unable to fund a whole budget increase
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(20, DENOM),
+        ExecuteMsg::TopUpEscrow { job_id },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidInput {
+            error: "20 does not fund a whole budget plus the poster's fee share; attach 19 or 21"
+                .to_string(),
+        }
+    );
+}
+
+#[test]
+fn deleting_a_job_refunds_only_its_own_deposit() {
+    let (mut deps, env) = setup_contract();
+    set_fee_terms(&mut deps, &env, None, Some(5_000));
+
+    // The poster paid the budget plus a 25 share of the fee, and gets all of it back
+    let funded = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1025, DENOM),
+        post_job_msg(1000),
+    )
+    .unwrap();
+    let job_id: u64 = attr_value(&funded, "id").unwrap().parse().unwrap();
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::DeleteJob { job_id },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: CLIENT.to_string(),
+            amount: coins(1025, DENOM),
+        })
+    );
+    let escrow = query_as::<EscrowResponse>(
+        &deps,
+        &env,
+        QueryMsg::GetEscrow {
+            escrow_id: format!("job_{}", job_id),
+        },
+    )
+    .escrow
+    .unwrap();
+    assert!(escrow.released);
+
+    // An unfunded listing holds nothing to refund
+    let unfunded = exec(&mut deps, &env, CLIENT, &[], post_job_msg(1000)).unwrap();
+    let job_id: u64 = attr_value(&unfunded, "id").unwrap().parse().unwrap();
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::DeleteJob { job_id },
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+}
+
+fn submit_work(deps: &mut TestDeps, env: &Env, job_id: u64) {
+    exec(
+        deps,
+        env,
+        FREELANCER,
+        &[],
+        ExecuteMsg::SubmitWork { job_id },
+    )
+    .unwrap();
+}
+
+fn job_status(deps: &TestDeps, env: &Env, job_id: u64) -> JobStatus {
+    query_as::<JobResponse>(deps, env, QueryMsg::GetJob { job_id })
+        .job
+        .unwrap()
+        .status
+}
+
+#[test]
+fn client_approval_releases_submitted_work() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+
+    // Nothing to approve until the freelancer delivers
+    let approve = ExecuteMsg::ApproveCompletion { job_id };
+    exec(&mut deps, &env, CLIENT, &[], approve.clone()).unwrap_err();
+
+    submit_work(&mut deps, &env, job_id);
+    assert_eq!(job_status(&deps, &env, job_id), JobStatus::WorkSubmitted);
+
+    let err = exec(&mut deps, &env, FREELANCER, &[], approve.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = exec(&mut deps, &env, CLIENT, &[], approve).unwrap();
+    assert_eq!(attr_value(&res, "escrow_released").as_deref(), Some("true"));
+    assert_eq!(attr_value(&res, "status").as_deref(), Some("completed"));
+    assert_eq!(job_status(&deps, &env, job_id), JobStatus::Completed);
+}
+
+#[test]
+fn client_can_dispute_submitted_work() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    submit_work(&mut deps, &env, job_id);
+
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::RaiseDispute {
+            job_id,
+            reason: "Deliverables are incomplete".to_string(),
+            evidence: vec![],
+        },
+    )
+    .unwrap();
+    assert_eq!(job_status(&deps, &env, job_id), JobStatus::Disputed);
+
+    // A disputed job can no longer be auto-released
+    let mut later = env.clone();
+    later.block.time = later.block.time.plus_seconds(4 * 24 * 60 * 60);
+    exec(
+        &mut deps,
+        &later,
+        FREELANCER,
+        &[],
+        ExecuteMsg::CompleteJob { job_id },
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn unanswered_submission_is_claimable_after_review_window() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    let claim = ExecuteMsg::CompleteJob { job_id };
+
+    let err = exec(&mut deps, &env, FREELANCER, &[], claim.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+
+    submit_work(&mut deps, &env, job_id);
+    let err = exec(&mut deps, &env, FREELANCER, &[], claim.clone()).unwrap_err();
+    assert_eq!(err, ContractError::DisputePeriodActive {});
+
+    // The review window follows the configured dispute period
+    let mut later = env.clone();
+    later.block.time = later.block.time.plus_seconds(3 * 24 * 60 * 60);
+    let res = exec(&mut deps, &later, FREELANCER, &[], claim).unwrap();
+    assert_eq!(attr_value(&res, "method").as_deref(), Some("complete_job"));
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(job_status(&deps, &later, job_id), JobStatus::Completed);
+}
+
+#[test]
+fn release_pays_the_proposal_payout_address() {
+    let (mut deps, env) = setup_contract();
+    let job_id = post_job(&mut deps, &env, CLIENT, 1000);
+
+    let mut msg = submit_proposal_msg(job_id);
+    if let ExecuteMsg::SubmitProposal { payout_address, .. } = &mut msg {
+        *payout_address = Some("treasurydao".to_string());
+    }
+    let res = exec(&mut deps, &env, FREELANCER, &[], msg).unwrap();
+    let proposal_id = attr_value(&res, "id").unwrap().parse().unwrap();
+    accept_proposal(&mut deps, &env, CLIENT, job_id, proposal_id);
+
+    submit_work(&mut deps, &env, job_id);
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::ApproveCompletion { job_id },
+    )
+    .unwrap();
+
+    // The net payout goes to the override while the freelancer keeps the credit
+    match &res.messages[0].msg {
+        CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+            assert_eq!(to_address, "treasurydao");
+            assert_eq!(amount, &coins(950, DENOM));
+        }
+        other => panic!("unexpected message {:?}", other),
+    }
+    assert_eq!(attr_value(&res, "freelancer").as_deref(), Some(FREELANCER));
+}
+
+#[test]
+fn completion_reports_fee_and_payout_recipients() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    submit_work(&mut deps, &env, job_id);
+
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::ApproveCompletion { job_id },
+    )
+    .unwrap();
+
+    // The treasury defaults to the admin when none is configured
+    assert_eq!(attr_value(&res, "fee_recipient").as_deref(), Some(ADMIN));
+    assert_eq!(attr_value(&res, "fee_amount").as_deref(), Some("50"));
+    assert_eq!(
+        attr_value(&res, "payout_recipient").as_deref(),
+        Some(FREELANCER)
+    );
+    assert_eq!(attr_value(&res, "net_payout").as_deref(), Some("950"));
+}
+
+fn locked(deps: &TestDeps, env: &Env, escrow_id: &str) -> Uint128 {
+    let escrow = query_as::<EscrowResponse>(
+        deps,
+        env,
+        QueryMsg::GetEscrow {
+            escrow_id: escrow_id.to_string(),
+        },
+    )
+    .escrow
+    .unwrap();
+    escrow.amount + escrow.platform_fee
+}
+
+#[test]
+fn solvency_sums_unreleased_escrows_per_denom() {
+    let (mut deps, env) = setup_contract();
+    exec(
+        &mut deps,
+        &env,
+        ADMIN,
+        &[],
+        ExecuteMsg::SetAllowedDenoms {
+            denoms: vec![DENOM.to_string(), "uatom".to_string()],
+        },
+    )
+    .unwrap();
+
+    let released = assigned_job(&mut deps, &env, 1000);
+    let open = post_job(&mut deps, &env, CLIENT, 2000);
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, "uatom"),
+        create_bounty_msg(1000, &[100]),
+    )
+    .unwrap();
+    let bounty_id = attr_value(&res, "id").unwrap();
+
+    let job_locked = locked(&deps, &env, &format!("job_{released}"));
+    let open_locked = locked(&deps, &env, &format!("job_{open}"));
+    let bounty_locked = locked(&deps, &env, &format!("bounty_{bounty_id}"));
+
+    let solvency: SolvencyResponse = query_as(&deps, &env, QueryMsg::GetSolvency {});
+    assert_eq!(
+        solvency.per_denom,
+        vec![
+            ("uatom".to_string(), bounty_locked),
+            (DENOM.to_string(), job_locked + open_locked),
+        ]
+    );
+    assert_eq!(
+        solvency.total_locked,
+        job_locked + open_locked + bounty_locked
+    );
+
+    // Released escrows no longer count towards what the contract must hold
+    submit_work(&mut deps, &env, released);
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::ApproveCompletion { job_id: released },
+    )
+    .unwrap();
+    let solvency: SolvencyResponse = query_as(&deps, &env, QueryMsg::GetSolvency {});
+    assert_eq!(
+        solvency.per_denom,
+        vec![
+            ("uatom".to_string(), bounty_locked),
+            (DENOM.to_string(), open_locked),
+        ]
+    );
+    assert_eq!(solvency.total_locked, open_locked + bounty_locked);
+}
+
+fn completed_job_with_policy(
+    deps: &mut TestDeps,
+    env: &Env,
+    policy: ReleasePolicy,
+) -> (u64, cosmwasm_std::Response) {
+    let mut msg = post_job_msg(1000);
+    if let ExecuteMsg::PostJob { release_policy, .. } = &mut msg {
+        *release_policy = Some(policy.clone());
+    }
+    let res = exec(deps, env, CLIENT, &coins(1000, DENOM), msg).unwrap();
+    let job_id: u64 = attr_value(&res, "id").unwrap().parse().unwrap();
+    let job = query_as::<JobResponse>(deps, env, QueryMsg::GetJob { job_id })
+        .job
+        .unwrap();
+    assert_eq!(job.release_policy, policy);
+
+    let proposal_id = submit_proposal(deps, env, FREELANCER, job_id);
+    accept_proposal(deps, env, CLIENT, job_id, proposal_id);
+    (job_id, complete_job(deps, env, job_id))
+}
+
+fn total_earned(deps: &TestDeps, env: &Env) -> Uint128 {
+    query_as::<UserStatsResponse>(
+        deps,
+        env,
+        QueryMsg::GetUserStats {
+            user: FREELANCER.to_string(),
+        },
+    )
+    .stats
+    .total_earned
+}
+
+#[test]
+fn auto_release_pays_out_on_completion() {
+    let (mut deps, env) = setup_contract();
+    let (job_id, res) = completed_job_with_policy(&mut deps, &env, ReleasePolicy::Auto);
+
+    assert_eq!(attr_value(&res, "escrow_released").as_deref(), Some("true"));
+    assert_eq!(res.messages.len(), 1);
+    let escrow = query_as::<EscrowResponse>(&deps, &env, QueryMsg::GetJobEscrow { job_id })
+        .escrow
+        .unwrap();
+    assert!(escrow.released);
+}
+
+#[test]
+fn manual_release_waits_for_the_poster() {
+    let (mut deps, env) = setup_contract();
+    let (job_id, res) = completed_job_with_policy(&mut deps, &env, ReleasePolicy::Manual);
+
+    // Completion closes the job but leaves the funds in escrow
+    assert_eq!(
+        attr_value(&res, "escrow_released").as_deref(),
+        Some("false")
+    );
+    assert!(res.messages.is_empty());
+    assert_eq!(job_status(&deps, &env, job_id), JobStatus::Completed);
+    assert_eq!(total_earned(&deps, &env), Uint128::zero());
+
+    let release = ExecuteMsg::ReleaseEscrow {
+        escrow_id: format!("job_{job_id}"),
+    };
+    let err = exec(&mut deps, &env, FREELANCER, &[], release.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = exec(&mut deps, &env, CLIENT, &[], release).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: FREELANCER.to_string(),
+            amount: coins(950, DENOM),
+        })
+    );
+    assert_eq!(total_earned(&deps, &env), Uint128::new(950));
+}
+
+#[test]
+fn both_release_policies_record_the_same_stats() {
+    let stats = |user: &str, deps: &TestDeps, env: &Env| {
+        query_as::<UserStatsResponse>(
+            deps,
+            env,
+            QueryMsg::GetUserStats {
+                user: user.to_string(),
+            },
+        )
+        .stats
+    };
+
+    let mut recorded = vec![];
+    for policy in [ReleasePolicy::Auto, ReleasePolicy::Manual] {
+        let (mut deps, env) = setup_contract();
+        let (job_id, _) = completed_job_with_policy(&mut deps, &env, policy.clone());
+        if policy == ReleasePolicy::Manual {
+            let release = ExecuteMsg::ReleaseEscrow {
+                escrow_id: format!("job_{job_id}"),
+            };
+            exec(&mut deps, &env, CLIENT, &[], release).unwrap();
+        }
+
+        // The completion counts once, whichever call pays the escrow out
+        let freelancer = stats(FREELANCER, &deps, &env);
+        assert_eq!(freelancer.total_jobs_completed, 1);
+        recorded.push((
+            freelancer.total_earned,
+            stats(CLIENT, &deps, &env).total_spent,
+        ));
+    }
+    assert_eq!(recorded[0], (Uint128::new(950), Uint128::new(950)));
+    assert_eq!(recorded[0], recorded[1]);
+}
+
+#[test]
+fn opt_in_overpayment_is_refunded() {
+    let (mut deps, env) = setup_contract();
+    exec(&mut deps, &env, CLIENT, &[], post_job_msg(1000)).unwrap();
+    let job_id = 0;
+    let fund = |refund_overpayment| ExecuteMsg::CreateEscrowNative {
+        job_id,
+        amount: Uint128::new(1000),
+        refund_overpayment,
+    };
+
+    // Strict by default
+    let err = exec(&mut deps, &env, CLIENT, &coins(1250, DENOM), fund(None)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidFunds { .. }));
+
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1250, DENOM),
+        fund(Some(true)),
+    )
+    .unwrap();
+    assert_eq!(attr_value(&res, "refunded_surplus").unwrap(), "250");
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: CLIENT.to_string(),
+            amount: coins(250, DENOM),
+        })
+    );
+    assert_eq!(locked(&deps, &env, "job_0"), Uint128::new(1000));
+}
+
+fn days_after(env: &Env, days: u64) -> Env {
+    let mut later = env.clone();
+    later.block.time = later.block.time.plus_seconds(days * 24 * 60 * 60);
+    later
+}
+
+#[test]
+fn on_time_delivery_auto_releases_after_review_window() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+
+    // Delivered on day 9 of a 10-day job, claimed after the deadline has passed
+    let res = exec(
+        &mut deps,
+        &days_after(&env, 9),
+        FREELANCER,
+        &[],
+        ExecuteMsg::SubmitWork { job_id },
+    )
+    .unwrap();
+    assert_eq!(attr_value(&res, "late").unwrap(), "false");
+
+    let later = days_after(&env, 12);
+    let res = exec(
+        &mut deps,
+        &later,
+        FREELANCER,
+        &[],
+        ExecuteMsg::CompleteJob { job_id },
+    )
+    .unwrap();
+    assert_eq!(attr_value(&res, "escrow_released").unwrap(), "true");
+    assert_eq!(job_status(&deps, &later, job_id), JobStatus::Completed);
+}
+
+#[test]
+fn late_delivery_requires_poster_approval() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+
+    let late = days_after(&env, 11);
+    let res = exec(
+        &mut deps,
+        &late,
+        FREELANCER,
+        &[],
+        ExecuteMsg::SubmitWork { job_id },
+    )
+    .unwrap();
+    assert_eq!(attr_value(&res, "late").unwrap(), "true");
+
+    // Even once the review window lapses the freelancer cannot self-complete
+    let err = exec(
+        &mut deps,
+        &days_after(&env, 20),
+        FREELANCER,
+        &[],
+        ExecuteMsg::CompleteJob { job_id },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::LateCompletionRequiresApproval {});
+    assert_eq!(job_status(&deps, &env, job_id), JobStatus::WorkSubmitted);
+
+    let res = exec(
+        &mut deps,
+        &days_after(&env, 20),
+        CLIENT,
+        &[],
+        ExecuteMsg::ApproveCompletion { job_id },
+    )
+    .unwrap();
+    assert_eq!(attr_value(&res, "escrow_released").unwrap(), "true");
+}
+
+#[test]
+fn legacy_create_escrow_funds_unescrowed_jobs() {
+    let (mut deps, env) = setup_contract();
+    exec(&mut deps, &env, CLIENT, &[], post_job_msg(1000)).unwrap();
+    let job_id = 0;
+
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, DENOM),
+        ExecuteMsg::CreateEscrow {
+            job_id,
+            refund_overpayment: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(attr_value(&res, "method").unwrap(), "create_escrow");
+    assert_eq!(locked(&deps, &env, "job_0"), Uint128::new(1000));
+}
+
+#[test]
+fn legacy_create_escrow_tops_up_an_existing_escrow() {
+    let (mut deps, env) = setup_contract();
+    let job_id = post_job(&mut deps, &env, CLIENT, 1000);
+    let legacy = ExecuteMsg::CreateEscrow {
+        job_id,
+        refund_overpayment: None,
+    };
+
+    let err = exec(&mut deps, &env, CLIENT, &[], legacy.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidFunds { .. }));
+
+    let res = exec(&mut deps, &env, CLIENT, &coins(500, DENOM), legacy).unwrap();
+    assert_eq!(attr_value(&res, "method").unwrap(), "top_up_escrow");
+    assert_eq!(attr_value(&res, "budget").unwrap(), "1500");
+    assert_eq!(locked(&deps, &env, "job_0"), Uint128::new(1500));
+}
+
+#[test]
+fn legacy_top_up_covers_the_posters_fee_share() {
+    let (mut deps, env) = setup_contract();
+    set_fee_terms(&mut deps, &env, None, Some(5_000));
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1025, DENOM),
+        post_job_msg(1000),
+    )
+    .unwrap();
+    let job_id: u64 = attr_value(&res, "id").unwrap().parse().unwrap();
+
+    // The coin is the added budget plus the poster's 25 half of its 50 fee
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1025, DENOM),
+        ExecuteMsg::CreateEscrow {
+            job_id,
+            refund_overpayment: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(attr_value(&res, "method").unwrap(), "top_up_escrow");
+    assert_eq!(attr_value(&res, "budget").unwrap(), "2000");
+    let escrow = query_as::<EscrowResponse>(&deps, &env, QueryMsg::GetJobEscrow { job_id })
+        .escrow
+        .unwrap();
+    assert_eq!(escrow.amount, Uint128::new(1950));
+    assert_eq!(escrow.platform_fee, Uint128::new(100));
+}
@@ -0,0 +1,357 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
+use cosmwasm_std::{coin, coins, BankMsg, CosmosMsg, Env, Uint128};
+use cw20::Denom;
+use xworks_freelance_contract::msg::{
+    AccruedFeesResponse, EscrowResponse, ExecuteMsg, FeePreviewResponse, QueryMsg,
+};
+use xworks_freelance_contract::ContractError;
+
+const TREASURY: &str = "treasury";
+
+fn accrued(deps: &TestDeps, env: &Env) -> Uint128 {
+    let res: AccruedFeesResponse = query_as(deps, env, QueryMsg::GetAccruedFees { denom: None });
+    res.amount
+}
+
+fn completed_job(deps: &mut TestDeps, env: &Env, budget: u128) {
+    let job_id = assigned_job(deps, env, budget);
+    complete_job(deps, env, job_id);
+}
+
+fn withdraw(
+    deps: &mut TestDeps,
+    env: &Env,
+    sender: &str,
+    amount: Option<u128>,
+) -> Result<cosmwasm_std::Response, ContractError> {
+    exec(
+        deps,
+        env,
+        sender,
+        &[],
+        ExecuteMsg::WithdrawFees {
+            denom: None,
+            amount: amount.map(Uint128::new),
+        },
+    )
+}
+
+#[test]
+fn completion_accrues_fee_instead_of_paying_admin() {
+    let (mut deps, env) = setup_contract();
+    completed_job(&mut deps, &env, 1000);
+
+    assert_eq!(accrued(&deps, &env), Uint128::new(50));
+}
+
+#[test]
+fn admin_withdraws_accrued_fees_to_treasury() {
+    let (mut deps, env) = setup_contract();
+    exec(
+        &mut deps,
+        &env,
+        ADMIN,
+        &[],
+        ExecuteMsg::UpdateConfig {
+            platform_fee_percent: None,
+            min_escrow_amount: None,
+            dispute_period_days: None,
+            max_job_duration_days: None,
+            treasury: Some(TREASURY.to_string()),
+            dispute_timeout_release_to_freelancer: None,
+            max_proposals_per_job: None,
+            job_archive_retention_days: None,
+            fee_split_bps: None,
+            max_bounty_reward: None,
+            edit_grace_period_days: None,
+            proposal_validity_days: None,
+            min_edit_interval_seconds: None,
+            dispute_deposit: None,
+            reputation_half_life_days: None,
+            dispute_cooldown_seconds: None,
+            max_open_jobs_per_user: None,
+        },
+    )
+    .unwrap();
+    completed_job(&mut deps, &env, 1000);
+    completed_job(&mut deps, &env, 2000);
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, coins(10_000, DENOM));
+
+    let err = withdraw(&mut deps, &env, CLIENT, None).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Partial withdrawal
+    let res = withdraw(&mut deps, &env, ADMIN, Some(100)).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: TREASURY.to_string(),
+            amount: coins(100, DENOM),
+        })
+    );
+    assert_eq!(accrued(&deps, &env), Uint128::new(50));
+
+    // Cannot take more than has accrued
+    let err = withdraw(&mut deps, &env, ADMIN, Some(51)).unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientFunds { .. }));
+
+    // Default sweeps the remainder
+    let res = withdraw(&mut deps, &env, ADMIN, None).unwrap();
+    assert_eq!(attr_value(&res, "amount"), Some("50".to_string()));
+    assert!(accrued(&deps, &env).is_zero());
+
+    let err = withdraw(&mut deps, &env, ADMIN, None).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+}
+
+#[test]
+fn withdrawal_is_limited_by_contract_balance() {
+    let (mut deps, env) = setup_contract();
+    completed_job(&mut deps, &env, 1000);
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, coins(20, DENOM));
+
+    let err = withdraw(&mut deps, &env, ADMIN, None).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientFunds {
+            expected: "50".to_string(),
+            actual: "20".to_string(),
+        }
+    );
+    assert_eq!(accrued(&deps, &env), Uint128::new(50));
+}
+
+fn accrued_in(deps: &TestDeps, env: &Env, denom: &str) -> Uint128 {
+    let res: AccruedFeesResponse = query_as(
+        deps,
+        env,
+        QueryMsg::GetAccruedFees {
+            denom: Some(Denom::Native(denom.to_string())),
+        },
+    );
+    res.amount
+}
+
+#[test]
+fn fees_are_kept_and_withdrawn_per_denom() {
+    let (mut deps, env) = setup_contract();
+    exec(
+        &mut deps,
+        &env,
+        ADMIN,
+        &[],
+        ExecuteMsg::SetAllowedDenoms {
+            denoms: vec![DENOM.to_string(), "uatom".to_string()],
+        },
+    )
+    .unwrap();
+
+    // A uatom job completes while a uxion escrow is still held
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, "uatom"),
+        post_job_in(Denom::Native("uatom".to_string())),
+    )
+    .unwrap();
+    let job_id: u64 = attr_value(&res, "id").unwrap().parse().unwrap();
+    let proposal_id = submit_proposal(&mut deps, &env, FREELANCER, job_id);
+    accept_proposal(&mut deps, &env, CLIENT, job_id, proposal_id);
+    complete_job(&mut deps, &env, job_id);
+    assigned_job(&mut deps, &env, 1000);
+    deps.querier.update_balance(
+        MOCK_CONTRACT_ADDR,
+        vec![coin(1000, DENOM), coin(50, "uatom")],
+    );
+
+    assert_eq!(accrued_in(&deps, &env, "uatom"), Uint128::new(50));
+    assert!(accrued(&deps, &env).is_zero());
+
+    // The held uxion principal can't be swept as fees
+    let err = withdraw(&mut deps, &env, ADMIN, None).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+
+    let res = exec(
+        &mut deps,
+        &env,
+        ADMIN,
+        &[],
+        ExecuteMsg::WithdrawFees {
+            denom: Some(Denom::Native("uatom".to_string())),
+            amount: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: ADMIN.to_string(),
+            amount: coins(50, "uatom"),
+        })
+    );
+    assert!(accrued_in(&deps, &env, "uatom").is_zero());
+}
+
+#[test]
+fn fee_split_charges_poster_and_freelancer_halves() {
+    let (mut deps, env) = setup_contract();
+    exec(
+        &mut deps,
+        &env,
+        ADMIN,
+        &[],
+        ExecuteMsg::UpdateConfig {
+            platform_fee_percent: None,
+            min_escrow_amount: None,
+            dispute_period_days: None,
+            max_job_duration_days: None,
+            treasury: None,
+            dispute_timeout_release_to_freelancer: None,
+            max_proposals_per_job: None,
+            job_archive_retention_days: None,
+            fee_split_bps: Some(5_000),
+            max_bounty_reward: None,
+            edit_grace_period_days: None,
+            proposal_validity_days: None,
+            min_edit_interval_seconds: None,
+            dispute_deposit: None,
+            reputation_half_life_days: None,
+            dispute_cooldown_seconds: None,
+            max_open_jobs_per_user: None,
+        },
+    )
+    .unwrap();
+
+    // The poster's 25 half of the 50 fee is charged on top of the budget
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, DENOM),
+        post_job_msg(1000),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientFunds {
+            expected: "1025".to_string(),
+            actual: "1000".to_string(),
+        }
+    );
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1025, DENOM),
+        post_job_msg(1000),
+    )
+    .unwrap();
+    let job_id: u64 = attr_value(&res, "id").unwrap().parse().unwrap();
+    let proposal_id = submit_proposal(&mut deps, &env, FREELANCER, job_id);
+    accept_proposal(&mut deps, &env, CLIENT, job_id, proposal_id);
+
+    // The freelancer's half comes out of the payout
+    let res = complete_job(&mut deps, &env, job_id);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: FREELANCER.to_string(),
+            amount: coins(975, DENOM),
+        })
+    );
+    assert_eq!(accrued(&deps, &env), Uint128::new(50));
+}
+
+#[test]
+fn fee_preview_matches_the_funded_escrow() {
+    let (mut deps, env) = setup_contract();
+    let preview: FeePreviewResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::PreviewFees {
+            amount: Uint128::new(1000),
+            category: Some("Development".to_string()),
+        },
+    );
+    assert_eq!(preview.fee_percent_applied, 5);
+    assert_eq!(preview.deposit, Uint128::new(1000));
+
+    let job_id = post_job(&mut deps, &env, CLIENT, 1000);
+    let escrow = query_as::<EscrowResponse>(&deps, &env, QueryMsg::GetJobEscrow { job_id })
+        .escrow
+        .unwrap();
+    assert_eq!(preview.platform_fee, escrow.platform_fee);
+    assert_eq!(preview.net_to_freelancer, escrow.amount);
+    assert_eq!(preview.net_to_freelancer, Uint128::new(950));
+}
+
+fn referred_proposal(
+    deps: &mut TestDeps,
+    env: &Env,
+    job_id: u64,
+    referrer: &str,
+    referral_bps: u16,
+) -> Result<cosmwasm_std::Response, ContractError> {
+    let mut msg = submit_proposal_msg(job_id);
+    if let ExecuteMsg::SubmitProposal {
+        referrer: r,
+        referral_bps: bps,
+        ..
+    } = &mut msg
+    {
+        *r = Some(referrer.to_string());
+        *bps = Some(referral_bps);
+    }
+    exec(deps, env, FREELANCER, &[], msg)
+}
+
+#[test]
+fn referral_cut_comes_out_of_the_platform_fee() {
+    let (mut deps, env) = setup_contract();
+    let job_id = post_job(&mut deps, &env, CLIENT, 1000);
+    let res = referred_proposal(&mut deps, &env, job_id, "referrer", 2000).unwrap();
+    let proposal_id = attr_value(&res, "id").unwrap().parse().unwrap();
+    accept_proposal(&mut deps, &env, CLIENT, job_id, proposal_id);
+
+    let res = complete_job(&mut deps, &env, job_id);
+    let sent: Vec<(String, Uint128)> = res
+        .messages
+        .iter()
+        .map(|m| match &m.msg {
+            CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+                (to_address.clone(), amount[0].amount)
+            }
+            other => panic!("unexpected message {:?}", other),
+        })
+        .collect();
+    assert_eq!(
+        sent,
+        vec![
+            (FREELANCER.to_string(), Uint128::new(950)),
+            ("referrer".to_string(), Uint128::new(10)),
+        ]
+    );
+    assert_eq!(accrued(&deps, &env), Uint128::new(40));
+    assert_eq!(attr_value(&res, "referral_amount").as_deref(), Some("10"));
+
+    // Freelancer, referrer and platform shares add back up to the escrowed budget
+    let total = sent.iter().map(|(_, amount)| *amount).sum::<Uint128>() + accrued(&deps, &env);
+    assert_eq!(total, Uint128::new(1000));
+}
+
+#[test]
+fn referral_share_is_bounded() {
+    let (mut deps, env) = setup_contract();
+    let job_id = post_job(&mut deps, &env, CLIENT, 1000);
+
+    let err = referred_proposal(&mut deps, &env, job_id, "referrer", 5001).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+    let err = referred_proposal(&mut deps, &env, job_id, FREELANCER, 100).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+}
@@ -0,0 +1,612 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{coins, Addr, Env, Uint128};
+use xworks_freelance_contract::msg::{
+    AcceptedProposalsResponse, ConfigResponse, ContentHashResolutionResponse, ExecuteMsg,
+    JobDetailResponse, JobResponse, ProposalResponse, ProposalsResponse, QueryMsg,
+};
+use xworks_freelance_contract::state::{JobStatus, ProposalMilestone, ProposalStatus};
+use xworks_freelance_contract::ContractError;
+
+#[test]
+fn accepting_a_proposal_rejects_the_others() {
+    let (mut deps, env) = setup_contract();
+    let job_id = post_job(&mut deps, &env, CLIENT, 1000);
+
+    let proposals: Vec<u64> = [FREELANCER, "designer", "writer", "coder"]
+        .iter()
+        .map(|freelancer| submit_proposal(&mut deps, &env, freelancer, job_id))
+        .collect();
+    let accepted = proposals[1];
+
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::AcceptProposal {
+            job_id,
+            proposal_id: accepted,
+        },
+    )
+    .unwrap();
+    assert_eq!(attr_value(&res, "rejected_count"), Some("3".to_string()));
+
+    for proposal_id in proposals {
+        let res: ProposalResponse = query_as(&deps, &env, QueryMsg::GetProposal { proposal_id });
+        let expected = if proposal_id == accepted {
+            ProposalStatus::Accepted
+        } else {
+            ProposalStatus::Rejected
+        };
+        assert_eq!(res.proposal.status, expected);
+    }
+}
+
+#[test]
+fn poster_rejects_a_pending_proposal() {
+    let (mut deps, env) = setup_contract();
+    let job_id = post_job(&mut deps, &env, CLIENT, 1000);
+    let rejected = submit_proposal(&mut deps, &env, FREELANCER, job_id);
+    let other = submit_proposal(&mut deps, &env, "designer", job_id);
+
+    let reject = |proposal_id| ExecuteMsg::RejectProposal {
+        proposal_id,
+        reason: Some("Looking for more experience".to_string()),
+    };
+
+    // Only the job's poster may reject
+    for sender in [FREELANCER, "designer", ADMIN] {
+        let err = exec(&mut deps, &env, sender, &[], reject(rejected)).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+
+    let res = exec(&mut deps, &env, CLIENT, &[], reject(rejected)).unwrap();
+    assert_eq!(
+        attr_value(&res, "reason"),
+        Some("Looking for more experience".to_string())
+    );
+    let res: ProposalResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetProposal {
+            proposal_id: rejected,
+        },
+    );
+    assert_eq!(res.proposal.status, ProposalStatus::Rejected);
+
+    // Rejection is final and leaves the other applicants untouched
+    let err = exec(&mut deps, &env, CLIENT, &[], reject(rejected)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+    let res: ProposalResponse = query_as(&deps, &env, QueryMsg::GetProposal { proposal_id: other });
+    assert_eq!(res.proposal.status, ProposalStatus::Submitted);
+
+    // The job stays open, so the remaining proposal can still be accepted
+    accept_proposal(&mut deps, &env, CLIENT, job_id, other);
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::RejectProposal {
+            proposal_id: other,
+            reason: None,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+}
+
+fn proposal_ids_by_status(
+    deps: &TestDeps,
+    env: &cosmwasm_std::Env,
+    job_id: u64,
+    status: ProposalStatus,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> Vec<u64> {
+    let res: ProposalsResponse = query_as(
+        deps,
+        env,
+        QueryMsg::GetJobProposalsByStatus {
+            job_id,
+            status,
+            start_after,
+            limit,
+        },
+    );
+    res.proposals.iter().map(|p| p.id).collect()
+}
+
+#[test]
+fn job_proposals_filter_by_status() {
+    let (mut deps, env) = setup_contract();
+    let job_id = post_job(&mut deps, &env, CLIENT, 1000);
+    let proposals: Vec<u64> = [FREELANCER, "designer", "writer", "coder"]
+        .iter()
+        .map(|freelancer| submit_proposal(&mut deps, &env, freelancer, job_id))
+        .collect();
+
+    // Pending proposals page in ascending id order
+    let first_page = proposal_ids_by_status(
+        &deps,
+        &env,
+        job_id,
+        ProposalStatus::Submitted,
+        None,
+        Some(2),
+    );
+    assert_eq!(first_page, proposals[..2]);
+    let second_page = proposal_ids_by_status(
+        &deps,
+        &env,
+        job_id,
+        ProposalStatus::Submitted,
+        Some(first_page[1]),
+        Some(2),
+    );
+    assert_eq!(second_page, proposals[2..]);
+
+    accept_proposal(&mut deps, &env, CLIENT, job_id, proposals[2]);
+
+    let submitted =
+        proposal_ids_by_status(&deps, &env, job_id, ProposalStatus::Submitted, None, None);
+    assert!(submitted.is_empty());
+    let rejected =
+        proposal_ids_by_status(&deps, &env, job_id, ProposalStatus::Rejected, None, None);
+    assert_eq!(rejected, vec![proposals[0], proposals[1], proposals[3]]);
+    let accepted =
+        proposal_ids_by_status(&deps, &env, job_id, ProposalStatus::Accepted, None, None);
+    assert_eq!(accepted, vec![proposals[2]]);
+}
+
+#[test]
+fn proposals_are_capped_per_job() {
+    let (mut deps, env) = setup_contract();
+    let config: ConfigResponse = query_as(&deps, &env, QueryMsg::GetConfig {});
+    assert_eq!(config.config.max_proposals_per_job, 100);
+
+    let mut update = ExecuteMsg::UpdateConfig {
+        platform_fee_percent: None,
+        min_escrow_amount: None,
+        dispute_period_days: None,
+        max_job_duration_days: None,
+        treasury: None,
+        dispute_timeout_release_to_freelancer: None,
+        max_proposals_per_job: Some(0),
+        job_archive_retention_days: None,
+        fee_split_bps: None,
+        max_bounty_reward: None,
+        edit_grace_period_days: None,
+        proposal_validity_days: None,
+        min_edit_interval_seconds: None,
+        dispute_deposit: None,
+        reputation_half_life_days: None,
+        dispute_cooldown_seconds: None,
+        max_open_jobs_per_user: None,
+    };
+    let err = exec(&mut deps, &env, ADMIN, &[], update.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+
+    if let ExecuteMsg::UpdateConfig {
+        max_proposals_per_job,
+        ..
+    } = &mut update
+    {
+        *max_proposals_per_job = Some(3);
+    }
+    exec(&mut deps, &env, ADMIN, &[], update).unwrap();
+
+    let job_id = post_job(&mut deps, &env, CLIENT, 1000);
+    for freelancer in [FREELANCER, "designer", "writer"] {
+        submit_proposal(&mut deps, &env, freelancer, job_id);
+    }
+
+    let err = exec(&mut deps, &env, "coder", &[], submit_proposal_msg(job_id)).unwrap_err();
+    assert_eq!(err, ContractError::MaxProposalsReached { max: 3 });
+
+    // The cap is per job
+    let other_job = post_job(&mut deps, &env, CLIENT, 1000);
+    submit_proposal(&mut deps, &env, "coder", other_job);
+}
+
+#[test]
+fn poster_reassigns_job_after_deadline() {
+    let (mut deps, env) = setup_contract();
+    let job_id = post_job(&mut deps, &env, CLIENT, 1000);
+    let original = submit_proposal(&mut deps, &env, FREELANCER, job_id);
+    let backup = submit_proposal(&mut deps, &env, "designer", job_id);
+    accept_proposal(&mut deps, &env, CLIENT, job_id, original);
+
+    let reassign = ExecuteMsg::ReassignJob {
+        job_id,
+        new_proposal_id: backup,
+    };
+
+    // The assigned freelancer keeps the job until the deadline
+    let err = exec(&mut deps, &env, CLIENT, &[], reassign.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+
+    let mut later = env.clone();
+    later.block.time = later.block.time.plus_seconds(10 * 24 * 60 * 60 + 1);
+
+    let err = exec(&mut deps, &later, FREELANCER, &[], reassign.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    exec(&mut deps, &later, CLIENT, &[], reassign.clone()).unwrap();
+
+    let job = query_as::<JobResponse>(&deps, &later, QueryMsg::GetJob { job_id })
+        .job
+        .unwrap();
+    assert_eq!(job.status, JobStatus::InProgress);
+    assert_eq!(job.assigned_freelancer, Some(Addr::unchecked("designer")));
+    assert_eq!(job.previous_freelancers, vec![Addr::unchecked(FREELANCER)]);
+    assert!(job.deadline > later.block.time);
+
+    for (proposal_id, expected) in [
+        (original, ProposalStatus::Rejected),
+        (backup, ProposalStatus::Accepted),
+    ] {
+        let res: ProposalResponse = query_as(&deps, &later, QueryMsg::GetProposal { proposal_id });
+        assert_eq!(res.proposal.status, expected);
+    }
+
+    // The new freelancer gets a fresh deadline
+    let err = exec(&mut deps, &later, CLIENT, &[], reassign).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+}
+
+#[test]
+fn reassignment_checks_the_proposal_like_acceptance() {
+    let (mut deps, env) = setup_contract();
+    exec(&mut deps, &env, CLIENT, &[], post_job_msg(1000)).unwrap();
+    let job_id = 0;
+
+    // The original freelancer starts without escrow; the backup insists on it
+    let mut msg = submit_proposal_msg(job_id);
+    if let ExecuteMsg::SubmitProposal {
+        agreed_to_escrow, ..
+    } = &mut msg
+    {
+        *agreed_to_escrow = false;
+    }
+    let res = exec(&mut deps, &env, FREELANCER, &[], msg).unwrap();
+    let original: u64 = attr_value(&res, "id").unwrap().parse().unwrap();
+    let backup = submit_proposal(&mut deps, &env, "designer", job_id);
+    accept_proposal(&mut deps, &env, CLIENT, job_id, original);
+
+    let reassign = ExecuteMsg::ReassignJob {
+        job_id,
+        new_proposal_id: backup,
+    };
+    let later = days_later(&env, 10, 1);
+    let err = exec(&mut deps, &later, CLIENT, &[], reassign.clone()).unwrap_err();
+    assert_eq!(err, ContractError::JobNotFunded { job_id });
+
+    exec(
+        &mut deps,
+        &later,
+        CLIENT,
+        &coins(1000, DENOM),
+        ExecuteMsg::CreateEscrowNative {
+            job_id,
+            amount: Uint128::new(1000),
+            refund_overpayment: None,
+        },
+    )
+    .unwrap();
+
+    // Funding doesn't revive a proposal past its validity window
+    let expired = days_later(&env, 30, 1);
+    let err = exec(&mut deps, &expired, CLIENT, &[], reassign).unwrap_err();
+    assert_eq!(err, ContractError::ProposalExpired {});
+}
+
+#[test]
+fn job_detail_bundles_escrow_and_proposals() {
+    let (mut deps, env) = setup_contract();
+    let job_id = post_job(&mut deps, &env, CLIENT, 1000);
+    let proposal_ids: Vec<u64> = (0..3)
+        .map(|i| submit_proposal(&mut deps, &env, &format!("freelancer{}", i), job_id))
+        .collect();
+
+    let detail: JobDetailResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetJobDetail {
+            job_id,
+            proposals_limit: Some(2),
+        },
+    );
+    assert_eq!(detail.job.id, job_id);
+    let escrow = detail.escrow.unwrap();
+    assert_eq!(escrow.job_id, job_id);
+    assert_eq!(escrow.amount.u128() + escrow.platform_fee.u128(), 1000);
+    assert_eq!(detail.proposal_count, 3);
+    assert_eq!(
+        detail.proposals.iter().map(|p| p.id).collect::<Vec<_>>(),
+        proposal_ids[..2]
+    );
+}
+
+#[test]
+fn ranked_proposals_put_proven_freelancers_first() {
+    let (mut deps, env) = setup_contract();
+
+    // FREELANCER builds a track record: one completed job rated five stars
+    let finished = assigned_job(&mut deps, &env, 1000);
+    complete_job(&mut deps, &env, finished);
+    for (rater, rating) in [(CLIENT, 5), (FREELANCER, 5)] {
+        exec(
+            &mut deps,
+            &env,
+            rater,
+            &[],
+            ExecuteMsg::SubmitRating {
+                job_id: finished,
+                rating,
+                comment: "Smooth job".to_string(),
+            },
+        )
+        .unwrap();
+    }
+
+    // Newcomers bid first, one of them slower than the job's 10 days
+    let job_id = post_job(&mut deps, &env, CLIENT, 1000);
+    let newcomer = submit_proposal(&mut deps, &env, "newcomer", job_id);
+    let mut slow_msg = submit_proposal_msg(job_id);
+    if let ExecuteMsg::SubmitProposal {
+        delivery_time_days, ..
+    } = &mut slow_msg
+    {
+        *delivery_time_days = 20;
+    }
+    exec(&mut deps, &env, "slowpoke", &[], slow_msg).unwrap();
+    let veteran = submit_proposal(&mut deps, &env, FREELANCER, job_id);
+
+    let ranked: ProposalsResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetJobProposalsRanked {
+            job_id,
+            limit: None,
+        },
+    );
+    let order: Vec<(u64, u8)> = ranked.proposals.iter().map(|p| (p.id, p.score)).collect();
+    assert_eq!(order[0].0, veteran);
+    assert_eq!(order[1], (newcomer, 20));
+    assert_eq!(order[2].1, 10);
+    assert!(order[0].1 > 50);
+
+    let top: ProposalsResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetJobProposalsRanked {
+            job_id,
+            limit: Some(1),
+        },
+    );
+    assert_eq!(top.proposals.len(), 1);
+    assert_eq!(top.proposals[0].id, veteran);
+}
+
+fn days_later(env: &Env, days: u64, extra_seconds: u64) -> Env {
+    let mut later = env.clone();
+    later.block.time = later
+        .block
+        .time
+        .plus_seconds(days * 24 * 60 * 60 + extra_seconds);
+    later
+}
+
+#[test]
+fn proposals_expire_after_the_validity_window() {
+    let (mut deps, env) = setup_contract();
+    let job_id = post_job(&mut deps, &env, CLIENT, 1000);
+    let stale = submit_proposal(&mut deps, &env, FREELANCER, job_id);
+    let fresh = submit_proposal(&mut deps, &days_later(&env, 10, 0), "designer", job_id);
+
+    // The 7 day delivery estimate is shorter than the 30 day default window
+    let proposal: ProposalResponse =
+        query_as(&deps, &env, QueryMsg::GetProposal { proposal_id: stale });
+    assert_eq!(
+        proposal.proposal.expires_at,
+        days_later(&env, 30, 0).block.time
+    );
+
+    let expired = days_later(&env, 30, 1);
+    let err = exec(
+        &mut deps,
+        &expired,
+        CLIENT,
+        &[],
+        ExecuteMsg::AcceptProposal {
+            job_id,
+            proposal_id: stale,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ProposalExpired {});
+
+    // Posters can hide stale proposals; by default every proposal is listed
+    let ids = |include_expired| {
+        query_as::<ProposalsResponse>(
+            &deps,
+            &expired,
+            QueryMsg::GetJobProposals {
+                job_id,
+                include_expired,
+            },
+        )
+        .proposals
+        .iter()
+        .map(|p| p.id)
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(ids(None), vec![stale, fresh]);
+    assert_eq!(ids(Some(false)), vec![fresh]);
+
+    // Ranking only considers proposals that can still be accepted
+    let ranked: ProposalsResponse = query_as(
+        &deps,
+        &expired,
+        QueryMsg::GetJobProposalsRanked {
+            job_id,
+            limit: None,
+        },
+    );
+    let ranked: Vec<u64> = ranked.proposals.iter().map(|p| p.id).collect();
+    assert_eq!(ranked, vec![fresh]);
+
+    accept_proposal(&mut deps, &expired, CLIENT, job_id, fresh);
+}
+
+#[test]
+fn proposal_is_acceptable_until_its_expiry() {
+    let (mut deps, env) = setup_contract();
+    let job_id = post_job(&mut deps, &env, CLIENT, 1000);
+
+    // A delivery estimate longer than the window extends the proposal's life
+    let mut msg = submit_proposal_msg(job_id);
+    if let ExecuteMsg::SubmitProposal {
+        delivery_time_days, ..
+    } = &mut msg
+    {
+        *delivery_time_days = 45;
+    }
+    let res = exec(&mut deps, &env, FREELANCER, &[], msg).unwrap();
+    let proposal_id = attr_value(&res, "id").unwrap().parse().unwrap();
+
+    accept_proposal(
+        &mut deps,
+        &days_later(&env, 45, 0),
+        CLIENT,
+        job_id,
+        proposal_id,
+    );
+}
+
+#[test]
+fn editing_a_cover_letter_replaces_the_content_hash() {
+    let (mut deps, env) = setup_contract();
+    let job_id = post_job(&mut deps, &env, CLIENT, 1000);
+    let proposal_id = submit_proposal(&mut deps, &env, FREELANCER, job_id);
+    let proposal_hash = |deps: &TestDeps| {
+        query_as::<ProposalResponse>(deps, &env, QueryMsg::GetProposal { proposal_id })
+            .proposal
+            .content_hash
+    };
+    let before = proposal_hash(&deps);
+
+    let res = exec(
+        &mut deps,
+        &env,
+        FREELANCER,
+        &[],
+        ExecuteMsg::EditProposal {
+            proposal_id,
+            cover_letter: Some("Revised approach with a faster turnaround".to_string()),
+            delivery_time_days: None,
+            milestones: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(attr_value(&res, "content_changed").unwrap(), "true");
+    assert_ne!(proposal_hash(&deps), before);
+
+    let resolved: ContentHashResolutionResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::ResolveContentHash {
+            hash: attr_value(&res, "content_hash").unwrap(),
+        },
+    );
+    assert_eq!(resolved.entity_type, "proposal");
+    assert_eq!(resolved.entity_id, proposal_id);
+}
+
+#[test]
+fn editing_only_milestones_keeps_the_cover_letter() {
+    let (mut deps, env) = setup_contract();
+    let job_id = post_job(&mut deps, &env, CLIENT, 1000);
+    let proposal_id = submit_proposal(&mut deps, &env, FREELANCER, job_id);
+    let before = query_as::<ProposalResponse>(&deps, &env, QueryMsg::GetProposal { proposal_id })
+        .proposal
+        .content_hash;
+
+    // No cover letter in the edit means the existing one stands
+    let res = exec(
+        &mut deps,
+        &env,
+        FREELANCER,
+        &[],
+        ExecuteMsg::EditProposal {
+            proposal_id,
+            cover_letter: None,
+            delivery_time_days: None,
+            milestones: Some(vec![ProposalMilestone {
+                title: "Wireframes".to_string(),
+                description: "Layouts for every page".to_string(),
+                amount: Uint128::new(400),
+                deadline_days: 3,
+            }]),
+        },
+    )
+    .unwrap();
+    assert_eq!(attr_value(&res, "content_changed").unwrap(), "true");
+    let after = query_as::<ProposalResponse>(&deps, &env, QueryMsg::GetProposal { proposal_id })
+        .proposal
+        .content_hash;
+    assert_ne!(after, before);
+
+    // An empty cover letter is still rejected when one is given
+    let err = exec(
+        &mut deps,
+        &env,
+        FREELANCER,
+        &[],
+        ExecuteMsg::EditProposal {
+            proposal_id,
+            cover_letter: Some(String::new()),
+            delivery_time_days: None,
+            milestones: None,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+}
+
+#[test]
+fn accepted_proposals_list_only_won_jobs() {
+    let (mut deps, env) = setup_contract();
+    let won_job = post_job(&mut deps, &env, CLIENT, 1000);
+    let lost_job = post_job(&mut deps, &env, CLIENT, 1000);
+    let won = submit_proposal(&mut deps, &env, FREELANCER, won_job);
+    let lost = submit_proposal(&mut deps, &env, FREELANCER, lost_job);
+
+    accept_proposal(&mut deps, &env, CLIENT, won_job, won);
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::RejectProposal {
+            proposal_id: lost,
+            reason: None,
+        },
+    )
+    .unwrap();
+
+    let res: AcceptedProposalsResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetAcceptedProposals {
+            freelancer: FREELANCER.to_string(),
+            limit: None,
+        },
+    );
+    assert_eq!(res.proposals.len(), 1);
+    assert_eq!(res.proposals[0].proposal.id, won);
+    assert_eq!(res.proposals[0].job_status, JobStatus::InProgress);
+}
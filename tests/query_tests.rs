@@ -0,0 +1,1301 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{coins, Uint128};
+use xworks_freelance_contract::contract::query;
+use xworks_freelance_contract::msg::{
+    BountiesResponse, CategoryRegistryResponse, ContentHashResolutionResponse,
+    ContentReferencesResponse, ContractInfoResponse, CountsResponse, EscrowResponse,
+    EscrowsResponse, ExecuteMsg, JobResponse, JobTemplateOverrides, JobTemplatesResponse,
+    JobsResponse, PlatformStatsResponse, PostJobItem, QueryMsg,
+};
+use xworks_freelance_contract::state::{DisputeStatus, JobStatus};
+use xworks_freelance_contract::ContractError;
+
+#[test]
+fn total_value_locked_tracks_unreleased_escrows() {
+    let (mut deps, env) = setup_contract();
+
+    // Two funded jobs; the 5% platform fee comes out of each budget
+    let released_job = assigned_job(&mut deps, &env, 1000);
+    post_job(&mut deps, &env, CLIENT, 2000);
+
+    let stats: PlatformStatsResponse = query_as(&deps, &env, QueryMsg::GetPlatformStats {});
+    assert_eq!(stats.total_value_locked, Uint128::new(1000 + 2000));
+
+    // Completing the first job releases its escrow
+    complete_job(&mut deps, &env, released_job);
+
+    let stats: PlatformStatsResponse = query_as(&deps, &env, QueryMsg::GetPlatformStats {});
+    assert_eq!(stats.total_value_locked, Uint128::new(2000));
+}
+
+fn escrows(
+    deps: &TestDeps,
+    env: &cosmwasm_std::Env,
+    released: Option<bool>,
+    dispute_status: Option<DisputeStatus>,
+) -> Vec<String> {
+    let res: EscrowsResponse = query_as(
+        deps,
+        env,
+        QueryMsg::GetEscrows {
+            start_after: None,
+            limit: None,
+            released,
+            dispute_status,
+        },
+    );
+    res.escrows.into_iter().map(|e| e.id).collect()
+}
+
+#[test]
+fn get_escrows_filters_by_release_and_dispute_status() {
+    let (mut deps, env) = setup_contract();
+    let completed = assigned_job(&mut deps, &env, 1000);
+    let disputed = assigned_job(&mut deps, &env, 1000);
+    let open = post_job(&mut deps, &env, CLIENT, 1000);
+
+    complete_job(&mut deps, &env, completed);
+    exec(
+        &mut deps,
+        &env,
+        FREELANCER,
+        &[],
+        ExecuteMsg::RaiseDispute {
+            job_id: disputed,
+            reason: "Client stopped responding".to_string(),
+            evidence: vec![],
+        },
+    )
+    .unwrap();
+
+    let id = |job_id: u64| format!("job_{}", job_id);
+
+    assert_eq!(escrows(&deps, &env, None, None).len(), 3);
+    assert_eq!(escrows(&deps, &env, Some(true), None), vec![id(completed)]);
+    assert_eq!(
+        escrows(&deps, &env, Some(false), None),
+        vec![id(disputed), id(open)]
+    );
+    assert_eq!(
+        escrows(&deps, &env, None, Some(DisputeStatus::Raised)),
+        vec![id(disputed)]
+    );
+    assert_eq!(
+        escrows(&deps, &env, Some(false), Some(DisputeStatus::None)),
+        vec![id(open)]
+    );
+
+    // Pagination resumes after the given escrow id
+    let page: EscrowsResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetEscrows {
+            start_after: Some(id(completed)),
+            limit: Some(1),
+            released: None,
+            dispute_status: None,
+        },
+    );
+    assert_eq!(page.escrows.len(), 1);
+    assert_eq!(page.escrows[0].id, id(disputed));
+}
+
+fn resolve_hash(
+    deps: &TestDeps,
+    env: &cosmwasm_std::Env,
+    hash: &str,
+) -> ContentHashResolutionResponse {
+    query_as(
+        deps,
+        env,
+        QueryMsg::ResolveContentHash {
+            hash: hash.to_string(),
+        },
+    )
+}
+
+#[test]
+fn resolve_content_hash_maps_jobs_and_bounties() {
+    let (mut deps, env) = setup_contract();
+
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, DENOM),
+        post_job_msg(1000),
+    )
+    .unwrap();
+    let job_id: u64 = attr_value(&res, "id").unwrap().parse().unwrap();
+    let job_hash = attr_value(&res, "content_hash").unwrap();
+
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, DENOM),
+        create_bounty_msg(1000, &[100]),
+    )
+    .unwrap();
+    let bounty_id: u64 = attr_value(&res, "id").unwrap().parse().unwrap();
+    let bounty_hash = attr_value(&res, "content_hash").unwrap();
+
+    assert_eq!(
+        resolve_hash(&deps, &env, &job_hash),
+        ContentHashResolutionResponse {
+            entity_type: "job".to_string(),
+            entity_id: job_id,
+            current_hash: job_hash.clone(),
+        }
+    );
+    assert_eq!(
+        resolve_hash(&deps, &env, &bounty_hash),
+        ContentHashResolutionResponse {
+            entity_type: "bounty".to_string(),
+            entity_id: bounty_id,
+            current_hash: bounty_hash.clone(),
+        }
+    );
+
+    let unknown = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::ResolveContentHash {
+            hash: "not-a-hash".to_string(),
+        },
+    );
+    assert!(unknown.is_err());
+}
+
+#[test]
+fn deleting_a_job_drops_its_hash_mappings() {
+    let (mut deps, env) = setup_contract();
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, DENOM),
+        post_job_msg(1000),
+    )
+    .unwrap();
+    let job_id: u64 = attr_value(&res, "id").unwrap().parse().unwrap();
+    let job_hash = attr_value(&res, "content_hash").unwrap();
+    assert_eq!(resolve_hash(&deps, &env, &job_hash).entity_id, job_id);
+
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::DeleteJob { job_id },
+    )
+    .unwrap();
+
+    let resolved = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::ResolveContentHash { hash: job_hash },
+    );
+    assert!(resolved.is_err());
+}
+
+fn jobs_in_budget_range(
+    deps: &TestDeps,
+    env: &cosmwasm_std::Env,
+    range: u8,
+    start_after: Option<u64>,
+) -> Vec<u64> {
+    query_as::<JobsResponse>(
+        deps,
+        env,
+        QueryMsg::GetJobsByBudgetRange {
+            range,
+            start_after,
+            limit: None,
+        },
+    )
+    .jobs
+    .iter()
+    .map(|job| job.id)
+    .collect()
+}
+
+#[test]
+fn jobs_are_indexed_by_budget_range() {
+    let (mut deps, env) = setup_contract();
+    let free = exec(&mut deps, &env, CLIENT, &[], post_job_msg(0)).unwrap();
+    let free: u64 = attr_value(&free, "id").unwrap().parse().unwrap();
+    let small = post_job(&mut deps, &env, CLIENT, 1000);
+    let mid = post_job(&mut deps, &env, CLIENT, 4999);
+    let large = post_job(&mut deps, &env, CLIENT, 5000);
+
+    assert_eq!(jobs_in_budget_range(&deps, &env, 1, None), vec![free]);
+    assert_eq!(jobs_in_budget_range(&deps, &env, 2, None), vec![small, mid]);
+    assert_eq!(jobs_in_budget_range(&deps, &env, 2, Some(small)), vec![mid]);
+    assert_eq!(jobs_in_budget_range(&deps, &env, 3, None), vec![large]);
+
+    // Topping up the escrow moves the job into its new tier
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1001, DENOM),
+        ExecuteMsg::TopUpEscrow { job_id: mid },
+    )
+    .unwrap();
+    assert_eq!(jobs_in_budget_range(&deps, &env, 2, None), vec![small]);
+    assert_eq!(jobs_in_budget_range(&deps, &env, 3, None), vec![mid, large]);
+
+    let invalid = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::GetJobsByBudgetRange {
+            range: 4,
+            start_after: None,
+            limit: None,
+        },
+    );
+    assert!(invalid.is_err());
+}
+
+#[test]
+fn budget_tier_is_stored_on_the_job() {
+    let (mut deps, env) = setup_contract();
+    let below = post_job(&mut deps, &env, CLIENT, 4999);
+    let at = post_job(&mut deps, &env, CLIENT, 5000);
+
+    let tier = |job_id| {
+        query_as::<JobResponse>(&deps, &env, QueryMsg::GetJob { job_id })
+            .job
+            .unwrap()
+            .budget_range
+    };
+    assert_eq!(tier(below), 2);
+    assert_eq!(tier(at), 3);
+    assert_eq!(jobs_in_budget_range(&deps, &env, 2, None), vec![below]);
+    assert_eq!(jobs_in_budget_range(&deps, &env, 3, None), vec![at]);
+}
+
+#[test]
+fn missing_entities_query_as_none() {
+    let (deps, env) = setup_contract();
+
+    let job: JobResponse = query_as(&deps, &env, QueryMsg::GetJob { job_id: 42 });
+    assert_eq!(job.job, None);
+
+    let escrow: EscrowResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetEscrow {
+            escrow_id: "job_42".to_string(),
+        },
+    );
+    assert_eq!(escrow.escrow, None);
+
+    let escrow: EscrowResponse = query_as(&deps, &env, QueryMsg::GetJobEscrow { job_id: 42 });
+    assert_eq!(escrow.escrow, None);
+}
+
+fn registry_id(entries: &[xworks_freelance_contract::msg::RegistryEntry], name: &str) -> u8 {
+    entries.iter().find(|e| e.name == name).unwrap().id
+}
+
+#[test]
+fn job_category_and_skill_ids_match_registry() {
+    let (mut deps, env) = setup_contract();
+    let registry: CategoryRegistryResponse = query_as(&deps, &env, QueryMsg::GetCategories {});
+
+    // post_job_msg uses "Web Development" with the skill "rust"
+    let job_id = post_job(&mut deps, &env, CLIENT, 1000);
+    let job = query_as::<JobResponse>(&deps, &env, QueryMsg::GetJob { job_id })
+        .job
+        .unwrap();
+    assert_eq!(
+        job.category_id,
+        registry_id(&registry.categories, "Web Development")
+    );
+    assert_eq!(job.skill_tags, vec![registry_id(&registry.skills, "Rust")]);
+
+    // Editing re-maps through the same registry; unknown skills are not tagged
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::EditJob {
+            job_id,
+            title: None,
+            description: None,
+            budget: None,
+            category: Some("design".to_string()),
+            skills_required: Some(vec![
+                "TypeScript".to_string(),
+                "react".to_string(),
+                "typescript".to_string(),
+                "Underwater basket weaving".to_string(),
+            ]),
+            duration_days: None,
+            documents: None,
+            milestones: None,
+            off_chain_storage_key: "job_key".to_string(),
+        },
+    )
+    .unwrap();
+    let job = query_as::<JobResponse>(&deps, &env, QueryMsg::GetJob { job_id })
+        .job
+        .unwrap();
+    assert_eq!(job.category_id, registry_id(&registry.categories, "Design"));
+    let mut expected = vec![
+        registry_id(&registry.skills, "React"),
+        registry_id(&registry.skills, "TypeScript"),
+    ];
+    expected.sort();
+    assert_eq!(job.skill_tags, expected);
+
+    // Unregistered categories fall back to "Other"
+    let mut later = env.clone();
+    later.block.time = later.block.time.plus_seconds(60);
+    exec(
+        &mut deps,
+        &later,
+        CLIENT,
+        &[],
+        ExecuteMsg::EditJob {
+            job_id,
+            title: None,
+            description: None,
+            budget: None,
+            category: Some("Gardening".to_string()),
+            skills_required: None,
+            duration_days: None,
+            documents: None,
+            milestones: None,
+            off_chain_storage_key: "job_key".to_string(),
+        },
+    )
+    .unwrap();
+    let job = query_as::<JobResponse>(&deps, &env, QueryMsg::GetJob { job_id })
+        .job
+        .unwrap();
+    assert_eq!(job.category_id, registry_id(&registry.categories, "Other"));
+}
+
+fn post_job_with_skills(deps: &mut TestDeps, env: &cosmwasm_std::Env, skills: &[&str]) -> u64 {
+    let mut msg = post_job_msg(1000);
+    if let ExecuteMsg::PostJob {
+        skills_required, ..
+    } = &mut msg
+    {
+        *skills_required = skills.iter().map(|s| s.to_string()).collect();
+    }
+    let res = exec(deps, env, CLIENT, &coins(1000, DENOM), msg).unwrap();
+    attr_value(&res, "id").unwrap().parse().unwrap()
+}
+
+fn jobs_by_skills(
+    deps: &TestDeps,
+    env: &cosmwasm_std::Env,
+    skills: &[&str],
+    match_all: bool,
+) -> Vec<u64> {
+    let res: JobsResponse = query_as(
+        deps,
+        env,
+        QueryMsg::GetJobsBySkills {
+            skills: skills.iter().map(|s| s.to_string()).collect(),
+            match_all,
+            limit: None,
+        },
+    );
+    res.jobs.into_iter().map(|job| job.id).collect()
+}
+
+#[test]
+fn jobs_by_skills_intersects_when_matching_all() {
+    let (mut deps, env) = setup_contract();
+    let rust_only = post_job_with_skills(&mut deps, &env, &["Rust"]);
+    let rust_and_react = post_job_with_skills(&mut deps, &env, &["rust", "React"]);
+    post_job_with_skills(&mut deps, &env, &["React"]);
+
+    assert_eq!(
+        jobs_by_skills(&deps, &env, &["Rust", "React"], true),
+        vec![rust_and_react]
+    );
+    assert_eq!(
+        jobs_by_skills(&deps, &env, &["Rust"], true),
+        vec![rust_and_react, rust_only]
+    );
+    // An unregistered skill cannot be satisfied
+    assert!(jobs_by_skills(&deps, &env, &["Rust", "Knitting"], true).is_empty());
+}
+
+#[test]
+fn jobs_by_skills_unions_when_matching_any() {
+    let (mut deps, env) = setup_contract();
+    let rust_only = post_job_with_skills(&mut deps, &env, &["Rust"]);
+    let both = post_job_with_skills(&mut deps, &env, &["Rust", "React"]);
+    let react_only = post_job_with_skills(&mut deps, &env, &["React"]);
+    post_job_with_skills(&mut deps, &env, &["Python"]);
+
+    assert_eq!(
+        jobs_by_skills(&deps, &env, &["Rust", "React", "Knitting"], false),
+        vec![react_only, both, rust_only]
+    );
+
+    // Re-tagging a job moves it between index entries
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::EditJob {
+            job_id: rust_only,
+            title: None,
+            description: None,
+            budget: None,
+            category: None,
+            skills_required: Some(vec!["Python".to_string()]),
+            duration_days: None,
+            documents: None,
+            milestones: None,
+            off_chain_storage_key: "job_key".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        jobs_by_skills(&deps, &env, &["Rust", "React"], false),
+        vec![react_only, both]
+    );
+}
+
+fn active_job_ids(deps: &TestDeps, env: &cosmwasm_std::Env) -> Vec<u64> {
+    let res: JobsResponse = query_as(
+        deps,
+        env,
+        QueryMsg::GetAllJobs {
+            limit: None,
+            category: None,
+        },
+    );
+    res.jobs.into_iter().map(|job| job.id).collect()
+}
+
+#[test]
+fn jobs_leave_active_index_when_no_longer_open() {
+    let (mut deps, env) = setup_contract();
+    let open = post_job(&mut deps, &env, CLIENT, 1000);
+    let completed = post_job(&mut deps, &env, CLIENT, 1000);
+    assert_eq!(active_job_ids(&deps, &env), vec![completed, open]);
+
+    // Accepting a proposal moves the job out of Open
+    let proposal_id = submit_proposal(&mut deps, &env, FREELANCER, completed);
+    accept_proposal(&mut deps, &env, CLIENT, completed, proposal_id);
+    assert_eq!(active_job_ids(&deps, &env), vec![open]);
+
+    complete_job(&mut deps, &env, completed);
+    assert_eq!(active_job_ids(&deps, &env), vec![open]);
+
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::CancelJob { job_id: open },
+    )
+    .unwrap();
+    assert!(active_job_ids(&deps, &env).is_empty());
+}
+
+#[test]
+fn cancelled_bounties_leave_active_index() {
+    let (mut deps, env) = setup_contract();
+    let kept = create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+    let cancelled = create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::CancelBounty {
+            bounty_id: cancelled,
+        },
+    )
+    .unwrap();
+
+    let res: BountiesResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetAllBounties {
+            limit: None,
+            category: None,
+        },
+    );
+    let ids: Vec<u64> = res.bounties.into_iter().map(|b| b.id).collect();
+    assert_eq!(ids, vec![kept]);
+}
+
+fn edit_job_msg(job_id: u64, title: Option<&str>, budget: Option<u128>) -> ExecuteMsg {
+    ExecuteMsg::EditJob {
+        job_id,
+        title: title.map(str::to_string),
+        description: None,
+        budget: budget.map(Uint128::new),
+        category: None,
+        skills_required: None,
+        duration_days: None,
+        documents: None,
+        milestones: None,
+        off_chain_storage_key: "job_key".to_string(),
+    }
+}
+
+#[test]
+fn job_edit_emits_new_content_hash() {
+    let (mut deps, env) = setup_contract();
+    let job_id = post_job(&mut deps, &env, CLIENT, 1000);
+
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        edit_job_msg(job_id, Some("Rewritten title"), None),
+    )
+    .unwrap();
+    let job = query_as::<JobResponse>(&deps, &env, QueryMsg::GetJob { job_id })
+        .job
+        .unwrap();
+    assert_eq!(
+        attr_value(&res, "content_hash"),
+        Some(job.content_hash.hash)
+    );
+
+    // Budget-only edits leave the off-chain bundle untouched
+    let mut later = env.clone();
+    later.block.time = later.block.time.plus_seconds(60);
+    let res = exec(
+        &mut deps,
+        &later,
+        CLIENT,
+        &[],
+        edit_job_msg(job_id, None, Some(2000)),
+    )
+    .unwrap();
+    assert_eq!(attr_value(&res, "content_hash"), None);
+}
+
+#[test]
+fn rapid_repeat_edits_are_throttled() {
+    let (mut deps, env) = setup_contract();
+    let job_id = post_job(&mut deps, &env, CLIENT, 1000);
+    let edit = |title| edit_job_msg(job_id, Some(title), None);
+
+    exec(&mut deps, &env, CLIENT, &[], edit("First rewrite")).unwrap();
+
+    let mut soon = env.clone();
+    soon.block.time = soon.block.time.plus_seconds(59);
+    let err = exec(&mut deps, &soon, CLIENT, &[], edit("Second rewrite")).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::EditTooSoon {
+            next_edit_at: env.block.time.plus_seconds(60)
+        }
+    );
+
+    let mut later = env.clone();
+    later.block.time = later.block.time.plus_seconds(60);
+    exec(&mut deps, &later, CLIENT, &[], edit("Second rewrite")).unwrap();
+}
+
+#[test]
+fn in_progress_job_content_is_editable_within_grace_period() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+
+    // Default two-day window: content goes through, budget does not
+    let mut early = env.clone();
+    early.block.time = env.block.time.plus_seconds(24 * 60 * 60);
+    let res = exec(
+        &mut deps,
+        &early,
+        CLIENT,
+        &[],
+        edit_job_msg(job_id, Some("Clarified scope"), None),
+    )
+    .unwrap();
+    assert_eq!(
+        attr_value(&res, "content_changed"),
+        Some("true".to_string())
+    );
+
+    let err = exec(
+        &mut deps,
+        &early,
+        CLIENT,
+        &[],
+        edit_job_msg(job_id, None, Some(2000)),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+
+    // The assigned freelancer can close the window early
+    exec(
+        &mut deps,
+        &early,
+        FREELANCER,
+        &[],
+        ExecuteMsg::ObjectToJobEdits { job_id },
+    )
+    .unwrap();
+    let err = exec(
+        &mut deps,
+        &early,
+        CLIENT,
+        &[],
+        edit_job_msg(job_id, Some("Another change"), None),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+}
+
+#[test]
+fn in_progress_job_edits_are_rejected_after_grace_period() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+
+    let mut late = env.clone();
+    late.block.time = env.block.time.plus_seconds(2 * 24 * 60 * 60);
+    let err = exec(
+        &mut deps,
+        &late,
+        CLIENT,
+        &[],
+        edit_job_msg(job_id, Some("Too late"), None),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+
+    // Only the assigned freelancer can object
+    let err = exec(
+        &mut deps,
+        &late,
+        CLIENT,
+        &[],
+        ExecuteMsg::ObjectToJobEdits { job_id },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+}
+
+#[test]
+fn job_documents_are_bounded() {
+    let (mut deps, env) = setup_contract();
+
+    let mut msg = post_job_msg(1000);
+    if let ExecuteMsg::PostJob { documents, .. } = &mut msg {
+        *documents = Some(vec!["ipfs://doc".to_string(); 11]);
+    }
+    let err = exec(&mut deps, &env, CLIENT, &coins(1000, DENOM), msg).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+
+    let job_id = post_job(&mut deps, &env, CLIENT, 1000);
+    let mut edit = edit_job_msg(job_id, None, None);
+    if let ExecuteMsg::EditJob { documents, .. } = &mut edit {
+        *documents = Some(vec![format!("https://{}", "a".repeat(512))]);
+    }
+    let err = exec(&mut deps, &env, CLIENT, &[], edit).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+}
+
+#[test]
+fn job_skills_are_bounded() {
+    let (mut deps, env) = setup_contract();
+    let post_with = |deps: &mut TestDeps, skills: Vec<String>| {
+        let mut msg = post_job_msg(1000);
+        if let ExecuteMsg::PostJob {
+            skills_required, ..
+        } = &mut msg
+        {
+            *skills_required = skills;
+        }
+        exec(deps, &env, CLIENT, &coins(1000, DENOM), msg)
+    };
+
+    let err = post_with(&mut deps, vec!["rust".to_string(), String::new()]).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+    let err = post_with(&mut deps, vec!["rust".to_string(); 21]).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+    post_with(&mut deps, vec!["rust".to_string(); 20]).unwrap();
+}
+
+#[test]
+fn archived_jobs_leave_the_live_scan() {
+    let (mut deps, env) = setup_contract();
+    let completed = assigned_job(&mut deps, &env, 1000);
+    complete_job(&mut deps, &env, completed);
+    let open = post_job(&mut deps, &env, CLIENT, 1000);
+    let before: PlatformStatsResponse = query_as(&deps, &env, QueryMsg::GetPlatformStats {});
+
+    let archive = |job_ids: Vec<u64>| ExecuteMsg::ArchiveJobs { job_ids };
+
+    // Closed jobs stay live for the retention window
+    let err = exec(&mut deps, &env, ADMIN, &[], archive(vec![completed])).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+
+    let mut later = env.clone();
+    later.block.time = later.block.time.plus_seconds(91 * 24 * 60 * 60);
+    let err = exec(&mut deps, &later, CLIENT, &[], archive(vec![completed])).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    exec(&mut deps, &later, ADMIN, &[], archive(vec![open])).unwrap_err();
+
+    let res = exec(&mut deps, &later, ADMIN, &[], archive(vec![completed])).unwrap();
+    assert_eq!(attr_value(&res, "archived_count").as_deref(), Some("1"));
+
+    let live: JobResponse = query_as(&deps, &later, QueryMsg::GetJob { job_id: completed });
+    assert!(live.job.is_none());
+    let jobs: JobsResponse = query_as(
+        &deps,
+        &later,
+        QueryMsg::GetJobs {
+            start_after: None,
+            limit: None,
+            category: None,
+            status: None,
+            poster: None,
+        },
+    );
+    assert_eq!(
+        jobs.jobs.iter().map(|j| j.id).collect::<Vec<_>>(),
+        vec![open]
+    );
+
+    let archived: JobResponse = query_as(
+        &deps,
+        &later,
+        QueryMsg::GetArchivedJob { job_id: completed },
+    );
+    assert_eq!(archived.job.unwrap().id, completed);
+    let archived: JobsResponse = query_as(
+        &deps,
+        &later,
+        QueryMsg::GetArchivedJobs {
+            start_after: None,
+            limit: None,
+        },
+    );
+    assert_eq!(archived.jobs.len(), 1);
+
+    // Totals still include archived jobs
+    let after: PlatformStatsResponse = query_as(&deps, &later, QueryMsg::GetPlatformStats {});
+    assert_eq!(after.total_jobs, before.total_jobs);
+    assert_eq!(after.completed_jobs, before.completed_jobs);
+}
+
+#[test]
+fn contract_info_reports_crate_name_and_version() {
+    let (deps, env) = setup_contract();
+
+    let info: ContractInfoResponse = query_as(&deps, &env, QueryMsg::GetContractInfo {});
+    assert_eq!(info.contract, "crates.io:xworks-freelance-contract");
+    assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+}
+
+fn batch_item(title: &str, budget: u128) -> PostJobItem {
+    PostJobItem {
+        title: title.to_string(),
+        description: "Full stack CosmWasm work".to_string(),
+        company: None,
+        location: None,
+        category: "Web Development".to_string(),
+        skills_required: vec!["rust".to_string()],
+        documents: None,
+        milestones: None,
+        budget: Uint128::new(budget),
+        duration_days: 10,
+        experience_level: 2,
+        is_remote: true,
+        urgency_level: 1,
+        off_chain_storage_key: "job_key".to_string(),
+        release_policy: None,
+        denom: None,
+    }
+}
+
+#[test]
+fn batch_posts_jobs_with_their_own_hash_and_escrow() {
+    let (mut deps, env) = setup_contract();
+
+    // More listings than the daily job limit, funded together
+    let jobs: Vec<PostJobItem> = (0..7)
+        .map(|i| batch_item(&format!("Listing {i}"), if i == 0 { 0 } else { 1000 }))
+        .collect();
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(6000, DENOM),
+        ExecuteMsg::PostJobsBatch { jobs },
+    )
+    .unwrap();
+    assert_eq!(attr_value(&res, "count").unwrap(), "7");
+    assert_eq!(attr_value(&res, "job_ids").unwrap(), "0,1,2,3,4,5,6");
+
+    let mut hashes = Vec::new();
+    for job_id in 0..7u64 {
+        let job = query_as::<JobResponse>(&deps, &env, QueryMsg::GetJob { job_id })
+            .job
+            .unwrap();
+        assert_eq!(job.escrow_id, Some(format!("job_{job_id}")));
+        hashes.push(job.content_hash.hash);
+    }
+    hashes.sort();
+    hashes.dedup();
+    assert_eq!(hashes.len(), 7);
+
+    let escrow = query_as::<EscrowResponse>(&deps, &env, QueryMsg::GetJobEscrow { job_id: 6 })
+        .escrow
+        .unwrap();
+    assert_eq!(escrow.amount + escrow.platform_fee, Uint128::new(1000));
+
+    // The whole batch used a single rate-limit unit
+    for _ in 0..4 {
+        post_job(&mut deps, &env, CLIENT, 1000);
+    }
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, DENOM),
+        post_job_msg(1000),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::RateLimitExceeded { .. }));
+}
+
+#[test]
+fn oversized_or_underfunded_batches_are_rejected() {
+    let (mut deps, env) = setup_contract();
+
+    let jobs: Vec<PostJobItem> = (0..21)
+        .map(|i| batch_item(&format!("Listing {i}"), 1000))
+        .collect();
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::PostJobsBatch { jobs },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+
+    let jobs = vec![batch_item("First", 1000), batch_item("Second", 1000)];
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, DENOM),
+        ExecuteMsg::PostJobsBatch { jobs },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientFunds { .. }));
+
+    // Nothing from the rejected batches was stored
+    let job = query_as::<JobResponse>(&deps, &env, QueryMsg::GetJob { job_id: 0 });
+    assert!(job.job.is_none());
+}
+
+#[test]
+fn platform_stats_count_users_by_their_stats_record() {
+    let (mut deps, env) = setup_contract();
+
+    // CLIENT and FREELANCER gain stats through a completed job, without profiles
+    let done = assigned_job(&mut deps, &env, 1000);
+    complete_job(&mut deps, &env, done);
+    post_job(&mut deps, &env, CLIENT, 2000);
+
+    // A profile on its own does not make a platform user
+    exec(
+        &mut deps,
+        &env,
+        "carol",
+        &[],
+        ExecuteMsg::UpdateUserProfile {
+            display_name: Some("Carol".to_string()),
+            bio: None,
+            skills: None,
+            location: None,
+            website: None,
+            portfolio_links: None,
+            hourly_rate: None,
+            availability: None,
+            off_chain_storage_key: "carol_key".to_string(),
+        },
+    )
+    .unwrap();
+
+    let stats: PlatformStatsResponse = query_as(&deps, &env, QueryMsg::GetPlatformStats {});
+    assert_eq!(stats.total_users, 2);
+    assert_eq!(stats.total_jobs, 2);
+    assert_eq!(
+        stats.open_jobs + stats.in_progress_jobs + stats.completed_jobs,
+        stats.total_jobs
+    );
+    assert_eq!((stats.open_jobs, stats.completed_jobs), (1, 1));
+    assert_eq!(stats.total_value_locked, Uint128::new(2000));
+}
+
+fn save_template_msg(name: &str, budget: u128) -> ExecuteMsg {
+    ExecuteMsg::SaveJobTemplate {
+        name: name.to_string(),
+        title: "Monthly smart contract audit".to_string(),
+        description: "Review the latest release".to_string(),
+        category: "Web Development".to_string(),
+        skills_required: vec!["rust".to_string()],
+        budget: Uint128::new(budget),
+        duration_days: 14,
+        experience_level: 3,
+        is_remote: true,
+        urgency_level: 2,
+    }
+}
+
+#[test]
+fn jobs_are_posted_from_saved_templates() {
+    let (mut deps, env) = setup_contract();
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        save_template_msg("audit", 1000),
+    )
+    .unwrap();
+    // Templates hold no funds and are validated like a listing
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, DENOM),
+        save_template_msg("funded", 1000),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidFunds { .. }));
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        save_template_msg("weekly", 1000),
+    )
+    .unwrap();
+    exec(&mut deps, &env, CLIENT, &[], save_template_msg("", 1000)).unwrap_err();
+    exec(&mut deps, &env, CLIENT, &[], save_template_msg("tiny", 1)).unwrap_err();
+
+    let res: JobTemplatesResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetJobTemplates {
+            poster: CLIENT.to_string(),
+        },
+    );
+    let names: Vec<_> = res.templates.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["audit", "weekly"]);
+    assert_eq!(res.templates[0].duration_days, 14);
+
+    let post_from = |name: &str, overrides| ExecuteMsg::PostJobFromTemplate {
+        name: name.to_string(),
+        overrides,
+        off_chain_storage_key: "audit_key".to_string(),
+    };
+    let overrides = JobTemplateOverrides {
+        budget: Some(Uint128::new(2000)),
+        ..Default::default()
+    };
+    let res = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(2000, DENOM),
+        post_from("audit", overrides.clone()),
+    )
+    .unwrap();
+    assert_eq!(attr_value(&res, "template").as_deref(), Some("audit"));
+    let job_id: u64 = attr_value(&res, "id").unwrap().parse().unwrap();
+    let job = query_as::<JobResponse>(&deps, &env, QueryMsg::GetJob { job_id })
+        .job
+        .unwrap();
+    assert_eq!(job.budget, Uint128::new(2000));
+    assert_eq!(job.duration_days, 14);
+    assert_eq!(job.escrow_id, Some(format!("job_{job_id}")));
+
+    // Templates belong to their poster
+    let err = exec(
+        &mut deps,
+        &env,
+        "agency",
+        &coins(2000, DENOM),
+        post_from("audit", overrides),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::JobTemplateNotFound {
+            name: "audit".to_string()
+        }
+    );
+
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::DeleteJobTemplate {
+            name: "audit".to_string(),
+        },
+    )
+    .unwrap();
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        post_from("audit", JobTemplateOverrides::default()),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::JobTemplateNotFound { .. }));
+}
+
+#[test]
+fn counts_track_creation_and_removal() {
+    let (mut deps, env) = setup_contract();
+    let counts =
+        |deps: &TestDeps| -> CountsResponse { query_as(deps, &env, QueryMsg::GetCounts {}) };
+    assert_eq!(
+        counts(&deps),
+        CountsResponse {
+            total_jobs: 0,
+            total_proposals: 0,
+            total_bounties: 0,
+            total_submissions: 0,
+        }
+    );
+
+    let kept = post_job(&mut deps, &env, CLIENT, 1000);
+    let deleted = post_job(&mut deps, &env, CLIENT, 1000);
+    let proposal_id = submit_proposal(&mut deps, &env, FREELANCER, kept);
+    submit_proposal(&mut deps, &env, "other", kept);
+    let bounty_id = create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+    submit_to_bounty(&mut deps, &env, FREELANCER, bounty_id);
+    assert_eq!(
+        counts(&deps),
+        CountsResponse {
+            total_jobs: 2,
+            total_proposals: 2,
+            total_bounties: 1,
+            total_submissions: 1,
+        }
+    );
+
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::DeleteJob { job_id: deleted },
+    )
+    .unwrap();
+    exec(
+        &mut deps,
+        &env,
+        FREELANCER,
+        &[],
+        ExecuteMsg::WithdrawProposal { proposal_id },
+    )
+    .unwrap();
+    let after = counts(&deps);
+    assert_eq!(after.total_jobs, 1);
+    assert_eq!(after.total_proposals, 1);
+}
+
+#[test]
+fn jobs_by_ids_skip_missing_entries() {
+    let (mut deps, env) = setup_contract();
+    let first = post_job(&mut deps, &env, CLIENT, 1000);
+    let second = post_job(&mut deps, &env, CLIENT, 2000);
+
+    let res: JobsResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetJobsByIds {
+            ids: vec![second, 404, first],
+        },
+    );
+    let ids: Vec<u64> = res.jobs.iter().map(|job| job.id).collect();
+    assert_eq!(ids, vec![second, first]);
+
+    let too_many = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::GetJobsByIds {
+            ids: (0..51).collect(),
+        },
+    );
+    assert!(too_many.is_err());
+}
+
+#[test]
+fn content_references_paginate_by_entity_key() {
+    let (mut deps, env) = setup_contract();
+    for _ in 0..3 {
+        post_job(&mut deps, &env, CLIENT, 1000);
+    }
+    create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+
+    let page = |deps: &TestDeps, start_after: Option<&str>| {
+        let res: ContentReferencesResponse = query_as(
+            deps,
+            &env,
+            QueryMsg::GetContentReferences {
+                start_after: start_after.map(str::to_string),
+                limit: Some(2),
+            },
+        );
+        res.references
+    };
+
+    let first = page(&deps, None);
+    let keys: Vec<&str> = first.iter().map(|r| r.entity_key.as_str()).collect();
+    assert_eq!(keys, vec!["bounty_0", "job_0"]);
+    let resolved = resolve_hash(&deps, &env, &first[1].hash);
+    assert_eq!(resolved.entity_type, "job");
+    assert_eq!(resolved.entity_id, 0);
+
+    let second = page(&deps, Some("job_0"));
+    let keys: Vec<&str> = second.iter().map(|r| r.entity_key.as_str()).collect();
+    assert_eq!(keys, vec!["job_1", "job_2"]);
+    assert!(page(&deps, Some("job_2")).is_empty());
+}
+
+#[test]
+fn open_jobs_are_capped_per_poster() {
+    let (mut deps, env) = setup_contract();
+    exec(
+        &mut deps,
+        &env,
+        ADMIN,
+        &[],
+        ExecuteMsg::UpdateConfig {
+            platform_fee_percent: None,
+            min_escrow_amount: None,
+            dispute_period_days: None,
+            max_job_duration_days: None,
+            treasury: None,
+            dispute_timeout_release_to_freelancer: None,
+            max_proposals_per_job: None,
+            job_archive_retention_days: None,
+            fee_split_bps: None,
+            max_bounty_reward: None,
+            edit_grace_period_days: None,
+            proposal_validity_days: None,
+            min_edit_interval_seconds: None,
+            dispute_deposit: None,
+            reputation_half_life_days: None,
+            dispute_cooldown_seconds: None,
+            max_open_jobs_per_user: Some(2),
+        },
+    )
+    .unwrap();
+
+    let first = post_job(&mut deps, &env, CLIENT, 1000);
+    post_job(&mut deps, &env, CLIENT, 1000);
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, DENOM),
+        post_job_msg(1000),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::TooManyOpenJobs { max: 2 });
+
+    // Other posters have their own allowance
+    post_job(&mut deps, &env, "otherclient", 1000);
+
+    // Closing a job frees its slot
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::CancelJob { job_id: first },
+    )
+    .unwrap();
+    let third = post_job(&mut deps, &env, CLIENT, 1000);
+
+    // So do assigning one and deleting one, a day later to clear the posting rate limit
+    let mut env = env;
+    env.block.time = env.block.time.plus_days(1);
+    let proposal_id = submit_proposal(&mut deps, &env, FREELANCER, third);
+    accept_proposal(&mut deps, &env, CLIENT, third, proposal_id);
+    let fourth = post_job(&mut deps, &env, CLIENT, 1000);
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::DeleteJob { job_id: fourth },
+    )
+    .unwrap();
+    post_job(&mut deps, &env, CLIENT, 1000);
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, DENOM),
+        post_job_msg(1000),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::TooManyOpenJobs { max: 2 });
+}
+
+#[test]
+fn user_jobs_come_from_the_poster_index() {
+    let (mut deps, env) = setup_contract();
+
+    // Fill well past the old ten-job scan window with other posters' listings
+    for poster in ["posterone", "postertwo", "posterthree"] {
+        for _ in 0..4 {
+            post_job(&mut deps, &env, poster, 1000);
+        }
+    }
+    let first = post_job(&mut deps, &env, CLIENT, 1000);
+    let second = post_job(&mut deps, &env, CLIENT, 1000);
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::CancelJob { job_id: first },
+    )
+    .unwrap();
+
+    let user_jobs = |status| -> Vec<u64> {
+        let res: JobsResponse = query_as(
+            &deps,
+            &env,
+            QueryMsg::GetUserJobs {
+                user: CLIENT.to_string(),
+                status,
+            },
+        );
+        assert!(res.jobs.iter().all(|job| job.poster.as_str() == CLIENT));
+        res.jobs.into_iter().map(|job| job.id).collect()
+    };
+    assert_eq!(user_jobs(None), vec![second, first]);
+    assert_eq!(user_jobs(Some(JobStatus::Open)), vec![second]);
+    assert_eq!(user_jobs(Some(JobStatus::Cancelled)), vec![first]);
+}
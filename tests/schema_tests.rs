@@ -0,0 +1,51 @@
+use cosmwasm_schema::schema_for;
+use cosmwasm_std::to_json_string;
+use serde_json::Value;
+use xworks_freelance_contract::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+
+fn variant_names(schema: &Value) -> Vec<String> {
+    schema["oneOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|variant| {
+            variant["required"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .chain(variant["enum"].as_array().into_iter().flatten())
+                .filter_map(|name| name.as_str().map(str::to_string))
+        })
+        .collect()
+}
+
+#[test]
+fn message_schemas_generate_with_snake_case_names() {
+    let instantiate = serde_json::to_value(schema_for!(InstantiateMsg)).unwrap();
+    assert_eq!(instantiate["title"], "InstantiateMsg");
+
+    for schema in [
+        serde_json::to_value(schema_for!(ExecuteMsg)).unwrap(),
+        serde_json::to_value(schema_for!(QueryMsg)).unwrap(),
+    ] {
+        let names = variant_names(&schema);
+        assert!(!names.is_empty());
+        for name in names {
+            assert_eq!(
+                name,
+                name.to_lowercase(),
+                "variant {} is not snake_case",
+                name
+            );
+        }
+    }
+}
+
+#[test]
+fn messages_serialize_with_snake_case_variants() {
+    let msg = to_json_string(&QueryMsg::GetJob { job_id: 1 }).unwrap();
+    assert_eq!(msg, r#"{"get_job":{"job_id":1}}"#);
+
+    let msg = to_json_string(&ExecuteMsg::CancelJob { job_id: 1 }).unwrap();
+    assert_eq!(msg, r#"{"cancel_job":{"job_id":1}}"#);
+}
@@ -0,0 +1,368 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{
+    coins, to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+    StdError, StdResult, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_multi_test::{App, ContractWrapper, Executor};
+use xworks_freelance_contract::contract::{execute, instantiate, query, reply};
+use xworks_freelance_contract::msg::{
+    BountyResponse, ExecuteMsg, InstantiateMsg, QueryMsg, RateLimitStatusResponse, ReceiveMsg,
+    RewardTierInput,
+};
+use xworks_freelance_contract::security::{acquire_reentrancy_guard, release_reentrancy_guard};
+use xworks_freelance_contract::state::BountyStatus;
+use xworks_freelance_contract::ContractError;
+
+#[test]
+fn reentrant_complete_job_is_rejected() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    exec(
+        &mut deps,
+        &env,
+        FREELANCER,
+        &[],
+        ExecuteMsg::SubmitWork { job_id },
+    )
+    .unwrap();
+    let complete = ExecuteMsg::ApproveCompletion { job_id };
+
+    // Simulate a call arriving while a payout body still holds the guard
+    acquire_reentrancy_guard(deps.as_mut()).unwrap();
+    let err = exec(&mut deps, &env, CLIENT, &[], complete.clone()).unwrap_err();
+    assert_eq!(err, ContractError::ReentrancyDetected {});
+
+    release_reentrancy_guard(deps.as_mut()).unwrap();
+    let res = exec(&mut deps, &env, CLIENT, &[], complete).unwrap();
+    // Freelancer payout only; the platform fee accrues for withdrawal
+    assert_eq!(res.messages.len(), 1);
+
+    // The guard is cleared once the payout body finishes
+    post_job(&mut deps, &env, CLIENT, 1000);
+}
+
+// A CW20 token that pays out normally
+fn honest_token_execute(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Cw20ExecuteMsg,
+) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+// A CW20 token that calls back into the paying contract from inside the transfer
+fn reentrant_token_execute(
+    _deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    _msg: Cw20ExecuteMsg,
+) -> StdResult<Response> {
+    Ok(Response::new().add_message(WasmMsg::Execute {
+        contract_addr: info.sender.to_string(),
+        msg: to_json_binary(&ExecuteMsg::CancelBounty { bounty_id: 0 })?,
+        funds: vec![],
+    }))
+}
+
+fn token_instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn token_query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+    Err(StdError::generic_err("unsupported"))
+}
+
+/// Deploy the contract next to a token using `token_execute`, with one CW20 bounty posted
+fn app_with_cw20_bounty(
+    token_execute: fn(DepsMut, Env, MessageInfo, Cw20ExecuteMsg) -> StdResult<Response>,
+) -> (App, Addr) {
+    let mut app = App::default();
+    let code_id = app.store_code(Box::new(
+        ContractWrapper::new(execute, instantiate, query).with_reply(reply),
+    ));
+    let token_code_id = app.store_code(Box::new(ContractWrapper::new(
+        token_execute,
+        token_instantiate,
+        token_query,
+    )));
+    let token = app
+        .instantiate_contract(
+            token_code_id,
+            Addr::unchecked(ADMIN),
+            &Empty {},
+            &[],
+            "token",
+            None,
+        )
+        .unwrap();
+    let contract = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(ADMIN),
+            &InstantiateMsg {
+                allowed_cw20_tokens: Some(vec![token.to_string()]),
+                ..instantiate_msg()
+            },
+            &[],
+            "xworks",
+            None,
+        )
+        .unwrap();
+
+    let hook = ReceiveMsg::CreateBounty {
+        title: "Design a logo".to_string(),
+        description: "Logo for the XWorks brand".to_string(),
+        requirements: vec!["SVG output".to_string()],
+        category: "Design".to_string(),
+        skills_required: vec!["design".to_string()],
+        submission_deadline_days: 7,
+        review_period_days: 3,
+        max_winners: 1,
+        reward_distribution: vec![RewardTierInput {
+            position: 1,
+            percentage: 100,
+        }],
+        documents: None,
+    };
+    app.execute_contract(
+        token,
+        contract.clone(),
+        &ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: CLIENT.to_string(),
+            amount: Uint128::new(1000),
+            msg: to_json_binary(&hook).unwrap(),
+        }),
+        &[],
+    )
+    .unwrap();
+    (app, contract)
+}
+
+#[test]
+fn token_callback_during_payout_is_rejected() {
+    let (mut app, contract) = app_with_cw20_bounty(reentrant_token_execute);
+
+    // The refund transfer re-enters before the contract's reply has cleared the lock
+    let err = app
+        .execute_contract(
+            Addr::unchecked(CLIENT),
+            contract.clone(),
+            &ExecuteMsg::CancelBounty { bounty_id: 0 },
+            &[],
+        )
+        .unwrap_err();
+    assert!(err
+        .chain()
+        .any(|cause| cause.downcast_ref() == Some(&ContractError::ReentrancyDetected {})));
+
+    // The whole transaction reverted, so the bounty is still open and unlocked
+    let res: BountyResponse = app
+        .wrap()
+        .query_wasm_smart(&contract, &QueryMsg::GetBounty { bounty_id: 0 })
+        .unwrap();
+    assert_eq!(res.bounty.unwrap().status, BountyStatus::Open);
+}
+
+#[test]
+fn reply_clears_the_lock_after_payout() {
+    let (mut app, contract) = app_with_cw20_bounty(honest_token_execute);
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(CLIENT),
+            contract.clone(),
+            &ExecuteMsg::CancelBounty { bounty_id: 0 },
+            &[],
+        )
+        .unwrap();
+    assert!(res.events.iter().any(|event| event.ty == "reply"));
+
+    // A later transaction is not mistaken for re-entry
+    app.execute_contract(Addr::unchecked(CLIENT), contract, &post_job_msg(0), &[])
+        .unwrap();
+}
+
+fn post_job_with(title: &str, description: &str, skill: &str) -> ExecuteMsg {
+    let mut msg = post_job_msg(1000);
+    if let ExecuteMsg::PostJob {
+        title: t,
+        description: d,
+        skills_required,
+        ..
+    } = &mut msg
+    {
+        *t = title.to_string();
+        *d = description.to_string();
+        *skills_required = vec![skill.to_string()];
+    }
+    msg
+}
+
+#[test]
+fn control_characters_in_job_text_are_rejected() {
+    let (mut deps, env) = setup_contract();
+    let funds = coins(1000, DENOM);
+
+    for msg in [
+        post_job_with("Title with\0null", "Plain description", "rust"),
+        post_job_with("Title with\nbreak", "Plain description", "rust"),
+        post_job_with("Plain title", "Bell\u{7} in description", "rust"),
+        post_job_with("Plain title", "Plain description", "ru\u{1b}st"),
+    ] {
+        let err = exec(&mut deps, &env, CLIENT, &funds, msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidInput { .. }));
+    }
+
+    // Unicode text and multi-line descriptions are fine
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &funds,
+        post_job_with(
+            "Développeur Rust — 日本語 🚀",
+            "Line one\n\tLine two\r\nLine three",
+            "rust",
+        ),
+    )
+    .unwrap();
+}
+
+#[test]
+fn job_rate_limit_resets_after_a_day() {
+    let (mut deps, mut env) = setup_contract();
+    for _ in 0..5 {
+        post_job(&mut deps, &env, CLIENT, 1000);
+    }
+
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, DENOM),
+        post_job_msg(1000),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::RateLimitExceeded { .. }));
+
+    let status: RateLimitStatusResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetRateLimitStatus {
+            address: CLIENT.to_string(),
+        },
+    );
+    assert_eq!(status.current_count, 5);
+    assert!(status.is_limited);
+
+    // Once the window has lapsed the query reports a fresh window ...
+    env.block.time = env.block.time.plus_seconds(25 * 3600);
+    let status: RateLimitStatusResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetRateLimitStatus {
+            address: CLIENT.to_string(),
+        },
+    );
+    assert_eq!(status.current_count, 0);
+    assert!(!status.is_limited);
+
+    // ... and posting is allowed again
+    post_job(&mut deps, &env, CLIENT, 1000);
+    let status: RateLimitStatusResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetRateLimitStatus {
+            address: CLIENT.to_string(),
+        },
+    );
+    assert_eq!(status.current_count, 1);
+    assert_eq!(status.window_start, env.block.time);
+}
+
+#[test]
+fn rate_limit_status_reports_every_action() {
+    let (mut deps, env) = setup_contract();
+    let job_id = post_job(&mut deps, &env, CLIENT, 1000);
+    post_job(&mut deps, &env, CLIENT, 1000);
+    for _ in 0..3 {
+        create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+    }
+    submit_proposal(&mut deps, &env, FREELANCER, job_id);
+
+    let err = exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &coins(1000, DENOM),
+        create_bounty_msg(1000, &[100]),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::RateLimitExceeded { .. }));
+
+    let status: RateLimitStatusResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetRateLimitStatus {
+            address: CLIENT.to_string(),
+        },
+    );
+    let counters: Vec<(String, u64, u64, bool)> = status
+        .counters
+        .into_iter()
+        .map(|c| (c.action, c.count, c.limit, c.is_limited))
+        .collect();
+    // Bounties are what block the client, not jobs
+    assert!(!status.is_limited);
+    assert_eq!(
+        counters,
+        vec![
+            ("jobs".to_string(), 2, 5, false),
+            ("proposals".to_string(), 0, 20, false),
+            ("bounties".to_string(), 3, 3, true),
+            ("disputes".to_string(), 0, 2, false),
+            ("escrows".to_string(), 0, 10, false),
+            ("admin_actions".to_string(), 0, 50, false),
+        ]
+    );
+
+    let status: RateLimitStatusResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetRateLimitStatus {
+            address: FREELANCER.to_string(),
+        },
+    );
+    assert_eq!(status.counters[1].count, 1);
+}
+
+#[test]
+fn action_counters_unblock_once_the_window_lapses() {
+    let (mut deps, mut env) = setup_contract();
+    for _ in 0..3 {
+        create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+    }
+    let bounties_limited = |deps: &TestDeps, env: &cosmwasm_std::Env| {
+        let status: RateLimitStatusResponse = query_as(
+            deps,
+            env,
+            QueryMsg::GetRateLimitStatus {
+                address: CLIENT.to_string(),
+            },
+        );
+        status.counters[2].is_limited
+    };
+    assert!(bounties_limited(&deps, &env));
+
+    // Still inside the window a second before it ends
+    env.block.time = env.block.time.plus_seconds(24 * 3600 - 1);
+    assert!(bounties_limited(&deps, &env));
+
+    env.block.time = env.block.time.plus_seconds(1);
+    assert!(!bounties_limited(&deps, &env));
+    create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+}
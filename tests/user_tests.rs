@@ -0,0 +1,422 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{Addr, Decimal, Env};
+use xworks_freelance_contract::contract::query;
+use xworks_freelance_contract::msg::{
+    ExecuteMsg, QueryMsg, RatingsResponse, UserActivityResponse, UserProfileResponse,
+    UserReputationResponse, UserStatsResponse, VerifiedUsersResponse,
+};
+use xworks_freelance_contract::state::Rating;
+use xworks_freelance_contract::ContractError;
+
+fn set_verified(
+    deps: &mut TestDeps,
+    env: &Env,
+    sender: &str,
+    address: &str,
+    verified: bool,
+) -> Result<cosmwasm_std::Response, ContractError> {
+    exec(
+        deps,
+        env,
+        sender,
+        &[],
+        ExecuteMsg::SetUserVerified {
+            address: address.to_string(),
+            verified,
+        },
+    )
+}
+
+fn verified_users(deps: &TestDeps, env: &Env) -> Vec<Addr> {
+    let res: VerifiedUsersResponse = query_as(
+        deps,
+        env,
+        QueryMsg::GetVerifiedUsers {
+            start_after: None,
+            limit: None,
+        },
+    );
+    res.users
+}
+
+fn profile_verified(deps: &TestDeps, env: &Env, user: &str) -> bool {
+    let res: UserProfileResponse = query_as(
+        deps,
+        env,
+        QueryMsg::GetUserProfile {
+            user: user.to_string(),
+        },
+    );
+    res.profile.is_verified
+}
+
+#[test]
+fn admin_can_verify_and_unverify_users() {
+    let (mut deps, env) = setup_contract();
+
+    set_verified(&mut deps, &env, ADMIN, FREELANCER, true).unwrap();
+    set_verified(&mut deps, &env, ADMIN, CLIENT, true).unwrap();
+
+    assert!(profile_verified(&deps, &env, FREELANCER));
+    assert_eq!(
+        verified_users(&deps, &env),
+        vec![Addr::unchecked(CLIENT), Addr::unchecked(FREELANCER)]
+    );
+
+    let stats: UserStatsResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetUserStats {
+            user: FREELANCER.to_string(),
+        },
+    );
+    assert!(stats.is_verified);
+
+    set_verified(&mut deps, &env, ADMIN, FREELANCER, false).unwrap();
+
+    assert!(!profile_verified(&deps, &env, FREELANCER));
+    assert_eq!(verified_users(&deps, &env), vec![Addr::unchecked(CLIENT)]);
+
+    let page: VerifiedUsersResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetVerifiedUsers {
+            start_after: Some(CLIENT.to_string()),
+            limit: None,
+        },
+    );
+    assert!(page.users.is_empty());
+}
+
+#[test]
+fn only_admin_can_verify_users() {
+    let (mut deps, env) = setup_contract();
+
+    let err = set_verified(&mut deps, &env, CLIENT, CLIENT, true).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    assert!(verified_users(&deps, &env).is_empty());
+    assert!(!profile_verified(&deps, &env, CLIENT));
+}
+
+#[test]
+fn user_activity_aggregates_per_user_indexes() {
+    let (mut deps, env) = setup_contract();
+
+    // FREELANCER completes one job and bids on two more
+    let finished = assigned_job(&mut deps, &env, 1000);
+    complete_job(&mut deps, &env, finished);
+    exec(
+        &mut deps,
+        &env,
+        CLIENT,
+        &[],
+        ExecuteMsg::SubmitRating {
+            job_id: finished,
+            rating: 5,
+            comment: "Great work".to_string(),
+        },
+    )
+    .unwrap();
+    rate(&mut deps, &env, FREELANCER, finished, 4);
+
+    let open_jobs: Vec<u64> = (0..2)
+        .map(|_| post_job(&mut deps, &env, CLIENT, 1000))
+        .collect();
+    let latest_proposals: Vec<u64> = open_jobs
+        .iter()
+        .map(|job_id| submit_proposal(&mut deps, &env, FREELANCER, *job_id))
+        .collect();
+
+    let bounty_id = create_bounty(&mut deps, &env, CLIENT, 1000, &[100]);
+    let submission_id = submit_to_bounty(&mut deps, &env, FREELANCER, bounty_id);
+
+    let activity: UserActivityResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetUserActivity {
+            user: FREELANCER.to_string(),
+            limit: Some(2),
+        },
+    );
+    assert_eq!(activity.total_jobs, 0);
+    assert_eq!(activity.total_proposals, 3);
+    assert_eq!(activity.total_bounty_submissions, 1);
+    assert_eq!(activity.total_ratings, 1);
+
+    // Newest first, capped at the limit
+    let proposal_ids: Vec<u64> = activity.recent_proposals.iter().map(|p| p.id).collect();
+    assert_eq!(proposal_ids, vec![latest_proposals[1], latest_proposals[0]]);
+    assert_eq!(activity.recent_bounty_submissions[0].id, submission_id);
+    assert_eq!(activity.recent_ratings[0].rating, 5);
+    assert_eq!(activity.recent_ratings[0].job_id, finished);
+
+    let client: UserActivityResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetUserActivity {
+            user: CLIENT.to_string(),
+            limit: None,
+        },
+    );
+    assert_eq!(client.total_jobs, 3);
+    let job_ids: Vec<u64> = client.recent_jobs.iter().map(|j| j.id).collect();
+    assert_eq!(job_ids, vec![open_jobs[1], open_jobs[0], finished]);
+    assert_eq!(client.total_proposals, 0);
+    assert_eq!(client.total_ratings, 1);
+    assert_eq!(client.recent_ratings[0].rating, 4);
+}
+
+fn rate(deps: &mut TestDeps, env: &Env, rater: &str, job_id: u64, rating: u8) {
+    exec(
+        deps,
+        env,
+        rater,
+        &[],
+        ExecuteMsg::SubmitRating {
+            job_id,
+            rating,
+            comment: "Thanks".to_string(),
+        },
+    )
+    .unwrap();
+}
+
+fn rating_stats(deps: &TestDeps, env: &Env, user: &str) -> (Decimal, u64) {
+    let res: UserStatsResponse = query_as(
+        deps,
+        env,
+        QueryMsg::GetUserStats {
+            user: user.to_string(),
+        },
+    );
+    (res.stats.average_rating, res.stats.total_ratings)
+}
+
+fn job_rating(deps: &TestDeps, env: &Env, job_id: u64, rater: &str) -> Rating {
+    query_as(
+        deps,
+        env,
+        QueryMsg::GetJobRating {
+            job_id,
+            rater: rater.to_string(),
+        },
+    )
+}
+
+fn has_rated(deps: &TestDeps, env: &Env, job_id: u64, user: &str) -> bool {
+    query_as(
+        deps,
+        env,
+        QueryMsg::HasRated {
+            job_id,
+            user: user.to_string(),
+        },
+    )
+}
+
+#[test]
+fn has_rated_tracks_each_party_separately() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    complete_job(&mut deps, &env, job_id);
+    assert!(!has_rated(&deps, &env, job_id, CLIENT));
+    assert!(!has_rated(&deps, &env, job_id, FREELANCER));
+
+    // A rating held until the counterpart rates still counts
+    rate(&mut deps, &env, CLIENT, job_id, 4);
+    assert!(has_rated(&deps, &env, job_id, CLIENT));
+    assert!(!has_rated(&deps, &env, job_id, FREELANCER));
+    assert!(!has_rated(&deps, &env, job_id + 1, CLIENT));
+
+    rate(&mut deps, &env, FREELANCER, job_id, 5);
+    assert!(has_rated(&deps, &env, job_id, FREELANCER));
+}
+
+fn job_rating_hidden(deps: &TestDeps, env: &Env, job_id: u64, rater: &str) -> bool {
+    let msg = QueryMsg::GetJobRating {
+        job_id,
+        rater: rater.to_string(),
+    };
+    query(deps.as_ref(), env.clone(), msg).is_err()
+}
+
+#[test]
+fn ratings_are_revealed_once_both_parties_rate() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    complete_job(&mut deps, &env, job_id);
+
+    rate(&mut deps, &env, CLIENT, job_id, 4);
+    assert!(job_rating_hidden(&deps, &env, job_id, CLIENT));
+    let activity: UserActivityResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetUserActivity {
+            user: FREELANCER.to_string(),
+            limit: None,
+        },
+    );
+    assert!(activity.recent_ratings.is_empty());
+    assert_eq!(rating_stats(&deps, &env, FREELANCER), (Decimal::zero(), 0));
+    let res: RatingsResponse = query_as(
+        &deps,
+        &env,
+        QueryMsg::GetUserRatings {
+            user: FREELANCER.to_string(),
+        },
+    );
+    assert!(res.ratings.is_empty());
+
+    rate(&mut deps, &env, FREELANCER, job_id, 2);
+    assert!(!job_rating(&deps, &env, job_id, CLIENT).pending);
+    assert!(!job_rating(&deps, &env, job_id, FREELANCER).pending);
+    assert_eq!(
+        rating_stats(&deps, &env, FREELANCER),
+        (Decimal::from_ratio(4u128, 1u128), 1)
+    );
+    assert_eq!(
+        rating_stats(&deps, &env, CLIENT),
+        (Decimal::from_ratio(2u128, 1u128), 1)
+    );
+
+    // Nothing is left to finalize
+    let err = exec(
+        &mut deps,
+        &env,
+        ADMIN,
+        &[],
+        ExecuteMsg::FinalizeRatings { job_id },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::RatingError { .. }));
+}
+
+#[test]
+fn single_rating_is_revealed_after_the_window() {
+    let (mut deps, mut env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+    complete_job(&mut deps, &env, job_id);
+    rate(&mut deps, &env, CLIENT, job_id, 5);
+
+    // The window is still open
+    let finalize = ExecuteMsg::FinalizeRatings { job_id };
+    let err = exec(&mut deps, &env, ADMIN, &[], finalize.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::RatingError { .. }));
+    assert_eq!(rating_stats(&deps, &env, FREELANCER), (Decimal::zero(), 0));
+
+    // Anyone can reveal the rating once it lapses
+    env.block.time = env.block.time.plus_seconds(7 * 24 * 60 * 60);
+    exec(&mut deps, &env, ADMIN, &[], finalize).unwrap();
+    assert!(!job_rating(&deps, &env, job_id, CLIENT).pending);
+    assert_eq!(
+        rating_stats(&deps, &env, FREELANCER),
+        (Decimal::from_ratio(5u128, 1u128), 1)
+    );
+
+    // A late counterpart rating counts straight away
+    rate(&mut deps, &env, FREELANCER, job_id, 3);
+    assert_eq!(
+        rating_stats(&deps, &env, CLIENT),
+        (Decimal::from_ratio(3u128, 1u128), 1)
+    );
+    assert_eq!(
+        rating_stats(&deps, &env, FREELANCER),
+        (Decimal::from_ratio(5u128, 1u128), 1)
+    );
+}
+
+fn reputation(deps: &TestDeps, env: &Env, user: &str) -> UserReputationResponse {
+    query_as(
+        deps,
+        env,
+        QueryMsg::GetUserReputation {
+            user: user.to_string(),
+        },
+    )
+}
+
+#[test]
+fn older_ratings_weigh_less_in_reputation() {
+    let (mut deps, env) = setup_contract();
+    let early = assigned_job(&mut deps, &env, 1000);
+    let recent = assigned_job(&mut deps, &env, 1000);
+
+    complete_job(&mut deps, &env, early);
+    rate(&mut deps, &env, CLIENT, early, 5);
+    rate(&mut deps, &env, FREELANCER, early, 5);
+    let rep = reputation(&deps, &env, FREELANCER);
+    assert_eq!(rep.reputation, Decimal::from_ratio(5u128, 1u128));
+    assert_eq!(rep.half_life_days, 180);
+
+    // Two half-lives later the early rating counts a quarter as much
+    let mut later = env.clone();
+    later.block.time = later.block.time.plus_seconds(360 * 24 * 60 * 60);
+    complete_job(&mut deps, &later, recent);
+    rate(&mut deps, &later, CLIENT, recent, 1);
+    rate(&mut deps, &later, FREELANCER, recent, 5);
+
+    let rep = reputation(&deps, &later, FREELANCER);
+    assert_eq!(rep.ratings_considered, 2);
+    // (5 * 0.25 + 1 * 1) / 1.25
+    assert_eq!(rep.reputation, Decimal::from_ratio(9u128, 5u128));
+    let (average, _) = rating_stats(&deps, &later, FREELANCER);
+    assert_eq!(average, Decimal::from_ratio(3u128, 1u128));
+}
+
+fn job_counts(deps: &TestDeps, env: &Env, user: &str) -> (u64, u64, Decimal) {
+    let res: UserStatsResponse = query_as(
+        deps,
+        env,
+        QueryMsg::GetUserStats {
+            user: user.to_string(),
+        },
+    );
+    (
+        res.stats.total_jobs_assigned,
+        res.stats.total_jobs_completed,
+        res.stats.completion_rate,
+    )
+}
+
+#[test]
+fn job_completion_is_counted_only_when_the_job_completes() {
+    let (mut deps, env) = setup_contract();
+    let job_id = assigned_job(&mut deps, &env, 1000);
+
+    // Acceptance counts as an assignment, not a completion
+    assert_eq!(job_counts(&deps, &env, FREELANCER), (1, 0, Decimal::zero()));
+
+    exec(
+        &mut deps,
+        &env,
+        FREELANCER,
+        &[],
+        ExecuteMsg::SubmitWork { job_id },
+    )
+    .unwrap();
+    assert_eq!(job_counts(&deps, &env, FREELANCER).1, 0);
+
+    let mut later = env.clone();
+    later.block.time = later.block.time.plus_seconds(3 * 24 * 60 * 60);
+    exec(
+        &mut deps,
+        &later,
+        FREELANCER,
+        &[],
+        ExecuteMsg::CompleteJob { job_id },
+    )
+    .unwrap();
+    assert_eq!(
+        job_counts(&deps, &later, FREELANCER),
+        (1, 1, Decimal::one())
+    );
+
+    // A second assignment halves the rate until it is delivered
+    assigned_job(&mut deps, &later, 1000);
+    assert_eq!(
+        job_counts(&deps, &later, FREELANCER),
+        (2, 1, Decimal::percent(50))
+    );
+}